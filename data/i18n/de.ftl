@@ -0,0 +1,130 @@
+# Ausgabetexte auf Deutsch. Fehlende Meldungen werden auf Englisch angezeigt.
+
+## Model table columns
+
+col-model = Modell
+col-name = Name
+col-provider = Anbieter
+col-input = Eingabe
+col-output = Ausgabe
+col-cache-read = Cache-Lesen
+col-cache-write = Cache-Schreiben
+col-request = Anfrage
+col-image = Bild
+col-context = Kontext
+col-max-completion = Max. Ausgabe
+col-created = Erstellt
+col-tokenizer = Tokenizer
+col-modality = Modalität
+col-license = Lizenz
+col-status = Status
+col-approval = Freigabe
+col-notes = Notizen
+col-tags = Tags
+col-tokens-per-dollar = Tokens/$
+col-gpu-hours = { $gpu }-Std./1M
+col-self-host = Eigenbetrieb/1M
+col-params = Parameter (Mrd.)
+col-family = Familie
+col-released = Veröffentlicht
+col-trend = Trend
+col-carbon = gCO2e/1M
+col-region = Region
+col-models = Modelle
+col-group = Gruppe
+col-min-input = Min. Eingabe
+col-avg-input = Ø Eingabe
+col-max-input = Max. Eingabe
+col-min-output = Min. Ausgabe
+col-avg-output = Ø Ausgabe
+col-max-output = Max. Ausgabe
+
+## Cost calculation
+
+col-total = Gesamt
+col-top-up-cost = Aufladekosten
+calc-heading = Kostenberechnung: { $input } Eingabe + { $output } Ausgabe
+calc-cached = ({ $cached } aus dem Cache, TTL { $ttl })
+calc-tier = in der Stufe { $tier }
+calc-tier-note = Preise in der Stufe { $tier }; Modelle ohne diese Stufe werden nicht angezeigt
+sweep-heading = Kostenvergleich: { $input } Eingabe + { $output } Ausgabe über { $scenarios } Szenarien
+sweep-sheet = Kostenvergleich
+col-ttl = TTL
+col-cached = Im Cache
+col-best = Beste
+cached-none = keine
+region-unknown = unbekannt
+credits-note = 1 Credit = 1,00 $. { $fee }
+fee-card = Beim Aufladen per Karte fallen 5,5 % Gebühr an (mindestens 0,80 $ pro Kauf).
+fee-crypto = Beim Aufladen mit Kryptowährung fallen 5 % Gebühr an.
+prices-from = Preise von { $source }
+page-title = LLM-Preise
+prices-per-source = Preise in USD pro { $unit } von { $source }
+prices-per = Preise in USD pro { $unit }.
+
+rates-heading = Angewendete Preise
+rate-cache-write-note = ({ $multiplier }x Eingabepreis, TTL { $ttl })
+
+## Units
+
+unit-token = Token
+unit-thousand = 1K Tokens
+unit-million = 1M Tokens
+tokens = { $tokens } Tokens
+unknown = unbekannt
+
+## Rate cards
+
+rate-card-title = Preisliste
+generated = Erstellt am { $date }
+rate-card-footer = Preise in USD pro { $unit } von { $source }, abgerufen { $fetched }. Preise können vom Anbieter geändert werden.
+page-of = Seite { $page } von { $pages }
+
+## Quotes
+
+quote-title = Kostenangebot: { $model }
+quote-prepared = Erstellt am { $date }
+quote-footer = Preise in USD pro { $unit } von { $source }, abgerufen { $fetched }. Die Preise legt der Anbieter fest; sie können sich nach diesem Datum ändern.
+col-detail = Angabe
+col-value = Wert
+detail-max-completion = Max. Ausgabe
+detail-note = Notiz
+col-item = Position
+col-tokens = Tokens
+col-rate = Preis
+col-cost = Kosten
+item-cache-read = Cache-Lesen
+item-cache-write = Cache-Schreiben
+item-cache-write-multiplier = Cache-Schreiben ({ $multiplier }x Eingabe)
+quote-total = Gesamt pro Anfrage
+quote-total-requests = Gesamt für { $requests } Anfragen
+
+
+## Invoices
+
+invoice-title = Rechnung { $number }
+invoice-issued = Ausgestellt am { $date }
+invoice-period = Zeitraum: { $period }
+invoice-bill-to = Rechnungsempfänger: { $client }
+invoice-footer = Nutzung berechnet zu den Preisen von { $source }, abgerufen { $fetched }. Beträge in USD.
+col-requests = Anfragen
+col-input-tokens = Eingabe-Tokens
+col-output-tokens = Ausgabe-Tokens
+col-cached-tokens = Gecachte Tokens
+col-amount = Betrag (USD)
+
+## Setup questions
+
+wizard-welcome = Willkommen bei llm-pricing! Ein paar Fragen zu Ihren Standardeinstellungen.
+wizard-defaults = Drücken Sie die Eingabetaste, um den Standardwert in Klammern zu übernehmen.
+wizard-output = Standard-Ausgabeformat ({ $formats }) [table]:
+wizard-choose = Bitte wählen Sie eines von: { $formats }
+wizard-cache = Abgerufene Preise bis zu { $duration } wiederverwenden, statt sie bei jedem Aufruf abzurufen?
+wizard-store-key = Einen OpenRouter-API-Schlüssel im Schlüsselbund des Betriebssystems speichern? (für öffentliche Preise nicht nötig)
+wizard-stored = Im Schlüsselbund des Betriebssystems gespeichert
+wizard-store-failed = Der Schlüssel konnte nicht gespeichert werden ({ $error }); setzen Sie stattdessen { $variable }
+wizard-saved = Einstellungen gespeichert in { $path }
+wizard-change-later = Mit `llm-pricing init` können Sie sie später ändern.
+wizard-yes-no = Bitte mit j oder n antworten
+wizard-yes = j
+wizard-no = n
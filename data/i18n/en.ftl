@@ -0,0 +1,132 @@
+# Output text in English. Every message used by the tool must be defined here; other
+# languages fall back to these for any message they leave out.
+
+## Model table columns
+
+col-model = Model
+col-name = Name
+col-provider = Provider
+col-input = Input
+col-output = Output
+col-cache-read = Cache Read
+col-cache-write = Cache Write
+col-request = Request
+col-image = Image
+col-context = Context
+col-max-completion = Max Completion
+col-created = Created
+col-tokenizer = Tokenizer
+col-modality = Modality
+col-license = License
+col-status = Status
+col-approval = Approval
+col-notes = Notes
+col-tags = Tags
+col-tokens-per-dollar = Tokens/$
+col-gpu-hours = { $gpu }-hrs/1M
+col-self-host = Self-host/1M
+col-params = Params (B)
+col-family = Family
+col-released = Released
+col-trend = Trend
+col-carbon = gCO2e/1M
+col-region = Region
+col-models = Models
+col-group = Group
+col-min-input = Min Input
+col-avg-input = Avg Input
+col-max-input = Max Input
+col-min-output = Min Output
+col-avg-output = Avg Output
+col-max-output = Max Output
+
+## Cost calculation
+
+col-total = Total
+col-top-up-cost = Top-up Cost
+calc-heading = Cost calculation: { $input } input + { $output } output
+calc-cached = ({ $cached } cached, { $ttl } TTL)
+calc-tier = at the { $tier } tier
+calc-tier-note = Pricing at the { $tier } tier; models that don't offer it are not shown
+sweep-heading = Cost sweep: { $input } input + { $output } output across { $scenarios } scenarios
+sweep-sheet = Cost sweep
+col-ttl = TTL
+col-cached = Cached
+col-best = Best
+cached-none = none
+region-unknown = unknown
+credits-note = 1 credit = $1.00. { $fee }
+fee-card = Card top-ups add a 5.5% fee ($0.80 minimum per purchase).
+fee-crypto = Crypto top-ups add a 5% fee.
+prices-from = Prices from { $source }
+page-title = LLM Pricing
+prices-per-source = Prices in USD per { $unit } from { $source }
+prices-per = Prices in USD per { $unit }.
+
+rates-heading = Rates applied
+rate-cache-write-note = ({ $multiplier }x input price, { $ttl } TTL)
+
+## Units
+
+unit-token = token
+unit-thousand = 1K tokens
+unit-million = 1M tokens
+tokens = { $tokens } tokens
+unknown = unknown
+
+## Rate cards
+
+rate-card-title = Rate Card
+generated = Generated { $date }
+rate-card-footer = Prices in USD per { $unit } from { $source }, fetched { $fetched }. Prices are subject to change by the provider.
+page-of = Page { $page } of { $pages }
+
+## Quotes
+
+quote-title = Cost quote: { $model }
+quote-prepared = Prepared { $date }
+quote-footer = Prices in USD per { $unit } from { $source }, fetched { $fetched }. Prices are set by the provider and may change after this date.
+col-detail = Detail
+col-value = Value
+detail-max-completion = Max completion
+detail-note = Note
+col-item = Item
+col-tokens = Tokens
+col-rate = Rate
+col-cost = Cost
+item-cache-read = Cache read
+item-cache-write = Cache write
+item-cache-write-multiplier = Cache write ({ $multiplier }x input)
+quote-total = Total per request
+quote-total-requests = Total for { $requests } requests
+
+
+## Invoices
+
+invoice-title = Invoice { $number }
+invoice-issued = Issued { $date }
+invoice-period = Period: { $period }
+invoice-bill-to = Bill to: { $client }
+invoice-footer = Usage priced at { $source } rates fetched { $fetched }. Amounts in USD.
+col-requests = Requests
+col-input-tokens = Input Tokens
+col-output-tokens = Output Tokens
+col-cached-tokens = Cached Tokens
+col-amount = Amount (USD)
+
+## Setup questions
+
+wizard-welcome = Welcome to llm-pricing! A couple of questions to set your defaults.
+wizard-defaults = Press Enter to accept the default shown in brackets.
+wizard-output = Default output format ({ $formats }) [table]:
+wizard-choose = Please choose one of: { $formats }
+wizard-cache = Reuse fetched prices for up to { $duration } instead of fetching on every run?
+wizard-store-key = Store an OpenRouter API key in the OS keychain? (not needed for public prices)
+wizard-stored = Stored in the OS keychain
+wizard-store-failed = Could not store the key ({ $error }); set { $variable } instead
+wizard-saved = Saved settings to { $path }
+wizard-change-later = Run `llm-pricing init` to change them later.
+wizard-yes-no = Please answer y or n
+# The first letter of each answer, shown as [Y/n] and accepted alongside y and n
+wizard-yes = y
+wizard-no = n
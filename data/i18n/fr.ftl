@@ -0,0 +1,130 @@
+# Textes de sortie en français. Les messages manquants sont affichés en anglais.
+
+## Model table columns
+
+col-model = Modèle
+col-name = Nom
+col-provider = Fournisseur
+col-input = Entrée
+col-output = Sortie
+col-cache-read = Lecture cache
+col-cache-write = Écriture cache
+col-request = Requête
+col-image = Image
+col-context = Contexte
+col-max-completion = Sortie max.
+col-created = Créé
+col-tokenizer = Tokeniseur
+col-modality = Modalité
+col-license = Licence
+col-status = Statut
+col-approval = Approbation
+col-notes = Notes
+col-tags = Étiquettes
+col-tokens-per-dollar = Tokens/$
+col-gpu-hours = h { $gpu }/1M
+col-self-host = Auto-hébergé/1M
+col-params = Paramètres (Md)
+col-family = Famille
+col-released = Publié
+col-trend = Tendance
+col-carbon = gCO2e/1M
+col-region = Région
+col-models = Modèles
+col-group = Groupe
+col-min-input = Entrée min
+col-avg-input = Entrée moy.
+col-max-input = Entrée max
+col-min-output = Sortie min
+col-avg-output = Sortie moy.
+col-max-output = Sortie max
+
+## Cost calculation
+
+col-total = Total
+col-top-up-cost = Coût de recharge
+calc-heading = Calcul du coût : { $input } en entrée + { $output } en sortie
+calc-cached = ({ $cached } en cache, TTL { $ttl })
+calc-tier = au niveau { $tier }
+calc-tier-note = Tarifs du niveau { $tier } ; les modèles qui ne le proposent pas ne sont pas affichés
+sweep-heading = Balayage des coûts : { $input } en entrée + { $output } en sortie sur { $scenarios } scénarios
+sweep-sheet = Balayage des coûts
+col-ttl = TTL
+col-cached = En cache
+col-best = Meilleur
+cached-none = aucun
+region-unknown = inconnue
+credits-note = 1 crédit = 1,00 $. { $fee }
+fee-card = Les recharges par carte ajoutent des frais de 5,5 % (0,80 $ minimum par achat).
+fee-crypto = Les recharges en cryptomonnaie ajoutent des frais de 5 %.
+prices-from = Prix issus de { $source }
+page-title = Tarifs LLM
+prices-per-source = Prix en USD par { $unit } issus de { $source }
+prices-per = Prix en USD par { $unit }.
+
+rates-heading = Tarifs appliqués
+rate-cache-write-note = ({ $multiplier }x le prix d'entrée, TTL { $ttl })
+
+## Units
+
+unit-token = token
+unit-thousand = 1K tokens
+unit-million = 1M tokens
+tokens = { $tokens } tokens
+unknown = inconnu
+
+## Rate cards
+
+rate-card-title = Grille tarifaire
+generated = Générée le { $date }
+rate-card-footer = Prix en USD par { $unit } issus de { $source }, récupérés le { $fetched }. Les prix peuvent être modifiés par le fournisseur.
+page-of = Page { $page } sur { $pages }
+
+## Quotes
+
+quote-title = Devis : { $model }
+quote-prepared = Établi le { $date }
+quote-footer = Prix en USD par { $unit } issus de { $source }, récupérés le { $fetched }. Les prix sont fixés par le fournisseur et peuvent changer après cette date.
+col-detail = Détail
+col-value = Valeur
+detail-max-completion = Sortie max.
+detail-note = Note
+col-item = Poste
+col-tokens = Tokens
+col-rate = Tarif
+col-cost = Coût
+item-cache-read = Lecture cache
+item-cache-write = Écriture cache
+item-cache-write-multiplier = Écriture cache ({ $multiplier }x l'entrée)
+quote-total = Total par requête
+quote-total-requests = Total pour { $requests } requêtes
+
+
+## Invoices
+
+invoice-title = Facture { $number }
+invoice-issued = Émise le { $date }
+invoice-period = Période : { $period }
+invoice-bill-to = Facturé à : { $client }
+invoice-footer = Utilisation tarifée aux prix de { $source }, récupérés le { $fetched }. Montants en USD.
+col-requests = Requêtes
+col-input-tokens = Jetons d'entrée
+col-output-tokens = Jetons de sortie
+col-cached-tokens = Jetons en cache
+col-amount = Montant (USD)
+
+## Setup questions
+
+wizard-welcome = Bienvenue dans llm-pricing ! Quelques questions pour définir vos préférences.
+wizard-defaults = Appuyez sur Entrée pour accepter la valeur par défaut entre crochets.
+wizard-output = Format de sortie par défaut ({ $formats }) [table] :
+wizard-choose = Veuillez choisir parmi : { $formats }
+wizard-cache = Réutiliser les prix récupérés pendant { $duration } au lieu de les récupérer à chaque exécution ?
+wizard-store-key = Enregistrer une clé API OpenRouter dans le trousseau du système ? (inutile pour les prix publics)
+wizard-stored = Enregistrée dans le trousseau du système
+wizard-store-failed = Impossible d'enregistrer la clé ({ $error }) ; définissez plutôt { $variable }
+wizard-saved = Préférences enregistrées dans { $path }
+wizard-change-later = Lancez `llm-pricing init` pour les modifier plus tard.
+wizard-yes-no = Veuillez répondre o ou n
+wizard-yes = o
+wizard-no = n
@@ -0,0 +1,128 @@
+# 日本語の出力テキスト。ここにないメッセージは英語で表示されます。
+
+## Model table columns
+
+col-model = モデル
+col-name = 名前
+col-provider = プロバイダー
+col-input = 入力
+col-output = 出力
+col-cache-read = キャッシュ読み取り
+col-cache-write = キャッシュ書き込み
+col-request = リクエスト
+col-image = 画像
+col-context = コンテキスト
+col-max-completion = 最大出力
+col-created = 作成日
+col-tokenizer = トークナイザー
+col-modality = モダリティ
+col-license = ライセンス
+col-status = ステータス
+col-approval = 承認
+col-notes = メモ
+col-tags = タグ
+col-tokens-per-dollar = トークン/$
+col-gpu-hours = { $gpu }時間/1M
+col-self-host = セルフホスト/1M
+col-params = パラメータ (B)
+col-family = ファミリー
+col-released = リリース日
+col-trend = 推移
+col-carbon = gCO2e/1M
+col-region = リージョン
+col-models = モデル数
+col-group = グループ
+col-min-input = 入力 最小
+col-avg-input = 入力 平均
+col-max-input = 入力 最大
+col-min-output = 出力 最小
+col-avg-output = 出力 平均
+col-max-output = 出力 最大
+
+## Cost calculation
+
+col-total = 合計
+col-top-up-cost = チャージ費用
+calc-heading = コスト計算: 入力 { $input } + 出力 { $output }
+calc-cached = (キャッシュ { $cached }、TTL { $ttl })
+calc-tier = { $tier } ティア
+calc-tier-note = { $tier } ティアの価格です。このティアを提供していないモデルは表示されません
+sweep-heading = コスト比較: 入力 { $input } + 出力 { $output }、{ $scenarios } 通りのシナリオ
+sweep-sheet = コスト比較
+col-ttl = TTL
+col-cached = キャッシュ
+col-best = 最安
+cached-none = なし
+region-unknown = 不明
+credits-note = 1 クレジット = $1.00。{ $fee }
+fee-card = カードでのチャージには 5.5% の手数料がかかります (1 回あたり最低 $0.80)。
+fee-crypto = 暗号資産でのチャージには 5% の手数料がかかります。
+prices-from = 価格の出典: { $source }
+page-title = LLM 料金
+prices-per-source = 価格は { $unit } あたりの米ドル、{ $source } より
+prices-per = 価格は { $unit } あたりの米ドルです。
+
+rates-heading = 適用された料金
+rate-cache-write-note = (入力価格の { $multiplier } 倍、TTL { $ttl })
+
+## Units
+
+unit-token = トークン
+unit-thousand = 1K トークン
+unit-million = 1M トークン
+tokens = { $tokens } トークン
+unknown = 不明
+
+## Rate cards
+
+rate-card-title = 料金表
+generated = 作成日 { $date }
+rate-card-footer = 価格は { $unit } あたりの米ドルで、{ $source } から { $fetched } に取得したものです。価格はプロバイダーにより変更される場合があります。
+page-of = { $page } / { $pages } ページ
+
+## Quotes
+
+quote-title = 見積もり: { $model }
+quote-prepared = 作成日時 { $date }
+quote-footer = 価格は { $unit } あたりの米ドルで、{ $source } から { $fetched } に取得したものです。価格はプロバイダーが設定し、この日付以降に変更される場合があります。
+col-detail = 項目
+col-value = 値
+detail-max-completion = 最大出力
+detail-note = メモ
+col-item = 明細
+col-tokens = トークン
+col-rate = 単価
+col-cost = 費用
+item-cache-read = キャッシュ読み取り
+item-cache-write = キャッシュ書き込み
+item-cache-write-multiplier = キャッシュ書き込み (入力の { $multiplier } 倍)
+quote-total = 1 リクエストあたりの合計
+quote-total-requests = { $requests } リクエストの合計
+
+
+## Invoices
+
+invoice-title = 請求書 { $number }
+invoice-issued = 発行日 { $date }
+invoice-period = 請求期間: { $period }
+invoice-bill-to = 請求先: { $client }
+invoice-footer = 利用量は { $source } から { $fetched } に取得した料金で計算しています。金額は米ドルです。
+col-requests = リクエスト数
+col-input-tokens = 入力トークン
+col-output-tokens = 出力トークン
+col-cached-tokens = キャッシュトークン
+col-amount = 金額 (USD)
+
+## Setup questions
+
+wizard-welcome = llm-pricing へようこそ!既定の設定についていくつか質問します。
+wizard-defaults = かっこ内の既定値を使う場合は Enter を押してください。
+wizard-output = 既定の出力形式 ({ $formats }) [table]:
+wizard-choose = 次のいずれかを選んでください: { $formats }
+wizard-cache = 取得した価格を毎回取得し直さず、最大 { $duration } 再利用しますか?
+wizard-store-key = OpenRouter の API キーを OS のキーチェーンに保存しますか?(公開価格には不要です)
+wizard-stored = OS のキーチェーンに保存しました
+wizard-store-failed = キーを保存できませんでした ({ $error })。代わりに { $variable } を設定してください
+wizard-saved = 設定を { $path } に保存しました
+wizard-change-later = 後で変更するには `llm-pricing init` を実行してください。
+wizard-yes-no = y または n で答えてください
@@ -0,0 +1,116 @@
+use crate::{
+    auth::{self, DEFAULT_PROVIDER},
+    config::Config,
+    table::{Cell, Table},
+};
+use anyhow::Context;
+use serde::Deserialize;
+
+const KEY_URL: &str = "https://openrouter.ai/api/v1/key";
+
+#[derive(Deserialize)]
+struct KeyResponse {
+    data: KeyInfo,
+}
+
+/// Credits used by an OpenRouter key and its spending limit, in dollars
+#[derive(Deserialize)]
+pub struct KeyInfo {
+    #[serde(default)]
+    label: String,
+    usage: f64,
+    /// None when the key has no limit
+    limit: Option<f64>,
+    limit_remaining: Option<f64>,
+}
+
+/// What the key endpoint reports for `key`; fails when OpenRouter rejects the key
+pub async fn fetch_key_info(client: &reqwest::Client, key: &str) -> anyhow::Result<KeyInfo> {
+    let response = client
+        .get(KEY_URL)
+        .bearer_auth(key)
+        .send()
+        .await?
+        .error_for_status()?;
+    let info: KeyResponse = response
+        .json()
+        .await
+        .context("Unexpected response from the OpenRouter key endpoint")?;
+    Ok(info.data)
+}
+
+/// Credit usage and limits for the default OpenRouter key and every named key, with totals
+/// across all of them, or None when no keys are configured
+pub async fn usage_table() -> anyhow::Result<Option<Table>> {
+    let config = Config::load()?;
+    let mut keys = Vec::new();
+    if let Some((key, _)) = auth::api_key(DEFAULT_PROVIDER)? {
+        keys.push(("default".to_string(), key));
+    }
+    for name in config.keys.get(DEFAULT_PROVIDER).into_iter().flatten() {
+        match auth::stored_key(DEFAULT_PROVIDER, Some(name)) {
+            Ok(Some(key)) => keys.push((name.clone(), key)),
+            Ok(None) => eprintln!(
+                "Warning: no key stored for '{name}'. Add it with 'llm-pricing auth set --name {name}'"
+            ),
+            Err(e) => eprintln!("Warning: couldn't read key '{name}': {e:#}"),
+        }
+    }
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let client = reqwest::Client::new();
+    let mut table = Table::new(["Key", "Label", "Usage", "Limit", "Remaining"]);
+    let mut usage = 0.0;
+    // Limits only add up when every key has one
+    let mut limit = Some(0.0);
+    let mut remaining = Some(0.0);
+    for (name, key) in &keys {
+        let info = match fetch_key_info(&client, key).await {
+            Ok(info) => info,
+            Err(e) => {
+                eprintln!("Warning: couldn't fetch usage for key '{name}': {e:#}");
+                continue;
+            }
+        };
+        usage += info.usage;
+        limit = limit.zip(info.limit).map(|(total, value)| total + value);
+        remaining = remaining
+            .zip(info.limit_remaining)
+            .map(|(total, value)| total + value);
+        table.push(vec![
+            Cell::text(name),
+            Cell::text(info.label),
+            Cell::Cost(info.usage),
+            info.limit.map_or(Cell::Missing, Cell::Cost),
+            info.limit_remaining.map_or(Cell::Missing, Cell::Cost),
+        ]);
+    }
+    if table.rows.is_empty() {
+        return Err(anyhow::anyhow!("Couldn't fetch usage for any OpenRouter key"));
+    }
+
+    if table.rows.len() > 1 {
+        table.footer.push(vec![
+            Cell::text("Total"),
+            Cell::text(""),
+            Cell::Cost(usage),
+            limit.map_or(Cell::Missing, Cell::Cost),
+            remaining.map_or(Cell::Missing, Cell::Cost),
+        ]);
+    }
+    Ok(Some(table))
+}
+
+/// Show credit usage and limits for every configured OpenRouter key
+pub async fn run() -> anyhow::Result<()> {
+    match usage_table().await? {
+        Some(table) => table.print(),
+        None => println!(
+            "No OpenRouter API keys configured. Add one with 'llm-pricing auth set', or a \
+             team's key with 'llm-pricing auth set --name <NAME>'"
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use crate::{
+    config::{AlertRule, Config},
+    history, matches_name_filters, model_link, parse_duration, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::{TimeDelta, Utc};
+
+/// How far back change rules look when they don't say
+const DEFAULT_WITHIN: &str = "1d";
+
+/// A price per token as USD per million tokens, rounded to hide floating-point noise. Routers
+/// report variable pricing as -1, which isn't a price to alert on
+fn per_million(price: &str) -> Option<f64> {
+    let price: f64 = price.parse().ok().filter(|price| *price >= 0.0)?;
+    Some((price * 1e6 * 1e6).round() / 1e6)
+}
+
+/// The rule's conditions as one line, e.g. "input below $2/M, change over 20% in 1d"
+fn describe(rule: &AlertRule) -> String {
+    let mut conditions = Vec::new();
+    let thresholds = [
+        ("input below", rule.input_below),
+        ("input above", rule.input_above),
+        ("output below", rule.output_below),
+        ("output above", rule.output_above),
+    ];
+    for (label, threshold) in thresholds {
+        if let Some(threshold) = threshold {
+            conditions.push(format!("{label} ${threshold}/M"));
+        }
+    }
+    if let Some(percent) = rule.change_percent {
+        let within = rule.within.as_deref().unwrap_or(DEFAULT_WITHIN);
+        conditions.push(format!("change over {percent}% in {within}"));
+    }
+    conditions.join(", ")
+}
+
+/// Save a rule under `name`, replacing any rule already saved under it
+pub fn add(name: &str, rule: AlertRule) -> anyhow::Result<()> {
+    if describe(&rule).is_empty() {
+        return Err(anyhow::anyhow!(
+            "Give at least one condition: --input-below, --input-above, --output-below, \
+             --output-above or --change"
+        ));
+    }
+    if let Some(within) = &rule.within {
+        if rule.change_percent.is_none() {
+            return Err(anyhow::anyhow!("--within only applies to --change"));
+        }
+        parse_duration(within)?;
+    }
+    let mut config = Config::load()?;
+    let summary = format!(
+        "{} ({})",
+        rule.model.as_deref().unwrap_or("any model"),
+        describe(&rule)
+    );
+    let replaced = config.alerts.insert(name.to_string(), rule).is_some();
+    let path = config.save()?;
+    let verb = if replaced { "Replaced" } else { "Added" };
+    println!("{verb} alert '{name}': {summary} in {}", path.display());
+    Ok(())
+}
+
+pub fn remove(name: &str) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    if config.alerts.remove(name).is_none() {
+        return Err(anyhow::anyhow!(
+            "No alert named '{name}'. Use 'llm-pricing alert list' to list alerts"
+        ));
+    }
+    let path = config.save()?;
+    println!("Removed alert '{}' from {}", name, path.display());
+    Ok(())
+}
+
+pub fn list() -> anyhow::Result<()> {
+    let config = Config::load()?;
+    if config.alerts.is_empty() {
+        println!("No alerts saved. Add one with 'llm-pricing alert add <NAME> --model <MODEL> --input-below <PRICE>'");
+        return Ok(());
+    }
+
+    let mut table = Table::new(["Name", "Models", "Conditions"]);
+    for (name, rule) in &config.alerts {
+        table.push(vec![
+            Cell::text(name),
+            Cell::text(rule.model.as_deref().unwrap_or("any")),
+            Cell::text(describe(rule)),
+        ]);
+    }
+    table.print();
+    Ok(())
+}
+
+/// One condition of a rule that holds for a model
+struct Trigger<'a> {
+    rule: &'a str,
+    model: &'a str,
+    condition: String,
+    detail: String,
+}
+
+/// Evaluate the saved rules against current prices and print each model and condition that
+/// triggered. Returns whether any did.
+pub fn check(
+    models: &[Model],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<bool> {
+    let config = Config::load()?;
+    if config.alerts.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No alerts saved. Add one with 'llm-pricing alert add <NAME> ...'"
+        ));
+    }
+
+    let mut triggers = Vec::new();
+    let mut unmatched = Vec::new();
+    for (name, rule) in &config.alerts {
+        let covered: Vec<&Model> = match &rule.model {
+            Some(filter) => models
+                .iter()
+                .filter(|model| matches_name_filters(model, &[filter.to_lowercase()]))
+                .collect(),
+            None => models.iter().collect(),
+        };
+        if covered.is_empty() {
+            unmatched.push(name.as_str());
+            continue;
+        }
+
+        for model in &covered {
+            let prices = [
+                ("input", per_million(&model.pricing.prompt), rule.input_below, rule.input_above),
+                ("output", per_million(&model.pricing.completion), rule.output_below, rule.output_above),
+            ];
+            for (side, price, below, above) in prices {
+                let Some(price) = price else {
+                    continue;
+                };
+                let crossed = [("below", below.filter(|below| price < *below)), ("above", above.filter(|above| price > *above))];
+                for (direction, threshold) in crossed {
+                    if let Some(threshold) = threshold {
+                        triggers.push(Trigger {
+                            rule: name,
+                            model: &model.id,
+                            condition: format!("{side} {direction} ${threshold}/M"),
+                            detail: format!("{side} is ${price}/M"),
+                        });
+                    }
+                }
+            }
+        }
+
+        let Some(percent) = rule.change_percent else {
+            continue;
+        };
+        let within = rule.within.as_deref().unwrap_or(DEFAULT_WITHIN);
+        let since = Utc::now().checked_sub_signed(parse_duration(within)?);
+        let Some((fetched_at, before)) = since.map(history::prices_at).transpose()?.flatten() else {
+            eprintln!(
+                "Warning: alert '{name}': the price history doesn't reach back {within} yet, so \
+                 changes can't be checked"
+            );
+            continue;
+        };
+        for model in &covered {
+            let Some(old) = before.get(&model.id) else {
+                continue;
+            };
+            let sides = [
+                ("input", &old.input, &model.pricing.prompt),
+                ("output", &old.output, &model.pricing.completion),
+            ];
+            for (side, old, new) in sides {
+                let (Some(old), Some(new)) = (per_million(old), per_million(new)) else {
+                    continue;
+                };
+                if old <= 0.0 {
+                    continue;
+                }
+                let change = (new - old) / old * 100.0;
+                if change.abs() > percent {
+                    triggers.push(Trigger {
+                        rule: name,
+                        model: &model.id,
+                        condition: format!("change over {percent}% in {within}"),
+                        detail: format!(
+                            "{side} {change:+.1}%, ${old}/M to ${new}/M since {}",
+                            fetched_at.format("%Y-%m-%d %H:%M UTC")
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    for name in unmatched {
+        eprintln!("Warning: alert '{name}' matches no models");
+    }
+
+    if triggers.is_empty() {
+        println!("No alerts triggered ({} rules checked)", config.alerts.len());
+        return Ok(false);
+    }
+    let mut table = Table::new(["Alert", "Model", "Condition", "Current"]);
+    for trigger in &triggers {
+        table.push(vec![
+            Cell::text(trigger.rule),
+            model_link(trigger.model),
+            Cell::text(&trigger.condition),
+            Cell::text(&trigger.detail),
+        ]);
+    }
+    print_titled_table(
+        &format!("{} alerts triggered", triggers.len()),
+        &table,
+        None,
+        format,
+        source,
+        stale_after,
+    )?;
+    Ok(true)
+}
@@ -0,0 +1,135 @@
+use crate::{
+    endpoints, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat,
+};
+use chrono::TimeDelta;
+use std::collections::BTreeMap;
+
+/// One place a model's weights can be run: a provider behind one of its catalog listings
+struct Host {
+    name: String,
+    cost: f64,
+}
+
+/// The cheapest and most expensive hosts of one set of open weights
+struct Spread {
+    weights: String,
+    hosts: usize,
+    cheapest: Host,
+    priciest: Host,
+}
+
+impl Spread {
+    fn savings(&self) -> f64 {
+        self.priciest.cost - self.cheapest.cost
+    }
+}
+
+/// For open-weights models served by more than one provider, or listed more than once, print
+/// the cheapest and most expensive host for `input` and `output` tokens a month and what
+/// moving between them saves, largest savings first
+pub async fn print(
+    models: &[&Model],
+    input: u64,
+    output: u64,
+    format: OutputFormat,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    // Free variants are rate-limited previews rather than somewhere to move a workload
+    let listings: BTreeMap<&str, &str> = models
+        .iter()
+        .filter(|model| !model.id.ends_with(":free"))
+        .filter_map(|model| {
+            let weights = model.hugging_face_id.as_deref().filter(|id| !id.is_empty())?;
+            Some((model.id.as_str(), weights))
+        })
+        .collect();
+    if listings.is_empty() {
+        return Err(anyhow::anyhow!("No open-weights models match"));
+    }
+
+    let ids = listings.keys().map(|id| id.to_string()).collect();
+    let source = endpoints::source();
+    let fetched = endpoints::fetch_all(ids).await;
+
+    let mut hosts: BTreeMap<&str, Vec<Host>> = BTreeMap::new();
+    let mut listed_as: BTreeMap<&str, usize> = BTreeMap::new();
+    for (id, _) in &fetched {
+        *listed_as.entry(listings[id.as_str()]).or_default() += 1;
+    }
+    for (id, endpoints) in &fetched {
+        let weights = listings[id.as_str()];
+        for endpoint in endpoints {
+            let Some(cost) = endpoint.cost(input, output) else {
+                continue;
+            };
+            // Name the listing only when the weights are listed under more than one ID
+            let name = if listed_as[weights] > 1 {
+                format!("{} via {id}", endpoint.provider())
+            } else {
+                endpoint.provider()
+            };
+            hosts.entry(weights).or_default().push(Host { name, cost });
+        }
+    }
+
+    let mut spreads: Vec<Spread> = hosts
+        .into_iter()
+        .filter(|(_, hosts)| hosts.len() > 1)
+        .map(|(weights, mut hosts)| {
+            hosts.sort_by(|a, b| a.cost.total_cmp(&b.cost).then_with(|| a.name.cmp(&b.name)));
+            let count = hosts.len();
+            let priciest = hosts.pop().expect("at least two hosts");
+            let cheapest = hosts.swap_remove(0);
+            Spread {
+                weights: weights.to_string(),
+                hosts: count,
+                cheapest,
+                priciest,
+            }
+        })
+        .collect();
+    if spreads.is_empty() {
+        return Err(anyhow::anyhow!(
+            "None of the matching open-weights models has more than one host"
+        ));
+    }
+    spreads.sort_by(|a, b| b.savings().total_cmp(&a.savings()).then_with(|| a.weights.cmp(&b.weights)));
+
+    let mut table = Table::new([
+        "Weights",
+        "Hosts",
+        "Cheapest",
+        "Cost",
+        "Most Expensive",
+        "Cost",
+        "Savings",
+        "Savings %",
+    ]);
+    for spread in &spreads {
+        table.push(vec![
+            Cell::text(&spread.weights),
+            Cell::Number(spread.hosts as f64, 0),
+            Cell::text(&spread.cheapest.name),
+            Cell::Cost(spread.cheapest.cost),
+            Cell::text(&spread.priciest.name),
+            Cell::Cost(spread.priciest.cost),
+            Cell::Cost(spread.savings()),
+            if spread.priciest.cost > 0.0 {
+                Cell::Number(spread.savings() / spread.priciest.cost * 100.0, 1)
+            } else {
+                Cell::Missing
+            },
+        ]);
+    }
+
+    print_titled_table(
+        &format!("Hosting spread for {input} input + {output} output tokens a month"),
+        &table,
+        Some("Costs in USD a month. Savings is what moving from the most expensive host to the cheapest saves."),
+        format,
+        &source,
+        stale_after,
+    )
+}
@@ -0,0 +1,224 @@
+use crate::{config::Config, AuthAction};
+use anyhow::Context;
+use keyring::Entry;
+use std::io::{self, BufRead, IsTerminal};
+
+/// Service name that keys are stored under in the OS keychain
+const SERVICE: &str = "llm-pricing";
+
+pub const DEFAULT_PROVIDER: &str = "openrouter";
+
+/// Where an API key was found
+pub enum KeySource {
+    Env(String),
+    Keychain,
+}
+
+impl KeySource {
+    pub fn describe(&self) -> String {
+        match self {
+            KeySource::Env(var) => format!("set via {var}"),
+            KeySource::Keychain => "stored in the OS keychain".to_string(),
+        }
+    }
+}
+
+/// Environment variable that overrides the stored key, e.g. OPENROUTER_API_KEY
+pub fn env_var(provider: &str) -> String {
+    format!("{}_API_KEY", provider.to_uppercase().replace('-', "_"))
+}
+
+fn validate_name(kind: &str, name: &str, example: &str) -> anyhow::Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow::anyhow!(
+            "Invalid {} name: '{}'. Use letters, digits, '-' and '_' (e.g., '{}')",
+            kind,
+            name,
+            example
+        ));
+    }
+    Ok(())
+}
+
+fn validate_provider(provider: &str) -> anyhow::Result<()> {
+    validate_name("provider", provider, DEFAULT_PROVIDER)
+}
+
+/// A provider's default key is stored under the provider's name, and additional named keys
+/// under `provider:name`
+fn entry(provider: &str, name: Option<&str>) -> anyhow::Result<Entry> {
+    validate_provider(provider)?;
+    let user = match name {
+        Some(name) => {
+            validate_name("key", name, "team-a")?;
+            format!("{provider}:{name}")
+        }
+        None => provider.to_string(),
+    };
+    Entry::new(SERVICE, &user).context("Failed to open the OS keychain")
+}
+
+/// The key stored in the keychain for a provider, or one of its named keys, if any
+pub fn stored_key(provider: &str, name: Option<&str>) -> anyhow::Result<Option<String>> {
+    match entry(provider, name)?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read from the OS keychain"),
+    }
+}
+
+/// The API key for a provider, from its environment variable or else the keychain
+pub fn api_key(provider: &str) -> anyhow::Result<Option<(String, KeySource)>> {
+    let var = env_var(provider);
+    if let Ok(key) = std::env::var(&var) {
+        if !key.trim().is_empty() {
+            return Ok(Some((key, KeySource::Env(var))));
+        }
+    }
+    Ok(stored_key(provider, None)?.map(|key| (key, KeySource::Keychain)))
+}
+
+pub fn store_key(provider: &str, name: Option<&str>, key: &str) -> anyhow::Result<()> {
+    entry(provider, name)?
+        .set_password(key)
+        .context("Failed to write to the OS keychain")
+}
+
+/// Remove a stored key, returning whether there was one
+pub fn remove_key(provider: &str, name: Option<&str>) -> anyhow::Result<bool> {
+    match entry(provider, name)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e).context("Failed to remove from the OS keychain"),
+    }
+}
+
+/// Read a key from a hidden prompt, or from stdin when it isn't a terminal
+pub fn read_key(label: &str) -> anyhow::Result<String> {
+    let key = if io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("{label} API key: "))?
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        line
+    };
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow::anyhow!("No API key given"));
+    }
+    Ok(key.to_string())
+}
+
+/// Show enough of a key to tell keys apart without revealing it
+fn mask(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+    let start: String = chars[..6].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{start}...{end}")
+}
+
+/// How a key is referred to in messages, e.g. "openrouter" or "openrouter 'team-a'"
+fn describe_key(provider: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{provider} '{name}'"),
+        None => provider.to_string(),
+    }
+}
+
+pub fn run(action: &AuthAction) -> anyhow::Result<()> {
+    let (AuthAction::Set { provider, .. }
+    | AuthAction::Status { provider }
+    | AuthAction::Remove { provider, .. }) = action;
+    validate_provider(provider)?;
+    if let AuthAction::Set { name: Some(name), .. } | AuthAction::Remove { name: Some(name), .. } =
+        action
+    {
+        validate_name("key", name, "team-a")?;
+    }
+
+    match action {
+        AuthAction::Set { provider, name } => {
+            let name = name.as_deref();
+            let label = describe_key(provider, name);
+            let key = read_key(&label)?;
+            store_key(provider, name, &key)?;
+            println!("Stored {label} API key in the OS keychain");
+            match name {
+                Some(name) => {
+                    let mut config = Config::load()?;
+                    let names = config.keys.entry(provider.clone()).or_default();
+                    if !names.iter().any(|existing| existing == name) {
+                        names.push(name.to_string());
+                        names.sort();
+                        config.save()?;
+                    }
+                }
+                None => {
+                    let var = env_var(provider);
+                    if std::env::var_os(&var).is_some() {
+                        println!("Note: {var} is set and takes precedence over the stored key");
+                    }
+                }
+            }
+        }
+        AuthAction::Status { provider } => {
+            let describe_stored = |name: Option<&str>| match stored_key(provider, name) {
+                Ok(Some(key)) => mask(&key),
+                Ok(None) => "none".to_string(),
+                Err(e) => format!("unavailable ({})", e.root_cause()),
+            };
+            let var = env_var(provider);
+            let env = match std::env::var(&var) {
+                Ok(key) if !key.trim().is_empty() => mask(&key),
+                _ => "not set".to_string(),
+            };
+            let in_use = match api_key(provider) {
+                Ok(Some((_, source))) => source.describe(),
+                _ => "no key".to_string(),
+            };
+
+            let mut rows = vec![
+                ("Keychain".to_string(), describe_stored(None)),
+                (var, env),
+                ("In use".to_string(), in_use),
+            ];
+            let config = Config::load()?;
+            for name in config.keys.get(provider).into_iter().flatten() {
+                rows.push((format!("Key '{name}'"), describe_stored(Some(name))));
+            }
+
+            let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+            for (label, value) in rows {
+                println!("{label:<width$}  {value}");
+            }
+        }
+        AuthAction::Remove { provider, name } => {
+            let name = name.as_deref();
+            let label = describe_key(provider, name);
+            if remove_key(provider, name)? {
+                println!("Removed {label} API key from the OS keychain");
+            } else {
+                println!("No {label} API key was stored in the OS keychain");
+            }
+            if let Some(name) = name {
+                let mut config = Config::load()?;
+                if let Some(names) = config.keys.get_mut(provider.as_str()) {
+                    names.retain(|existing| existing != name);
+                    if names.is_empty() {
+                        config.keys.remove(provider.as_str());
+                    }
+                    config.save()?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use crate::{
+    find_model, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::TimeDelta;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// One historical request, as a line of the usage JSONL file
+#[derive(Deserialize)]
+struct Request {
+    model: String,
+    input_tokens: u64,
+    output_tokens: u64,
+    /// Input tokens read from the prompt cache, included in `input_tokens`
+    #[serde(default)]
+    cached_tokens: u64,
+    /// What the request actually cost in USD, when known; otherwise it's priced on its model
+    #[serde(default)]
+    cost: Option<f64>,
+    /// Quality tier the request was labelled with, for quality-tiered strategies
+    #[serde(default)]
+    tier: Option<String>,
+    /// Input modalities beyond text the request used (e.g., "image")
+    #[serde(default)]
+    modalities: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct StrategyFile {
+    #[serde(rename = "strategy")]
+    strategies: Vec<Strategy>,
+}
+
+#[derive(Deserialize)]
+struct Strategy {
+    name: String,
+    #[serde(flatten)]
+    kind: Kind,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum Kind {
+    /// Send each request to the candidate with the lowest list price that can take it
+    CheapestCapable { models: Vec<String> },
+    /// Send each request to the model named for its tier, or the default model
+    QualityTiered {
+        tiers: BTreeMap<String, String>,
+        default: String,
+    },
+    /// Like cheapest-capable, but counting the prompt cache discount a request only keeps on
+    /// the model that served it
+    CacheAware { models: Vec<String> },
+}
+
+impl Kind {
+    fn label(&self) -> &'static str {
+        match self {
+            Kind::CheapestCapable { .. } => "cheapest-capable",
+            Kind::QualityTiered { .. } => "quality-tiered",
+            Kind::CacheAware { .. } => "cache-aware",
+        }
+    }
+}
+
+fn read_usage(path: &Path) -> anyhow::Result<Vec<Request>> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut requests = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Request = serde_json::from_str(line)
+            .with_context(|| format!("{} line {}", path.display(), number + 1))?;
+        requests.push(request);
+    }
+    if requests.is_empty() {
+        return Err(anyhow::anyhow!("No requests in {}", path.display()));
+    }
+    Ok(requests)
+}
+
+/// Strategies from a TOML file with a `[[strategy]]` table per strategy, or the same as YAML
+/// or JSON
+fn read_strategies(path: &Path) -> anyhow::Result<Vec<Strategy>> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: StrategyFile = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&contents).map_err(anyhow::Error::from),
+        Some("yaml" | "yml") => serde_yaml::from_str(&contents).map_err(anyhow::Error::from),
+        _ => toml::from_str(&contents).map_err(anyhow::Error::from),
+    }
+    .with_context(|| format!("Invalid strategy file {}", path.display()))?;
+    if file.strategies.is_empty() {
+        return Err(anyhow::anyhow!("No strategies in {}", path.display()));
+    }
+    Ok(file.strategies)
+}
+
+/// Whether the request fits the model's context window and it takes the request's inputs
+fn capable(model: &Model, request: &Request) -> bool {
+    let fits = model
+        .context_length
+        .is_none_or(|context| request.input_tokens + request.output_tokens <= context);
+    let inputs = model
+        .architecture
+        .as_ref()
+        .and_then(|architecture| architecture.input_modalities.as_ref());
+    let takes_inputs = request
+        .modalities
+        .iter()
+        .all(|modality| inputs.is_some_and(|inputs| inputs.contains(modality)));
+    fits && takes_inputs
+}
+
+/// A fixed per-token price, or None for unparseable prices and the -1 routers report for
+/// variable pricing, neither of which can be replayed
+fn price(price: &str) -> Option<f64> {
+    price.parse::<f64>().ok().filter(|price| *price >= 0.0)
+}
+
+/// What the request costs on `model`, or None without fixed prices. Cached tokens only stay
+/// cached on the model that served the request; anywhere else they're billed as ordinary input.
+fn cost_on(model: &Model, request: &Request) -> Option<f64> {
+    let input_price = price(&model.pricing.prompt)?;
+    let cache_read_price = match model.pricing.input_cache_read.as_deref() {
+        Some(cache_read) => price(cache_read)?,
+        None => input_price,
+    };
+    let cached = if model.id == request.model {
+        request.cached_tokens.min(request.input_tokens)
+    } else {
+        0
+    };
+    Some(
+        (request.input_tokens - cached) as f64 * input_price
+            + cached as f64 * cache_read_price
+            + request.output_tokens as f64 * price(&model.pricing.completion)?,
+    )
+}
+
+/// What the request costs at list prices, ignoring the prompt cache, or None without fixed prices
+fn list_cost(model: &Model, request: &Request) -> Option<f64> {
+    Some(
+        request.input_tokens as f64 * price(&model.pricing.prompt)?
+            + request.output_tokens as f64 * price(&model.pricing.completion)?,
+    )
+}
+
+/// A strategy resolved against the catalog
+enum Router<'a> {
+    Cheapest { models: Vec<&'a Model>, cache_aware: bool },
+    Tiered {
+        tiers: BTreeMap<&'a str, &'a Model>,
+        default: &'a Model,
+    },
+}
+
+impl<'a> Router<'a> {
+    fn new(models: &'a [Model], kind: &'a Kind) -> anyhow::Result<Self> {
+        let resolve = |ids: &[String]| -> anyhow::Result<Vec<&'a Model>> {
+            ids.iter().map(|id| find_model(models, id)).collect()
+        };
+        Ok(match kind {
+            Kind::CheapestCapable { models: ids } => Router::Cheapest {
+                models: resolve(ids)?,
+                cache_aware: false,
+            },
+            Kind::CacheAware { models: ids } => Router::Cheapest {
+                models: resolve(ids)?,
+                cache_aware: true,
+            },
+            Kind::QualityTiered { tiers, default } => Router::Tiered {
+                tiers: tiers
+                    .iter()
+                    .map(|(tier, id)| Ok((tier.as_str(), find_model(models, id)?)))
+                    .collect::<anyhow::Result<_>>()?,
+                default: find_model(models, default)?,
+            },
+        })
+    }
+
+    /// The model the request would go to and what it would cost there, or None if the
+    /// strategy has no model with fixed prices that can take it
+    fn route(&self, request: &Request) -> Option<(&'a Model, f64)> {
+        match self {
+            Router::Cheapest { models, cache_aware } => models
+                .iter()
+                .filter(|model| capable(model, request))
+                .filter_map(|model| {
+                    let rank = if *cache_aware {
+                        cost_on(model, request)
+                    } else {
+                        list_cost(model, request)
+                    };
+                    Some((*model, rank?, cost_on(model, request)?))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(model, _, cost)| (model, cost)),
+            Router::Tiered { tiers, default } => {
+                let model = request
+                    .tier
+                    .as_deref()
+                    .and_then(|tier| tiers.get(tier))
+                    .unwrap_or(default);
+                if !capable(model, request) {
+                    return None;
+                }
+                Some((*model, cost_on(model, request)?))
+            }
+        }
+    }
+}
+
+/// Replay historical requests through each strategy and print what each would have cost next
+/// to what the requests actually cost. Requests a strategy has no capable model for stay on
+/// the model that served them.
+pub fn print(
+    models: &[Model],
+    usage: &Path,
+    strategies: &Path,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let requests = read_usage(usage)?;
+    let strategies = read_strategies(strategies)?;
+
+    let mut actual_costs = Vec::with_capacity(requests.len());
+    for request in &requests {
+        let cost = match request.cost {
+            Some(cost) => cost,
+            None => models
+                .iter()
+                .find(|model| model.id == request.model)
+                .and_then(|model| cost_on(model, request))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No current fixed prices for {}; give requests to it a 'cost'",
+                        request.model
+                    )
+                })?,
+        };
+        actual_costs.push(cost);
+    }
+    let actual: f64 = actual_costs.iter().sum();
+
+    let mut table = Table::new([
+        "Strategy", "Kind", "Cost", "vs Actual", "Savings %", "Rerouted", "Kept",
+    ]);
+    table.push(vec![
+        Cell::text("actual"),
+        Cell::text("as served"),
+        Cell::Cost(actual),
+        Cell::Missing,
+        Cell::Missing,
+        Cell::Number(0.0, 0),
+        Cell::Number(requests.len() as f64, 0),
+    ]);
+    for strategy in &strategies {
+        let router = Router::new(models, &strategy.kind)
+            .with_context(|| format!("Strategy '{}'", strategy.name))?;
+        let (mut cost, mut rerouted, mut kept) = (0.0, 0, 0);
+        for (request, actual_cost) in requests.iter().zip(&actual_costs) {
+            match router.route(request) {
+                Some((model, model_cost)) if model.id != request.model => {
+                    rerouted += 1;
+                    cost += model_cost;
+                }
+                // Staying put costs what it did, including any discounts the actual cost had
+                _ => {
+                    kept += 1;
+                    cost += actual_cost;
+                }
+            }
+        }
+        table.push(vec![
+            Cell::text(&strategy.name),
+            Cell::text(strategy.kind.label()),
+            Cell::Cost(cost),
+            Cell::Cost(cost - actual),
+            if actual > 0.0 {
+                Cell::Number((actual - cost) / actual * 100.0, 1)
+            } else {
+                Cell::Missing
+            },
+            Cell::Number(rerouted as f64, 0),
+            Cell::Number(kept as f64, 0),
+        ]);
+    }
+
+    let heading = format!("Backtest of {} requests from {}", requests.len(), usage.display());
+    let note = "Costs in USD at current prices, except requests with a recorded cost that stay on \
+                their model. Rerouted: requests sent to a different model. Kept: requests left on \
+                the model that served them, including any the strategy had no capable model for.";
+    print_titled_table(&heading, &table, Some(note), format, source, stale_after)
+}
@@ -0,0 +1,108 @@
+use crate::{html, table, Model};
+use anyhow::Context;
+use clap::ValueEnum;
+use std::{fs, path::Path};
+
+/// Which of a model's prices a badge shows
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum BadgePrice {
+    Input,
+    Output,
+    #[default]
+    Both,
+}
+
+/// Shields' default blue, and its green for free models
+const COLOR: &str = "#007ec6";
+const FREE_COLOR: &str = "#4c1";
+
+/// Rough width of Verdana 11px text, which shields badges are set in. Wide enough that the
+/// text never touches the edges.
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+/// The badge's right-hand side, e.g. "$3.00/1M in | $15.00/1M out"
+fn message(model: &Model, price: BadgePrice) -> String {
+    let unit = table::price_unit().short();
+    let format = |price: &str| match price.parse::<f64>() {
+        Ok(price) => format!("${}/{unit}", table::format_price(price)),
+        Err(_) => "N/A".to_string(),
+    };
+    match price {
+        BadgePrice::Input => format(&model.pricing.prompt),
+        BadgePrice::Output => format(&model.pricing.completion),
+        BadgePrice::Both => format!(
+            "{} in | {} out",
+            format(&model.pricing.prompt),
+            format(&model.pricing.completion)
+        ),
+    }
+}
+
+fn is_free(model: &Model) -> bool {
+    [&model.pricing.prompt, &model.pricing.completion]
+        .iter()
+        .all(|price| price.parse::<f64>().is_ok_and(|price| price == 0.0))
+}
+
+/// A badge in the shields.io "flat" style
+fn svg(label: &str, message: &str, color: &str) -> String {
+    let (label_width, message_width) = (text_width(label), text_width(message));
+    let width = label_width + message_width;
+    let (label, message) = (html::escape(label), html::escape(message));
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+<title>{label}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text>
+<text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text>
+</g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+/// Write a badge showing the model's current prices: an SVG, or for a `.json` file, the JSON
+/// that shields.io's endpoint badge renders, so a badge regenerated on a schedule stays
+/// current wherever it's embedded. Prints the SVG when no file is given.
+pub fn write(model: &Model, price: BadgePrice, label: Option<&str>, file: Option<&Path>) -> anyhow::Result<()> {
+    let label = label.unwrap_or(&model.id);
+    let message = message(model, price);
+    let color = if is_free(model) { FREE_COLOR } else { COLOR };
+
+    let Some(path) = file else {
+        print!("{}", svg(label, &message, color));
+        return Ok(());
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let contents = match extension.as_deref() {
+        Some("svg") => svg(label, &message, color),
+        Some("json") => {
+            let endpoint = serde_json::json!({
+                "schemaVersion": 1,
+                "label": label,
+                "message": message,
+                "color": color.trim_start_matches('#'),
+            });
+            format!("{endpoint}\n")
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported badge file '{}'. Use a .svg or .json extension",
+                path.display()
+            ))
+        }
+    };
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote badge for {} ({message}) to {}", model.id, path.display());
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::prefix_map::PrefixMap;
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
+
+/// Benchmark scores as published by each model's developer, keyed by model ID. Keys ending
+/// in `*` match any ID with that prefix; exact keys take precedence, then the longest prefix.
+const BUNDLED_BENCHMARKS: &str = include_str!("../data/benchmarks.json");
+
+/// A benchmark models can be ranked on
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Benchmark {
+    /// MMLU: multiple-choice questions across 57 subjects, 0-100
+    #[default]
+    Mmlu,
+    /// GPQA Diamond: graduate-level science questions, 0-100
+    Gpqa,
+}
+
+impl Benchmark {
+    pub fn label(self) -> &'static str {
+        match self {
+            Benchmark::Mmlu => "MMLU",
+            Benchmark::Gpqa => "GPQA Diamond",
+        }
+    }
+}
+
+/// A model's scores; any of them may be unpublished
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scores {
+    #[serde(default)]
+    pub mmlu: Option<f64>,
+    #[serde(default)]
+    pub gpqa: Option<f64>,
+}
+
+impl Scores {
+    /// Take the scores `other` has, keeping ours for the ones it leaves out
+    fn merge(&mut self, other: Scores) {
+        self.mmlu = other.mmlu.or(self.mmlu);
+        self.gpqa = other.gpqa.or(self.gpqa);
+    }
+
+    pub fn get(&self, benchmark: Benchmark) -> Option<f64> {
+        match benchmark {
+            Benchmark::Mmlu => self.mmlu,
+            Benchmark::Gpqa => self.gpqa,
+        }
+    }
+}
+
+pub struct Benchmarks(PrefixMap<Scores>);
+
+impl Benchmarks {
+    /// Load the bundled scores, with scores from `override_path` taking precedence one
+    /// benchmark at a time
+    pub fn load(override_path: Option<&Path>) -> anyhow::Result<Self> {
+        let mut entries: HashMap<String, Scores> =
+            serde_json::from_str(BUNDLED_BENCHMARKS).context("Invalid bundled benchmark data")?;
+
+        if let Some(path) = override_path {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read benchmarks file {}", path.display()))?;
+            let overrides: HashMap<String, Scores> = serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid benchmarks file {}", path.display()))?;
+            for (key, scores) in overrides {
+                match entries.get_mut(&key) {
+                    Some(bundled) => bundled.merge(scores),
+                    None => {
+                        entries.insert(key, scores);
+                    }
+                }
+            }
+        }
+
+        Ok(Self(PrefixMap::new(entries)))
+    }
+
+    /// Scores for a model. Variants such as `:free` or `:nitro` share their model's scores.
+    pub fn lookup(&self, model_id: &str) -> Option<&Scores> {
+        let model_id = model_id.split_once(':').map_or(model_id, |(base, _)| base);
+        self.0.lookup(model_id)
+    }
+}
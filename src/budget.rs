@@ -0,0 +1,168 @@
+use crate::{calculate_cost, table::format_decimal, Model, SourceInfo};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// Tokens in the unit IaC prices are given in. Variable names spell it out, so it doesn't
+/// follow --per.
+const TOKENS_PER_UNIT: f64 = 1_000_000.0;
+
+/// Expected monthly usage of each model, for projecting budgets
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Usage {
+    #[serde(rename = "requests_per_month")]
+    pub requests: u64,
+    #[serde(rename = "input_tokens_per_request")]
+    pub input: u64,
+    #[serde(rename = "output_tokens_per_request")]
+    pub output: u64,
+    /// Share added on top of the projected cost, e.g. 0.2 for 20%
+    pub headroom: f64,
+}
+
+/// A model's prices per 1M tokens, and its budget when usage was given
+#[derive(Serialize)]
+struct ModelBudget {
+    input_per_million: Option<f64>,
+    output_per_million: Option<f64>,
+    cache_read_per_million: Option<f64>,
+    cache_write_per_million: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monthly_cost: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monthly_budget: Option<f64>,
+}
+
+/// Round a dollar amount up to whole cents, so a budget never falls short of the projection
+fn round_up_to_cents(amount: f64) -> f64 {
+    clean(amount * 100.0).ceil() / 100.0
+}
+
+/// Drop float noise from scaling per-token prices, e.g. 0.30000000000000004
+fn clean(value: f64) -> f64 {
+    (value * 1e10).round() / 1e10
+}
+
+fn model_budgets(
+    models: &[&Model],
+    usage: Option<&Usage>,
+) -> anyhow::Result<BTreeMap<String, ModelBudget>> {
+    let per_million = |price: Option<&String>| {
+        price
+            .and_then(|price| price.parse::<f64>().ok())
+            .map(|price| clean(price * TOKENS_PER_UNIT))
+    };
+    let mut budgets = BTreeMap::new();
+    for model in models {
+        let pricing = &model.pricing;
+        let monthly_cost = usage
+            .map(|usage| {
+                calculate_cost(model, usage.input, usage.output, None, 5)
+                    .map(|cost| clean(cost.total_cost * usage.requests as f64))
+            })
+            .transpose()?;
+        budgets.insert(
+            model.id.clone(),
+            ModelBudget {
+                input_per_million: per_million(Some(&pricing.prompt)),
+                output_per_million: per_million(Some(&pricing.completion)),
+                cache_read_per_million: per_million(pricing.input_cache_read.as_ref()),
+                cache_write_per_million: per_million(pricing.input_cache_write.as_ref()),
+                monthly_cost,
+                monthly_budget: monthly_cost.zip(usage).map(|(cost, usage)| {
+                    round_up_to_cents(cost * (1.0 + usage.headroom))
+                }),
+            },
+        );
+    }
+    Ok(budgets)
+}
+
+/// A JSON document of prices and budgets keyed by model ID, for tools that read JSON, such as
+/// Terraform's `jsondecode` or Pulumi config
+pub fn json(
+    models: &[&Model],
+    usage: Option<&Usage>,
+    source: &SourceInfo,
+    generated_at: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let document = json!({
+        "generated_at": generated_at,
+        "source": {
+            "name": source.name,
+            "url": source.url,
+            "fetched_at": source.fetched_at,
+        },
+        "currency": "USD",
+        "usage": usage,
+        "models": model_budgets(models, usage)?,
+    });
+    Ok(format!("{}\n", serde_json::to_string_pretty(&document)?))
+}
+
+/// Quote a string for HCL, escaping its template sequences
+fn hcl_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "$${")
+        .replace("%{", "%%{");
+    format!("\"{escaped}\"")
+}
+
+fn hcl_number(value: Option<f64>) -> String {
+    value.map_or("null".to_string(), format_decimal)
+}
+
+/// Terraform variable definitions: a `llm_model_prices` map of prices per 1M tokens by model
+/// ID, and with usage, a `llm_monthly_budgets` map of budget amounts in USD for budget and
+/// alert modules
+pub fn tfvars(
+    models: &[&Model],
+    usage: Option<&Usage>,
+    source: &SourceInfo,
+    generated_at: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    let budgets = model_budgets(models, usage)?;
+    let mut hcl = format!(
+        "# Generated by llm-pricing on {} from {}, fetched {}\n# Prices in USD per 1M tokens\n",
+        generated_at.format("%Y-%m-%d %H:%M UTC"),
+        source.name,
+        source.fetched_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    hcl.push_str("\nllm_model_prices = {\n");
+    for (id, budget) in &budgets {
+        hcl.push_str(&format!(
+            "  {} = {{\n    input_per_million       = {}\n    output_per_million      = {}\n    \
+             cache_read_per_million  = {}\n    cache_write_per_million = {}\n  }}\n",
+            hcl_string(id),
+            hcl_number(budget.input_per_million),
+            hcl_number(budget.output_per_million),
+            hcl_number(budget.cache_read_per_million),
+            hcl_number(budget.cache_write_per_million),
+        ));
+    }
+    hcl.push_str("}\n");
+
+    if let Some(usage) = usage {
+        hcl.push_str(&format!(
+            "\n# {} requests a month of {} input and {} output tokens, plus {}% headroom\n",
+            usage.requests,
+            usage.input,
+            usage.output,
+            format_decimal(usage.headroom * 100.0)
+        ));
+        hcl.push_str("llm_monthly_budgets = {\n");
+        for (id, budget) in &budgets {
+            hcl.push_str(&format!(
+                "  {} = {}\n",
+                hcl_string(id),
+                hcl_number(budget.monthly_budget)
+            ));
+        }
+        hcl.push_str("}\n");
+    }
+    Ok(hcl)
+}
@@ -0,0 +1,86 @@
+use crate::{huggingface, paths, Model};
+use anyhow::Context;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// How long a cached model list is reused before fetching again
+pub const CATALOG_TTL: TimeDelta = TimeDelta::hours(1);
+
+/// How long Hugging Face metadata is reused. It rarely changes once a model is published.
+pub const HUGGING_FACE_TTL: TimeDelta = TimeDelta::days(7);
+
+#[derive(Deserialize)]
+struct CachedCatalog {
+    fetched_at: DateTime<Utc>,
+    models: Vec<Model>,
+}
+
+fn catalog_file() -> anyhow::Result<PathBuf> {
+    Ok(paths::cache_dir()?.join("models.json"))
+}
+
+/// The cached model list and when it was fetched, if there is one no older than `max_age`.
+/// A missing or unreadable cache is treated as empty.
+pub fn load_catalog(max_age: TimeDelta) -> Option<(Vec<Model>, DateTime<Utc>)> {
+    let contents = fs::read_to_string(catalog_file().ok()?).ok()?;
+    let cached: CachedCatalog = serde_json::from_str(&contents).ok()?;
+    if Utc::now() - cached.fetched_at > max_age {
+        return None;
+    }
+    Some((cached.models, cached.fetched_at))
+}
+
+/// When the cached model list was fetched, however old it is, without reading the models
+pub fn catalog_fetched_at() -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct FetchedAt {
+        fetched_at: DateTime<Utc>,
+    }
+
+    let contents = fs::read_to_string(catalog_file().ok()?).ok()?;
+    let cached: FetchedAt = serde_json::from_str(&contents).ok()?;
+    Some(cached.fetched_at)
+}
+
+pub fn store_catalog(models: &[Model], fetched_at: DateTime<Utc>) -> anyhow::Result<()> {
+    let path = catalog_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create cache directory {}", parent.display()))?;
+    }
+    let cached = serde_json::json!({ "fetched_at": fetched_at, "models": models });
+    fs::write(&path, cached.to_string())
+        .with_context(|| format!("Failed to write cache file {}", path.display()))
+}
+
+/// Hugging Face metadata for one model and when it was fetched
+#[derive(Serialize, Deserialize)]
+pub struct CachedModelInfo {
+    pub fetched_at: DateTime<Utc>,
+    pub info: huggingface::ModelInfo,
+}
+
+fn hugging_face_file() -> anyhow::Result<PathBuf> {
+    Ok(paths::cache_dir()?.join("huggingface.json"))
+}
+
+/// Cached Hugging Face metadata keyed by Hugging Face ID, however old. A missing or unreadable
+/// cache is treated as empty.
+pub fn load_hugging_face() -> BTreeMap<String, CachedModelInfo> {
+    hugging_face_file()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn store_hugging_face(entries: &BTreeMap<String, CachedModelInfo>) -> anyhow::Result<()> {
+    let path = hugging_face_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create cache directory {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string(entries)?)
+        .with_context(|| format!("Failed to write cache file {}", path.display()))
+}
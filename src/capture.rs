@@ -0,0 +1,42 @@
+use anyhow::Context;
+use gag::BufferRedirect;
+use std::{
+    io::{self, Read, Write},
+    sync::Mutex,
+};
+
+/// Stdout held back while a command runs, and what to do with it once the command is done
+struct Capture {
+    redirect: BufferRedirect,
+    then: fn(String),
+}
+
+static CAPTURE: Mutex<Option<Capture>> = Mutex::new(None);
+
+/// Hold back everything printed to stdout until `finish`, which passes it to `then`
+pub fn start(then: fn(String)) -> anyhow::Result<()> {
+    io::stdout().flush()?;
+    let redirect = BufferRedirect::stdout().context("Failed to capture output")?;
+    if let Ok(mut capture) = CAPTURE.lock() {
+        *capture = Some(Capture { redirect, then });
+    }
+    Ok(())
+}
+
+/// Restore stdout and hand over what was printed since `start`, if it was called
+pub fn finish() {
+    let _ = io::stdout().flush();
+    let Some(mut capture) = CAPTURE.lock().ok().and_then(|mut capture| capture.take()) else {
+        return;
+    };
+    let mut output = Vec::new();
+    let _ = capture.redirect.read_to_end(&mut output);
+    drop(capture.redirect);
+    (capture.then)(String::from_utf8_lossy(&output).into_owned());
+}
+
+/// Exit with `code` once the held-back output has gone where it was going
+pub fn exit(code: i32) -> ! {
+    finish();
+    std::process::exit(code)
+}
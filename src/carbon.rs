@@ -0,0 +1,42 @@
+use crate::Model;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// gCO2e per 1M tokens for each billion parameters when the config doesn't set one. A
+/// forward pass costs about 2 FLOPs per parameter per token; at the low utilization of
+/// batched inference, plus datacenter overhead, that's roughly 5 Wh per 1M tokens per
+/// billion parameters, at a grid average of about 400 gCO2e/kWh.
+const DEFAULT_GRAMS_PER_BILLION_PARAMS: f64 = 2.0;
+
+/// How emissions are estimated, from the `[carbon]` section of the config file. These are
+/// rough, order-of-magnitude figures: providers don't publish per-token energy use.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Emissions {
+    /// gCO2e per 1M tokens for each billion parameters [default: 2]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grams_per_billion_params: Option<f64>,
+    /// Multipliers for providers whose grid is cleaner or dirtier than average, keyed by
+    /// the provider part of model IDs (e.g., 'mistralai')
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, f64>,
+    /// gCO2e per 1M tokens for specific models, keyed by model ID, used instead of the
+    /// estimate
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, f64>,
+}
+
+impl Emissions {
+    /// Estimated gCO2e per 1M tokens of `model`, input and output alike, from a configured
+    /// figure or its Hugging Face parameter count; None when there's neither
+    pub fn per_million(&self, model: &Model, params: Option<u64>) -> Option<f64> {
+        if let Some(grams) = self.models.get(&model.id) {
+            return Some(*grams);
+        }
+        let provider = model.id.split('/').next().unwrap_or_default();
+        let multiplier = self.providers.get(provider).copied().unwrap_or(1.0);
+        let per_billion = self
+            .grams_per_billion_params
+            .unwrap_or(DEFAULT_GRAMS_PER_BILLION_PARAMS);
+        params.map(|params| params as f64 / 1e9 * per_billion * multiplier)
+    }
+}
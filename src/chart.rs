@@ -0,0 +1,750 @@
+use crate::{
+    drawing::{self, Drawing},
+    model_link, price_header, print_heading, print_note, print_titled_table,
+    table::{self, Cell, Table},
+    terminal, Model, OutputFormat, SortBy, SourceInfo, MODEL_PAGE_URL,
+};
+use chrono::TimeDelta;
+use clap::ValueEnum;
+use plotters::{
+    coord::Shift,
+    prelude::*,
+    style::{
+        text_anchor::{HPos, Pos, VPos},
+        FontDesc,
+    },
+};
+use std::{collections::BTreeMap, path::Path};
+
+/// Width to draw for when there's no terminal to measure
+const DEFAULT_WIDTH: usize = 80;
+
+/// Model IDs longer than this share of the width are cut short to leave room for the bars
+const MAX_LABEL_SHARE: f64 = 0.4;
+
+/// Partial blocks for drawing bars to an eighth of a character
+const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Image size and spacing for chart images, in pixels
+const IMAGE_WIDTH: u32 = 800;
+const IMAGE_MARGIN: u32 = 20;
+const IMAGE_ROW_HEIGHT: u32 = 22;
+const IMAGE_PLOT_SIZE: u32 = 520;
+
+/// Font sizes in chart images for the heading, other text, and model names beside points
+const HEADING_SIZE: f64 = 16.0;
+const TEXT_SIZE: f64 = 12.0;
+const POINT_LABEL_SIZE: f64 = 9.0;
+
+const MUTED: RGBColor = RGBColor(0x44, 0x44, 0x44);
+const DIAGONAL: RGBColor = RGBColor(0x99, 0x99, 0x99);
+
+/// Rows and column limits for scatter plots in the terminal
+const SCATTER_HEIGHT: usize = 20;
+const MIN_SCATTER_WIDTH: usize = 20;
+const MAX_SCATTER_WIDTH: usize = 100;
+
+/// Room past the last column for its tick label
+const MAX_TICK_LABEL: usize = 8;
+
+/// Marks for models in terminal scatter plots, when there are few enough to mark each one
+const MARKERS: &str = "123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Share of models at each end of a price ranking that count as cheap or expensive on it
+const LOPSIDED_SHARE: f64 = 1.0 / 3.0;
+
+/// Provider colors in scatter plots, reused in turn when there are more providers
+const PALETTE: [RGBColor; 10] = [
+    RGBColor(0x4e, 0x79, 0xa7),
+    RGBColor(0xf2, 0x8e, 0x2b),
+    RGBColor(0xe1, 0x57, 0x59),
+    RGBColor(0x76, 0xb7, 0xb2),
+    RGBColor(0x59, 0xa1, 0x4f),
+    RGBColor(0xed, 0xc9, 0x48),
+    RGBColor(0xb0, 0x7a, 0xa1),
+    RGBColor(0xff, 0x9d, 0xa7),
+    RGBColor(0x9c, 0x75, 0x5f),
+    RGBColor(0xba, 0xb0, 0xac),
+];
+
+/// Which price to chart
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Metric {
+    /// Price of input (prompt) tokens
+    Input,
+    /// Price of output (completion) tokens
+    Output,
+}
+
+impl Metric {
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Input => "Input",
+            Metric::Output => "Output",
+        }
+    }
+
+    /// The --sort order that lists models by this price
+    pub fn sort_by(self) -> SortBy {
+        match self {
+            Metric::Input => SortBy::Input,
+            Metric::Output => SortBy::Output,
+        }
+    }
+
+    fn price(self, model: &Model) -> Option<f64> {
+        let price = match self {
+            Metric::Input => &model.pricing.prompt,
+            Metric::Output => &model.pricing.completion,
+        };
+        price.parse().ok()
+    }
+}
+
+/// What to draw
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ChartKind {
+    /// A horizontal bar per model for the --metric price
+    Bar,
+    /// Each model's input price against its output price, on log scales, highlighting
+    /// models that are cheap on one and expensive on the other
+    Scatter,
+}
+
+/// Cut `text` to `width` characters, ending with an ellipsis when anything was cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+/// A bar `length` characters long, drawn in eighths with block characters, or with '#' for
+/// plain output
+fn bar(length: f64) -> String {
+    if terminal::is_plain() {
+        return "#".repeat(length.round() as usize);
+    }
+    let eighths = (length * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(EIGHTHS[eighths % 8]);
+    }
+    bar
+}
+
+/// Models with the `metric` price, and the heading for a chart of them. Routers list a
+/// negative price when it depends on the model they pick, which can't be drawn.
+fn prices<'a>(models: &[&'a Model], metric: Metric) -> (Vec<(&'a Model, f64)>, String) {
+    let prices: Vec<(&Model, f64)> = models
+        .iter()
+        .filter_map(|model| metric.price(model).map(|price| (*model, price)))
+        .filter(|(_, price)| *price >= 0.0)
+        .collect();
+    let heading = format!(
+        "{} prices: {} models, USD per {}",
+        metric.label(),
+        prices.len(),
+        table::price_unit().describe()
+    );
+    (prices, heading)
+}
+
+/// Green for the cheapest through yellow to red for the most expensive, like the terminal's
+/// price colors
+fn heat_color(position: f64) -> RGBColor {
+    let position = position.clamp(0.0, 1.0);
+    let (red, green) = if position < 0.5 {
+        (position * 2.0, 1.0)
+    } else {
+        (1.0, (1.0 - position) * 2.0)
+    };
+    RGBColor((red * 220.0) as u8, (green * 180.0) as u8, 0x40)
+}
+
+fn heading_font() -> FontDesc<'static> {
+    drawing::font(FontFamily::SansSerif, HEADING_SIZE).style(FontStyle::Bold)
+}
+
+/// The bar chart the terminal shows, as an image
+struct BarImage<'a> {
+    prices: Vec<(&'a Model, f64)>,
+    values: Vec<String>,
+    heading: String,
+}
+
+impl<'a> BarImage<'a> {
+    fn new(prices: Vec<(&'a Model, f64)>, heading: String) -> Self {
+        let values = prices
+            .iter()
+            .map(|(_, price)| table::format_price(*price))
+            .collect();
+        Self {
+            prices,
+            values,
+            heading,
+        }
+    }
+
+    /// Where the bars start, the room they have, and the width of the widest price
+    fn columns(&self) -> (u32, u32, u32) {
+        let font = drawing::font(FontFamily::SansSerif, TEXT_SIZE);
+        let widest = |texts: &mut dyn Iterator<Item = &str>| {
+            texts.map(|text| drawing::text_width(&font, text)).max().unwrap_or(0)
+        };
+        let label_width = widest(&mut self.prices.iter().map(|(model, _)| model.id.as_str()));
+        let value_width = widest(&mut self.values.iter().map(String::as_str));
+        let bar_left = IMAGE_MARGIN + label_width + 10;
+        let bar_space = IMAGE_WIDTH
+            .saturating_sub(bar_left + value_width + 10 + IMAGE_MARGIN)
+            .max(100);
+        (bar_left, bar_space, value_width)
+    }
+}
+
+impl Drawing for BarImage<'_> {
+    fn size(&self) -> (u32, u32) {
+        let (bar_left, bar_space, value_width) = self.columns();
+        let heading_width = drawing::text_width(&heading_font(), &self.heading);
+        let width = (bar_left + bar_space + value_width + 30)
+            .max(IMAGE_WIDTH)
+            .max(heading_width + 2 * IMAGE_MARGIN);
+        let height = IMAGE_MARGIN + 30 + self.prices.len() as u32 * IMAGE_ROW_HEIGHT + IMAGE_MARGIN;
+        (width, height)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, area: &DrawingArea<DB, Shift>) -> anyhow::Result<()>
+    where
+        DB::ErrorType: 'static,
+    {
+        let (bar_left, bar_space, _) = self.columns();
+        let font = drawing::font(FontFamily::SansSerif, TEXT_SIZE);
+        let max = self.prices.iter().map(|(_, price)| *price).fold(0.0, f64::max);
+        let top = (IMAGE_MARGIN + 30) as i32;
+        let row = IMAGE_ROW_HEIGHT as i32;
+        let bar_left = bar_left as i32;
+
+        area.fill(&WHITE)?;
+        area.draw(&Text::new(
+            self.heading.as_str(),
+            (IMAGE_MARGIN as i32, IMAGE_MARGIN as i32),
+            heading_font().color(&BLACK),
+        ))?;
+        for (i, ((model, price), value)) in self.prices.iter().zip(&self.values).enumerate() {
+            let share = if max > 0.0 { price / max } else { 0.0 };
+            let length = (share * bar_space as f64).round() as i32;
+            let y = top + i as i32 * row;
+            let middle = y + row / 2;
+            area.draw(&Text::new(
+                model.id.as_str(),
+                (bar_left - 10, middle),
+                font.color(&BLACK).pos(Pos::new(HPos::Right, VPos::Center)),
+            ))?;
+            area.draw(&Rectangle::new(
+                [(bar_left, y + 3), (bar_left + length, y + row - 3)],
+                heat_color(share).filled(),
+            ))?;
+            area.draw(&Text::new(
+                value.as_str(),
+                (bar_left + length + 6, middle),
+                font.color(&BLACK).pos(Pos::new(HPos::Left, VPos::Center)),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+/// A model placed by input and output price, both in the --per unit
+struct Point<'a> {
+    model: &'a Model,
+    input: f64,
+    output: f64,
+    /// The price among the cheapest third of models when the other is among the priciest
+    cheap_on: Option<Metric>,
+}
+
+impl Point<'_> {
+    fn describe_lopsided(&self) -> &'static str {
+        match self.cheap_on {
+            Some(Metric::Input) => "cheap input, expensive output",
+            Some(Metric::Output) => "cheap output, expensive input",
+            None => "",
+        }
+    }
+}
+
+/// Models with input and output prices above zero, which log scales can place, with the
+/// lopsided ones marked
+fn scatter_points<'a>(models: &[&'a Model]) -> anyhow::Result<Vec<Point<'a>>> {
+    let tokens = table::price_unit().tokens();
+    let mut points: Vec<Point> = models
+        .iter()
+        .filter_map(|model| {
+            let input = Metric::Input.price(model)? * tokens;
+            let output = Metric::Output.price(model)? * tokens;
+            (input > 0.0 && output > 0.0).then_some(Point {
+                model,
+                input,
+                output,
+                cheap_on: None,
+            })
+        })
+        .collect();
+    if points.is_empty() {
+        return Err(anyhow::anyhow!(
+            "None of the models have input and output prices above zero to plot"
+        ));
+    }
+
+    // Rank each price among the distinct prices on its axis, from 0.0 (cheapest) to 1.0
+    let ranks = |values: Vec<f64>| {
+        let mut values = values;
+        values.sort_by(f64::total_cmp);
+        values.dedup();
+        move |value: f64| {
+            let rank = values.partition_point(|other| *other < value);
+            rank as f64 / (values.len() - 1).max(1) as f64
+        }
+    };
+    let input_rank = ranks(points.iter().map(|point| point.input).collect());
+    let output_rank = ranks(points.iter().map(|point| point.output).collect());
+    for point in &mut points {
+        let (input, output) = (input_rank(point.input), output_rank(point.output));
+        point.cheap_on = if input <= LOPSIDED_SHARE && output >= 1.0 - LOPSIDED_SHARE {
+            Some(Metric::Input)
+        } else if output <= LOPSIDED_SHARE && input >= 1.0 - LOPSIDED_SHARE {
+            Some(Metric::Output)
+        } else {
+            None
+        };
+    }
+    Ok(points)
+}
+
+/// Powers of ten around every price. Both axes share them, so models charging the same for
+/// input and output lie on the diagonal.
+fn log_bounds(points: &[Point]) -> (i32, i32) {
+    let (low, high) = points
+        .iter()
+        .flat_map(|point| [point.input, point.output])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), price| {
+            (low.min(price), high.max(price))
+        });
+    let low = low.log10().floor() as i32;
+    (low, (high.log10().ceil() as i32).max(low + 1))
+}
+
+/// Where a price falls between the bounds on a log scale, from 0.0 to 1.0
+fn log_position(price: f64, (low, high): (i32, i32)) -> f64 {
+    (price.log10() - low as f64) / (high - low) as f64
+}
+
+/// Input against output price for each model, on matching log scales with a diagonal where
+/// they're equal, colored by provider. Lopsided models are ringed and labeled in bold.
+struct ScatterImage<'a> {
+    points: Vec<Point<'a>>,
+    providers: BTreeMap<&'a str, RGBColor>,
+}
+
+/// Room left of and below the plot for the axes' ticks and descriptions
+const Y_LABEL_AREA: u32 = 70;
+const X_LABEL_AREA: u32 = 50;
+
+impl<'a> ScatterImage<'a> {
+    fn new(points: Vec<Point<'a>>) -> Self {
+        let mut providers: BTreeMap<&str, RGBColor> = BTreeMap::new();
+        for point in &points {
+            providers.insert(point.model.id.split('/').next().unwrap_or("unknown"), BLACK);
+        }
+        for (i, color) in providers.values_mut().enumerate() {
+            *color = PALETTE[i % PALETTE.len()];
+        }
+        Self { points, providers }
+    }
+
+    fn has_lopsided(&self) -> bool {
+        self.points.iter().any(|point| point.cheap_on.is_some())
+    }
+
+    /// Width and height of the plot with its axes, left of the legend
+    fn plot_size() -> (u32, u32) {
+        (
+            IMAGE_MARGIN + Y_LABEL_AREA + IMAGE_PLOT_SIZE + 10,
+            IMAGE_MARGIN + 40 + IMAGE_PLOT_SIZE + X_LABEL_AREA + IMAGE_MARGIN,
+        )
+    }
+}
+
+const LOPSIDED_LABEL: &str = "cheap on one price, expensive on the other";
+
+impl Drawing for ScatterImage<'_> {
+    fn size(&self) -> (u32, u32) {
+        let (plot_width, plot_height) = Self::plot_size();
+        let font = drawing::font(FontFamily::SansSerif, TEXT_SIZE);
+        let legend_width = self
+            .providers
+            .keys()
+            .map(|provider| drawing::text_width(&font, provider))
+            .chain([drawing::text_width(&font, LOPSIDED_LABEL)])
+            .max()
+            .unwrap_or(0);
+        let legend_height = self.providers.len() as u32 * 18 + if self.has_lopsided() { 26 } else { 0 };
+        let heading_width = drawing::text_width(&heading_font(), &scatter_heading(&self.points));
+        (
+            (plot_width + 26 + legend_width + IMAGE_MARGIN).max(heading_width + 2 * IMAGE_MARGIN),
+            plot_height.max(IMAGE_MARGIN + 40 + legend_height + IMAGE_MARGIN),
+        )
+    }
+
+    fn draw<DB: DrawingBackend>(&self, area: &DrawingArea<DB, Shift>) -> anyhow::Result<()>
+    where
+        DB::ErrorType: 'static,
+    {
+        let (low, high) = log_bounds(&self.points);
+        let (low, high) = (10f64.powi(low), 10f64.powi(high));
+        let font = drawing::font(FontFamily::SansSerif, TEXT_SIZE);
+        let point_font = drawing::font(FontFamily::SansSerif, POINT_LABEL_SIZE);
+        let unit = table::price_unit().describe();
+        let top = IMAGE_MARGIN + 40;
+
+        area.fill(&WHITE)?;
+        area.draw(&Text::new(
+            scatter_heading(&self.points),
+            (IMAGE_MARGIN as i32, IMAGE_MARGIN as i32),
+            heading_font().color(&BLACK),
+        ))?;
+        let (plot_area, legend_area) = area.split_horizontally(Self::plot_size().0);
+        let mut chart = ChartBuilder::on(&plot_area)
+            .margin_top(top)
+            .margin_left(IMAGE_MARGIN)
+            .margin_right(10)
+            .margin_bottom(IMAGE_MARGIN)
+            .x_label_area_size(X_LABEL_AREA)
+            .y_label_area_size(Y_LABEL_AREA)
+            .build_cartesian_2d((low..high).log_scale(), (low..high).log_scale())?;
+        chart
+            .configure_mesh()
+            .x_desc(format!("Input price (USD per {unit})"))
+            .y_desc(format!("Output price (USD per {unit})"))
+            .label_style(font.color(&BLACK))
+            .axis_desc_style(font.color(&BLACK))
+            .light_line_style(BLACK.mix(0.04))
+            .x_label_formatter(&|price| table::format_decimal(*price))
+            .y_label_formatter(&|price| table::format_decimal(*price))
+            .draw()?;
+        chart.draw_series(DashedLineSeries::new(
+            [(low, low), (high, high)],
+            4,
+            4,
+            DIAGONAL.stroke_width(1),
+        ))?;
+
+        let plot = chart.plotting_area();
+        for point in &self.points {
+            let id = &point.model.id;
+            let provider = id.split('/').next().unwrap_or("unknown");
+            let position = (point.input, point.output);
+            plot.draw(&Circle::new(position, 4, self.providers[provider].filled()))?;
+            let label_font = match point.cheap_on {
+                Some(_) => {
+                    plot.draw(&Circle::new(position, 5, BLACK.stroke_width(2)))?;
+                    point_font.style(FontStyle::Bold)
+                }
+                None => point_font.clone(),
+            };
+            let name = id.split_once('/').map_or(id.as_str(), |(_, name)| name);
+            plot.draw(
+                &(EmptyElement::at(position)
+                    + Text::new(name.to_string(), (6, -5), label_font.color(&MUTED))),
+            )?;
+        }
+
+        let mut y = top as i32;
+        for (provider, color) in &self.providers {
+            legend_area.draw(&Rectangle::new([(10, y), (20, y + 10)], color.filled()))?;
+            legend_area.draw(&Text::new(
+                *provider,
+                (26, y + 5),
+                font.color(&BLACK).pos(Pos::new(HPos::Left, VPos::Center)),
+            ))?;
+            y += 18;
+        }
+        if self.has_lopsided() {
+            legend_area.draw(&Circle::new((15, y + 9), 4, BLACK.stroke_width(2)))?;
+            legend_area.draw(&Text::new(
+                LOPSIDED_LABEL,
+                (26, y + 9),
+                font.color(&BLACK).pos(Pos::new(HPos::Left, VPos::Center)),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+fn scatter_heading(points: &[Point]) -> String {
+    format!(
+        "Input vs output prices: {} models, USD per {}",
+        points.len(),
+        table::price_unit().describe()
+    )
+}
+
+/// Plot models on a grid of input price across and output price up, marking each with a
+/// character listed below when there are few enough, or with counts when there are many
+fn print_scatter(points: &[Point]) {
+    let bounds = log_bounds(points);
+    let (low, high) = bounds;
+    let plain = terminal::is_plain();
+    let color = terminal::use_color();
+    let labelled = points.len() <= MARKERS.len();
+    let markers: Vec<char> = MARKERS.chars().collect();
+
+    let rows = SCATTER_HEIGHT;
+    let labels: Vec<String> = (low..=high).map(|power| 10f64.powi(power).to_string()).collect();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let width = terminal::width().unwrap_or(DEFAULT_WIDTH);
+    let columns = width
+        .saturating_sub(label_width + 2)
+        .clamp(MIN_SCATTER_WIDTH, MAX_SCATTER_WIDTH);
+    let column = |price: f64| (log_position(price, bounds) * (columns - 1) as f64).round() as usize;
+    let row = |price: f64| rows - 1 - (log_position(price, bounds) * (rows - 1) as f64).round() as usize;
+
+    let mut grid: Vec<Vec<Vec<usize>>> = vec![vec![Vec::new(); columns]; rows];
+    for (i, point) in points.iter().enumerate() {
+        grid[row(point.output)][column(point.input)].push(i);
+    }
+    let mut row_labels: BTreeMap<usize, &str> = BTreeMap::new();
+    let mut column_labels: BTreeMap<usize, &str> = BTreeMap::new();
+    for (power, label) in (low..=high).zip(&labels) {
+        let price = 10f64.powi(power);
+        row_labels.insert(row(price), label);
+        column_labels.insert(column(price), label);
+    }
+
+    print_heading(&scatter_heading(points));
+    for (r, cells) in grid.iter().enumerate() {
+        let label = row_labels.get(&r).copied().unwrap_or("");
+        let axis = match (plain, row_labels.contains_key(&r)) {
+            (true, true) => '+',
+            (true, false) => '|',
+            (false, true) => '┤',
+            (false, false) => '│',
+        };
+        let mut line = format!("{label:>label_width$} {axis}");
+        // Equal input and output prices lie on the diagonal
+        let diagonal =
+            ((rows - 1 - r) as f64 / (rows - 1) as f64 * (columns - 1) as f64).round() as usize;
+        for (c, cell) in cells.iter().enumerate() {
+            let mark = match cell.len() {
+                0 if c == diagonal => if plain { '.' } else { '·' },
+                0 => ' ',
+                1 if labelled => markers[cell[0]],
+                _ if labelled => '+',
+                count => char::from_digit(count.min(10) as u32, 10).unwrap_or('*'),
+            };
+            let lopsided = cell.iter().any(|&i| points[i].cheap_on.is_some());
+            if lopsided && color {
+                line.push_str(&terminal::heat(&mark.to_string(), 1.0));
+            } else {
+                line.push(mark);
+            }
+        }
+        println!("{}", line.trim_end());
+    }
+
+    let (corner, rule, tick) = if plain { ('+', '-', '+') } else { ('└', '─', '┬') };
+    let mut axis = format!("{:label_width$} {corner}", "");
+    let mut ticks = vec![' '; columns + MAX_TICK_LABEL];
+    for c in 0..columns {
+        axis.push(if column_labels.contains_key(&c) { tick } else { rule });
+    }
+    // Center each label under its tick, skipping any that would run into the one before
+    let mut free_from = 0;
+    for (&c, label) in &column_labels {
+        let start = c.saturating_sub(label.len() / 2).max(free_from);
+        if start + label.len() > ticks.len() {
+            break;
+        }
+        ticks[start..start + label.len()].copy_from_slice(&label.chars().collect::<Vec<_>>());
+        free_from = start + label.len() + 1;
+    }
+    println!("{axis}");
+    let ticks: String = ticks.into_iter().collect();
+    println!("{:label_width$}  {}", "", ticks.trim_end());
+
+    let unit = table::price_unit().describe();
+    let lopsided: Vec<(usize, &Point)> = points
+        .iter()
+        .enumerate()
+        .filter(|(_, point)| labelled || point.cheap_on.is_some())
+        .collect();
+    if !lopsided.is_empty() {
+        println!();
+        let mut headers = vec![
+            "Model".to_string(),
+            price_header("Input"),
+            price_header("Output"),
+            "Lopsided".to_string(),
+        ];
+        if labelled {
+            headers.insert(0, "Mark".to_string());
+        }
+        let mut table = Table::new(headers);
+        for (i, point) in lopsided {
+            let mut cells = vec![
+                model_link(&point.model.id),
+                Cell::Price(point.input / table::price_unit().tokens()),
+                Cell::Price(point.output / table::price_unit().tokens()),
+                Cell::text(point.describe_lopsided()),
+            ];
+            if labelled {
+                cells.insert(0, Cell::text(markers[i].to_string()));
+            }
+            table.push(cells);
+        }
+        table.print();
+    }
+    print_note(&format!(
+        "Input price across, output price up, in USD per {unit} on log scales; dots mark equal \
+         prices. Lopsided models are among the cheapest third on one price and the priciest \
+         third on the other{}.",
+        if color { ", shown in red" } else { "" }
+    ));
+}
+
+/// Draw the chart as an image at `path`, as PNG for a .png file and SVG otherwise
+fn write_image(models: &[&Model], metric: Metric, kind: ChartKind, path: &Path) -> anyhow::Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let format = match extension.as_deref() {
+        None | Some("svg") => drawing::Format::Svg,
+        Some("png") => drawing::Format::Png,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported chart file '{}'. Use a .svg or .png extension",
+                path.display()
+            ))
+        }
+    };
+
+    let (charted, skipped) = match kind {
+        ChartKind::Bar => {
+            let (prices, heading) = prices(models, metric);
+            let charted = prices.len();
+            drawing::save(&BarImage::new(prices, heading), path, format)?;
+            (charted, models.len() - charted)
+        }
+        ChartKind::Scatter => {
+            let points = scatter_points(models)?;
+            let charted = points.len();
+            drawing::save(&ScatterImage::new(points), path, format)?;
+            (charted, models.len() - charted)
+        }
+    };
+    println!("Wrote a chart of {charted} models to {}", path.display());
+    if skipped > 0 {
+        let reason = match kind {
+            ChartKind::Bar => format!("without a {} price", metric.label().to_lowercase()),
+            ChartKind::Scatter => "that are free or missing a price".to_string(),
+        };
+        eprintln!("{skipped} models {reason} aren't shown");
+    }
+    Ok(())
+}
+
+/// Draw a horizontal bar per model, scaled to the most expensive, so relative prices are
+/// clear at a glance. With `out`, the chart is drawn as an SVG or PNG image there instead; formats
+/// other than table get the prices as a table.
+pub fn print(
+    models: &[&Model],
+    metric: Metric,
+    kind: ChartKind,
+    out: Option<&Path>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    if let Some(out) = out {
+        return write_image(models, metric, kind, out);
+    }
+    if kind == ChartKind::Scatter {
+        let points = scatter_points(models)?;
+        if format != OutputFormat::Table {
+            let mut table = Table::new([
+                "Model".to_string(),
+                price_header("Input"),
+                price_header("Output"),
+                "Lopsided".to_string(),
+            ]);
+            let tokens = table::price_unit().tokens();
+            for point in &points {
+                table.push(vec![
+                    model_link(&point.model.id),
+                    Cell::Price(point.input / tokens),
+                    Cell::Price(point.output / tokens),
+                    Cell::text(point.describe_lopsided()),
+                ]);
+            }
+            let heading = scatter_heading(&points);
+            return print_titled_table(&heading, &table, None, format, source, stale_after);
+        }
+        print_scatter(&points);
+        return Ok(());
+    }
+
+    let (prices, heading) = prices(models, metric);
+
+    if format != OutputFormat::Table {
+        let mut table = Table::new(["Model".to_string(), price_header(metric.label())]);
+        for (model, price) in &prices {
+            table.push(vec![model_link(&model.id), Cell::Price(*price)]);
+        }
+        return print_titled_table(&heading, &table, None, format, source, stale_after);
+    }
+
+    let width = terminal::width().unwrap_or(DEFAULT_WIDTH);
+    let labels: Vec<String> = prices
+        .iter()
+        .map(|(model, _)| truncate(&model.id, (width as f64 * MAX_LABEL_SHARE) as usize))
+        .collect();
+    let values: Vec<String> = prices
+        .iter()
+        .map(|(_, price)| table::format_price(*price))
+        .collect();
+    let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+    let value_width = values.iter().map(String::len).max().unwrap_or(0);
+    let bar_width = width.saturating_sub(label_width + value_width + 2).max(1);
+    let max = prices.iter().map(|(_, price)| *price).fold(0.0, f64::max);
+
+    print_heading(&heading);
+    for (((model, price), label), value) in prices.iter().zip(&labels).zip(&values) {
+        let share = if max > 0.0 { price / max } else { 0.0 };
+        let padding = " ".repeat(label_width - label.chars().count());
+        let label = if terminal::use_links() {
+            terminal::hyperlink(label, &format!("{MODEL_PAGE_URL}{}", model.id))
+        } else {
+            label.clone()
+        };
+        let bar = bar(share * bar_width as f64);
+        let gap = " ".repeat(bar_width.saturating_sub(bar.chars().count()));
+        let bar = if terminal::use_color() {
+            terminal::heat(&bar, share)
+        } else {
+            bar
+        };
+        println!("{label}{padding} {bar}{gap} {value:>value_width$}");
+    }
+
+    let skipped = models.len() - prices.len();
+    if skipped > 0 {
+        print_note(&format!(
+            "{skipped} models without a {} price aren't shown.",
+            metric.label().to_lowercase()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::{
+    model_link, price_header, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+use clap::ValueEnum;
+use std::{fmt, str::FromStr};
+
+/// What "cheapest" is measured by
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Metric {
+    Input,
+    Output,
+    /// Input and output prices weighted by --mix
+    #[default]
+    Blended,
+}
+
+/// Input to output token ratio for blended prices, written as INPUT:OUTPUT
+#[derive(Debug, Clone, Copy)]
+pub struct Mix {
+    input: f64,
+    output: f64,
+}
+
+impl FromStr for Mix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let invalid = || anyhow::anyhow!("Invalid mix: '{}'. Expected INPUT:OUTPUT (e.g., '3:1')", s);
+        let (input, output) = s.split_once(':').ok_or_else(invalid)?;
+        let parse = |part: &str| part.trim().parse::<f64>().ok().filter(|weight| *weight >= 0.0);
+        let (input, output) = (parse(input).ok_or_else(invalid)?, parse(output).ok_or_else(invalid)?);
+        if input + output == 0.0 {
+            return Err(invalid());
+        }
+        Ok(Self { input, output })
+    }
+}
+
+impl fmt::Display for Mix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.input, self.output)
+    }
+}
+
+impl Mix {
+    /// Price per token of a blend of input and output tokens in this ratio
+    pub fn blend(self, input: f64, output: f64) -> f64 {
+        (input * self.input + output * self.output) / (self.input + self.output)
+    }
+}
+
+/// How to rank models and how many to keep
+pub struct Ranking {
+    pub count: usize,
+    pub metric: Metric,
+    pub mix: Mix,
+    /// Rank models with both prices at zero too; they'd otherwise fill the list
+    pub include_free: bool,
+}
+
+/// Print the cheapest models by the ranking's metric, cheapest first
+pub fn print(
+    models: &[&Model],
+    ranking: &Ranking,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let Ranking {
+        count,
+        metric,
+        mix,
+        include_free,
+    } = *ranking;
+    let mut ranked: Vec<(&Model, f64, f64, f64)> = models
+        .iter()
+        .filter_map(|model| {
+            let input = model.pricing.prompt.parse::<f64>().ok().filter(|price| *price >= 0.0)?;
+            let output = model.pricing.completion.parse::<f64>().ok().filter(|price| *price >= 0.0)?;
+            let value = match metric {
+                Metric::Input => input,
+                Metric::Output => output,
+                Metric::Blended => mix.blend(input, output),
+            };
+            Some((*model, input, output, value))
+        })
+        .filter(|(_, input, output, _)| include_free || *input > 0.0 || *output > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| a.3.total_cmp(&b.3).then_with(|| a.0.id.cmp(&b.0.id)));
+    ranked.truncate(count);
+    if ranked.is_empty() {
+        return Err(anyhow::anyhow!("No priced models match"));
+    }
+
+    let mut table = Table::new([
+        "#".to_string(),
+        "Model".to_string(),
+        price_header("Input"),
+        price_header("Output"),
+        price_header(&format!("Blended {mix}")),
+        "Context".to_string(),
+    ]);
+    for (rank, (model, input, output, _)) in ranked.iter().enumerate() {
+        table.push(vec![
+            Cell::Number((rank + 1) as f64, 0),
+            model_link(&model.id),
+            Cell::Price(*input),
+            Cell::Price(*output),
+            Cell::Price(mix.blend(*input, *output)),
+            model
+                .context_length
+                .map_or(Cell::Missing, |context| Cell::Number(context as f64, 0)),
+        ]);
+    }
+
+    let by = match metric {
+        Metric::Input => "input price".to_string(),
+        Metric::Output => "output price".to_string(),
+        Metric::Blended => format!("blended price ({mix} input to output tokens)"),
+    };
+    let mut note = "Prices in USD.".to_string();
+    if !include_free {
+        note.push_str(" Free models are left out; pass --include-free to rank them too.");
+    }
+    print_titled_table(
+        &format!("{} cheapest models by {by}", ranked.len()),
+        &table,
+        Some(&note),
+        format,
+        source,
+        stale_after,
+    )
+}
@@ -0,0 +1,29 @@
+use crate::{capture, terminal};
+use anyhow::Context;
+use std::io::{self, IsTerminal, Write};
+
+/// Copy the rest of stdout to the system clipboard once the command is done, printing it as
+/// well. In a terminal, tables stay aligned but lose colors and links, so the copy pastes
+/// cleanly.
+pub fn start() -> anyhow::Result<()> {
+    let shown = io::stdout().is_terminal();
+    capture::start(copy).context("Failed to capture output for --copy")?;
+    if shown {
+        terminal::set_copying();
+    }
+    Ok(())
+}
+
+fn copy(output: String) {
+    print!("{output}");
+    let _ = io::stdout().flush();
+    if output.trim().is_empty() {
+        return;
+    }
+
+    let lines = output.lines().count();
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(output)) {
+        Ok(()) => eprintln!("Copied {lines} lines to the clipboard"),
+        Err(e) => eprintln!("Warning: couldn't copy to the clipboard: {e}"),
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    calculate_cost, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+
+/// A prompt compression scenario: how much of the prompt survives and how much longer
+/// responses are expected to get from working with the compressed prompt
+pub struct Compression {
+    pub input: u64,
+    pub output: u64,
+    /// Fraction of input tokens kept after compression, e.g. 0.6
+    pub ratio: f64,
+    /// Expected increase in output tokens, as a fraction of the original output
+    pub output_risk: f64,
+}
+
+impl Compression {
+    fn compressed_input(&self) -> u64 {
+        (self.input as f64 * self.ratio).round() as u64
+    }
+
+    fn risked_output(&self) -> u64 {
+        (self.output as f64 * (1.0 + self.output_risk)).round() as u64
+    }
+}
+
+/// Print per-model savings from compressing prompts, net of the expected extra output
+pub fn print(
+    models: &[&Model],
+    compression: &Compression,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut rows = Vec::new();
+    for model in models {
+        let original = calculate_cost(model, compression.input, compression.output, None, 5)?;
+        let compressed = calculate_cost(
+            model,
+            compression.compressed_input(),
+            compression.risked_output(),
+            None,
+            5,
+        )?;
+        rows.push((model, original, compressed));
+    }
+    // Biggest savings first
+    rows.sort_by(|(_, a_original, a_compressed), (_, b_original, b_compressed)| {
+        let a_savings = a_original.total_cost - a_compressed.total_cost;
+        let b_savings = b_original.total_cost - b_compressed.total_cost;
+        b_savings.total_cmp(&a_savings)
+    });
+
+    let mut table = Table::new([
+        "Model",
+        "Original",
+        "Compressed",
+        "Savings",
+        "Savings %",
+        "Break-even Extra Output",
+    ]);
+    for (model, original, compressed) in &rows {
+        let savings = original.total_cost - compressed.total_cost;
+        let input_savings = original.input.cost - compressed.input.cost;
+        // Extra output tokens that would cost as much as the input compression saves
+        let break_even = match compressed.output.rate {
+            rate if rate > 0.0 => Cell::Number(input_savings / rate, 0),
+            _ => Cell::Missing,
+        };
+
+        table.push(vec![
+            Cell::text(&model.id),
+            Cell::Cost(original.total_cost),
+            Cell::Cost(compressed.total_cost),
+            Cell::Cost(savings),
+            if original.total_cost > 0.0 {
+                Cell::Number(savings / original.total_cost * 100.0, 1)
+            } else {
+                Cell::Missing
+            },
+            break_even,
+        ]);
+    }
+
+    let heading = format!(
+        "Compression savings: {} input compressed to {} ({:.0}%), {} output{}",
+        compression.input,
+        compression.compressed_input(),
+        compression.ratio * 100.0,
+        compression.output,
+        if compression.output_risk > 0.0 {
+            format!(" expected to grow to {}", compression.risked_output())
+        } else {
+            String::new()
+        }
+    );
+    let note = "Break-even Extra Output: additional output tokens per request that would cancel \
+                out the input savings.";
+
+    print_titled_table(&heading, &table, Some(note), format, source, stale_after)
+}
@@ -0,0 +1,135 @@
+use crate::{carbon::Emissions, i18n::Lang, paths, OutputFormat};
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Internal governance decision about whether a model may be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Approval {
+    /// Cleared for production use
+    Approved,
+    /// Allowed for evaluation, not yet for production
+    Trial,
+    /// Must not be used
+    Banned,
+}
+
+impl Approval {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Approval::Approved => "approved",
+            Approval::Trial => "trial",
+            Approval::Banned => "banned",
+        }
+    }
+}
+
+/// Conditions `alert check` reports on. A rule triggers when any of its conditions holds for
+/// any model it covers.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AlertRule {
+    /// Models covered, as a name filter (e.g., 'anthropic/'); every model when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Input price thresholds in USD per million tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_below: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_above: Option<f64>,
+    /// Output price thresholds in USD per million tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_below: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_above: Option<f64>,
+    /// Percentage the input or output price moved by, either way, over `within`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_percent: Option<f64>,
+    /// How far back to look for a change, as a duration (e.g., '7d') [default: 1d]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub within: Option<String>,
+}
+
+/// Who invoices are from, shown at the top of every `invoice`
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct InvoiceBranding {
+    /// Business name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Address lines under the name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address: Vec<String>,
+    /// Text added to the footer, such as payment terms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Output format used when --output isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+    /// Language used when --lang isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Lang>,
+    /// Reuse the fetched model list for a while instead of fetching on every run
+    #[serde(default)]
+    pub cache: bool,
+    /// Free-text notes about models, keyed by model ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub notes: BTreeMap<String, String>,
+    /// Approval status of models, keyed by model ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub approvals: BTreeMap<String, Approval>,
+    /// Saved invocations, keyed by name, stored as the arguments that follow `llm-pricing`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub queries: BTreeMap<String, Vec<String>>,
+    /// Names of additional API keys per provider. The keys themselves stay in the OS keychain.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, Vec<String>>,
+    /// Google service account key file used by `export --google-sheet`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub google_service_account: Option<PathBuf>,
+    /// Model that `hook` prices prompt templates at when --model isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_model: Option<String>,
+    /// Price alert rules, keyed by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub alerts: BTreeMap<String, AlertRule>,
+    /// Branding for `invoice`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoice: Option<InvoiceBranding>,
+    /// How the carbon column and `forecast --carbon` estimate emissions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carbon: Option<Emissions>,
+    /// Regions providers serve from, keyed by provider name or endpoint tag, replacing the
+    /// bundled entries for `--region` (e.g., Mistral = ["eu"])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub regions: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    /// Load the config file, or an empty config if none exists yet
+    pub fn load() -> anyhow::Result<Self> {
+        let path = paths::config_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let path = paths::config_file()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory {}", parent.display()))?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write config file {}", path.display()))?;
+        Ok(path)
+    }
+}
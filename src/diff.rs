@@ -0,0 +1,282 @@
+use crate::{
+    history::{self, Prices},
+    model_link, print_titled_table, MODEL_PAGE_URL,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{NaiveDate, TimeDelta, Utc};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+/// Model lists as the OpenRouter API returns them, as `--output json` and the cache write
+/// them, or as a bare array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SnapshotFile {
+    Api { data: Vec<Model> },
+    List { models: Vec<Model> },
+    Models(Vec<Model>),
+}
+
+/// Every model's prices at some point, and a description of where they came from for the
+/// heading
+pub struct Snapshot {
+    label: String,
+    prices: BTreeMap<String, Prices>,
+    /// Canonical slugs by model ID, which stay the same when a model is renamed. The price
+    /// history doesn't keep them.
+    slugs: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    pub fn from_models(models: &[Model], label: String) -> Self {
+        Self {
+            label,
+            prices: models.iter().map(|model| (model.id.clone(), Prices::of(model))).collect(),
+            slugs: models
+                .iter()
+                .filter_map(|model| Some((model.id.clone(), model.canonical_slug.clone()?)))
+                .collect(),
+        }
+    }
+
+    /// A model list saved from the API, `--output json` or the cache
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: SnapshotFile = serde_json::from_str(&contents).with_context(|| {
+            format!(
+                "{} isn't a model list; save one with 'llm-pricing --output json > FILE'",
+                path.display()
+            )
+        })?;
+        let models = match file {
+            SnapshotFile::Api { data } => data,
+            SnapshotFile::List { models } | SnapshotFile::Models(models) => models,
+        };
+        Ok(Self::from_models(&models, path.display().to_string()))
+    }
+
+    /// Prices as recorded in the local history on `date`
+    pub fn from_history(date: NaiveDate) -> anyhow::Result<Self> {
+        let (fetched_at, prices) = history::prices_on(date)?;
+        Ok(Self {
+            label: format!("history as of {date} (fetched {})", fetched_at.format("%Y-%m-%d %H:%M UTC")),
+            prices,
+            slugs: BTreeMap::new(),
+        })
+    }
+
+    /// Whether the same models are listed in both snapshots at the same prices
+    pub fn same_as(&self, other: &Snapshot) -> bool {
+        self.prices.len() == other.prices.len()
+            && self.prices.iter().all(|(id, prices)| {
+                other.prices.get(id).is_some_and(|other| {
+                    same_price(&prices.input, &other.input) && same_price(&prices.output, &other.output)
+                })
+            })
+    }
+}
+
+/// A model's canonical slug, or its ID when the slug isn't known
+fn slug<'a>(snapshot: &'a Snapshot, id: &'a str) -> &'a str {
+    snapshot.slugs.get(id).map_or(id, String::as_str)
+}
+
+/// The provider and the words of the rest of an ID, so "google/gemini-pro-1.5" and
+/// "google/gemini-1.5-pro" come out the same
+fn id_words(id: &str) -> (&str, BTreeSet<&str>) {
+    let (provider, name) = id.split_once('/').unwrap_or(("", id));
+    let name = name.split(':').next().unwrap_or(name);
+    (provider, name.split(['-', '_', '.']).filter(|word| !word.is_empty()).collect())
+}
+
+/// Whether a model that disappeared and one that appeared are likely the same model under a
+/// new ID: the same canonical slug, or failing that the same provider and either the same
+/// words in another order, or the same prices with one ID's words a subset of the other's
+fn is_rename(old: &Snapshot, old_id: &str, new: &Snapshot, new_id: &str) -> bool {
+    if old.slugs.contains_key(old_id) && new.slugs.contains_key(new_id) {
+        return slug(old, old_id) == slug(new, new_id);
+    }
+    if slug(new, new_id) == old_id {
+        return true;
+    }
+    let (old_provider, old_words) = id_words(old_id);
+    let (new_provider, new_words) = id_words(new_id);
+    if old_provider != new_provider {
+        return false;
+    }
+    let same_prices = match (old.prices.get(old_id), new.prices.get(new_id)) {
+        (Some(old), Some(new)) => same_price(&old.input, &new.input) && same_price(&old.output, &new.output),
+        _ => false,
+    };
+    old_words == new_words
+        || (same_prices && (old_words.is_subset(&new_words) || new_words.is_subset(&old_words)))
+}
+
+/// Pairs of old and new IDs of models that look renamed between the snapshots, each ID in at
+/// most one pair
+pub fn renames(old: &Snapshot, new: &Snapshot) -> Vec<(String, String)> {
+    let mut added: Vec<&String> = new.prices.keys().filter(|id| !old.prices.contains_key(*id)).collect();
+    let mut pairs = Vec::new();
+    for old_id in old.prices.keys().filter(|id| !new.prices.contains_key(*id)) {
+        if let Some(index) = added.iter().position(|new_id| is_rename(old, old_id, new, new_id)) {
+            pairs.push((old_id.clone(), added.remove(index).clone()));
+        }
+    }
+    pairs
+}
+
+/// Warn when models have disappeared or changed ID since the last fetch in the price history,
+/// since scripts pinned to a model ID stop finding it without any other sign
+pub fn warn_removed(models: &[Model]) {
+    let Ok(Some((fetched_at, prices))) = history::prices_at(Utc::now()) else {
+        return;
+    };
+    let old = Snapshot {
+        label: String::new(),
+        prices,
+        slugs: BTreeMap::new(),
+    };
+    let new = Snapshot::from_models(models, String::new());
+    let renames = renames(&old, &new);
+    let mut gone: Vec<String> = old
+        .prices
+        .keys()
+        .filter(|id| !new.prices.contains_key(*id) && !renames.iter().any(|(old_id, _)| old_id == *id))
+        .cloned()
+        .collect();
+    gone.extend(renames.iter().map(|(old_id, new_id)| format!("{old_id} (now {new_id}?)")));
+    if gone.is_empty() {
+        return;
+    }
+    eprintln!(
+        "Warning: {} models listed at the last fetch ({}) are gone: {}. Scripts using these IDs \
+         will no longer find them; see 'llm-pricing diff --since DATE'.",
+        gone.len(),
+        fetched_at.format("%Y-%m-%d %H:%M UTC"),
+        gone.join(", ")
+    );
+}
+
+/// Per-token price, or None for prices the API gives as something other than a number
+fn price(price: &str) -> Option<f64> {
+    price.parse().ok()
+}
+
+/// Compare as numbers so "0.000003" and "3e-6" aren't a change
+fn same_price(old: &str, new: &str) -> bool {
+    match (price(old), price(new)) {
+        (Some(old), Some(new)) => old == new,
+        _ => old == new,
+    }
+}
+
+fn price_cell(price: Option<&str>) -> Cell {
+    price.map_or(Cell::Missing, Cell::price_str)
+}
+
+fn change_cell(old: &str, new: &str) -> Cell {
+    match (price(old), price(new)) {
+        (Some(old), Some(new)) if old > 0.0 => Cell::Number((new - old) / old * 100.0, 1),
+        _ => Cell::Missing,
+    }
+}
+
+/// Print the models added and removed between two snapshots and those whose input or output
+/// price changed, with the old and new prices and the percentage change of each
+pub fn print(
+    old: &Snapshot,
+    new: &Snapshot,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut table = Table::new([
+        "Change", "Model", "Old Input", "New Input", "Input %", "Old Output", "New Output", "Output %",
+    ]);
+    let renames = renames(old, new);
+    let renamed_from = |id: &str| renames.iter().any(|(old_id, _)| old_id == id);
+    let renamed_to = |id: &str| renames.iter().any(|(_, new_id)| new_id == id);
+    let (mut removed, mut added, mut changed) = (0, 0, 0);
+    for (old_id, new_id) in &renames {
+        let (old_prices, new_prices) = (&old.prices[old_id], &new.prices[new_id]);
+        table.push(vec![
+            Cell::text("Renamed"),
+            Cell::Link {
+                text: format!("{old_id} -> {new_id}"),
+                url: format!("{MODEL_PAGE_URL}{new_id}"),
+            },
+            price_cell(Some(&old_prices.input)),
+            price_cell(Some(&new_prices.input)),
+            change_cell(&old_prices.input, &new_prices.input),
+            price_cell(Some(&old_prices.output)),
+            price_cell(Some(&new_prices.output)),
+            change_cell(&old_prices.output, &new_prices.output),
+        ]);
+    }
+    for (id, prices) in &old.prices {
+        if !new.prices.contains_key(id) && !renamed_from(id) {
+            removed += 1;
+            table.push(vec![
+                Cell::text("Removed"),
+                Cell::text(id),
+                price_cell(Some(&prices.input)),
+                Cell::Missing,
+                Cell::Missing,
+                price_cell(Some(&prices.output)),
+                Cell::Missing,
+                Cell::Missing,
+            ]);
+        }
+    }
+    for (id, prices) in &new.prices {
+        if !old.prices.contains_key(id) && !renamed_to(id) {
+            added += 1;
+            table.push(vec![
+                Cell::text("Added"),
+                model_link(id),
+                Cell::Missing,
+                price_cell(Some(&prices.input)),
+                Cell::Missing,
+                Cell::Missing,
+                price_cell(Some(&prices.output)),
+                Cell::Missing,
+            ]);
+        }
+    }
+    for (id, new_prices) in &new.prices {
+        let Some(old_prices) = old.prices.get(id) else {
+            continue;
+        };
+        if same_price(&old_prices.input, &new_prices.input)
+            && same_price(&old_prices.output, &new_prices.output)
+        {
+            continue;
+        }
+        changed += 1;
+        table.push(vec![
+            Cell::text("Changed"),
+            model_link(id),
+            price_cell(Some(&old_prices.input)),
+            price_cell(Some(&new_prices.input)),
+            change_cell(&old_prices.input, &new_prices.input),
+            price_cell(Some(&old_prices.output)),
+            price_cell(Some(&new_prices.output)),
+            change_cell(&old_prices.output, &new_prices.output),
+        ]);
+    }
+
+    let heading = format!("Price changes from {} to {}", old.label, new.label);
+    let note = format!(
+        "{added} added, {removed} removed, {} renamed, {changed} with changed prices. Prices in \
+         USD; % is the change from the old price. Renamed models are matched by canonical slug, \
+         or by ID and price when the slug isn't known.",
+        renames.len()
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
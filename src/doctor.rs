@@ -0,0 +1,223 @@
+use crate::{account, auth, cache, config::Config, fetch_catalog, format_duration, paths};
+use chrono::Utc;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Result of a single diagnostic check, with a suggested fix when it doesn't pass
+struct Check {
+    name: String,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Status::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Print resolved paths and diagnostic checks, returning whether every check passed
+pub async fn run() -> anyhow::Result<bool> {
+    let config_file = paths::config_file()?;
+    let config_dir = config_file
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let cache_dir = paths::cache_dir()?;
+    let data_dir = paths::data_dir()?;
+
+    println!("Paths:");
+    for (label, path) in [
+        ("Config file", &config_file),
+        ("Cache dir", &cache_dir),
+        ("Data dir", &data_dir),
+    ] {
+        let state = if path.exists() { "" } else { " (not created yet)" };
+        println!("  {:<12} {}{}", format!("{label}:"), path.display(), state);
+    }
+
+    let checks = vec![
+        check_writable("Config directory", &config_dir),
+        check_writable("Cache directory", &cache_dir),
+        check_writable("Data directory", &data_dir),
+        check_config(&config_file),
+        check_cache(),
+        check_api_key().await,
+        check_connectivity().await,
+    ];
+
+    println!();
+    println!("Checks:");
+    for check in &checks {
+        let marker = match check.status {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "fail",
+        };
+        println!("  [{marker:<4}] {}: {}", check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("         Fix: {fix}");
+        }
+    }
+
+    Ok(checks.iter().all(|check| check.status != Status::Fail))
+}
+
+/// Check that files can be created in `dir`, or in its nearest existing ancestor if it
+/// hasn't been created yet
+fn check_writable(name: &str, dir: &Path) -> Check {
+    let Some(existing) = dir.ancestors().find(|ancestor| ancestor.exists()) else {
+        return Check::fail(
+            name,
+            format!("no existing parent directory for {}", dir.display()),
+            "Check that your home directory exists",
+        );
+    };
+
+    let probe = existing.join(".llm-pricing-write-test");
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            Check::ok(name, "writable")
+        }
+        Err(e) => Check::fail(
+            name,
+            format!("{} is not writable ({e})", existing.display()),
+            format!("Fix the permissions on {}", existing.display()),
+        ),
+    }
+}
+
+fn check_config(config_file: &Path) -> Check {
+    const NAME: &str = "Config file";
+    if !config_file.exists() {
+        return Check::ok(NAME, "not created yet (defaults in use)");
+    }
+    match Config::load() {
+        Ok(config) => Check::ok(NAME, format!("valid ({} saved queries)", config.queries.len())),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{e:#}"),
+            format!("Correct or remove {}", config_file.display()),
+        ),
+    }
+}
+
+/// How old the cached model list is, from the fetch time recorded with it
+fn check_cache() -> Check {
+    const NAME: &str = "Cache";
+    match cache::catalog_fetched_at() {
+        Some(fetched_at) => {
+            let age = Utc::now() - fetched_at;
+            let state = if age > cache::CATALOG_TTL {
+                "the next run fetches it again"
+            } else {
+                "reused when caching is on"
+            };
+            Check::ok(
+                NAME,
+                format!(
+                    "model list fetched {} ago, {state} (kept for {})",
+                    format_duration(age),
+                    format_duration(cache::CATALOG_TTL)
+                ),
+            )
+        }
+        None => Check::ok(NAME, "no model list cached yet"),
+    }
+}
+
+/// Whether OpenRouter accepts the key, checked with an authenticated request to the key
+/// endpoint
+async fn check_api_key() -> Check {
+    const NAME: &str = "OpenRouter API key";
+    let (key, source) = match auth::api_key(auth::DEFAULT_PROVIDER) {
+        Ok(Some(found)) => found,
+        Ok(None) => return Check::ok(NAME, "not set (not needed for public pricing data)"),
+        Err(e) => {
+            return Check::ok(
+                NAME,
+                format!(
+                    "not set (not needed for public pricing data); OS keychain unavailable: {}",
+                    e.root_cause()
+                ),
+            )
+        }
+    };
+
+    match account::fetch_key_info(&reqwest::Client::new(), &key).await {
+        Ok(_) => Check::ok(NAME, format!("{}, accepted by OpenRouter", source.describe())),
+        Err(e) => {
+            let status = e
+                .downcast_ref::<reqwest::Error>()
+                .and_then(|error| error.status());
+            match status {
+                Some(status) if status.as_u16() == 401 || status.as_u16() == 403 => Check::fail(
+                    NAME,
+                    format!("{}, but OpenRouter rejected it ({status})", source.describe()),
+                    "The key is invalid or revoked. Create a new one at https://openrouter.ai/keys \
+                     and store it with 'llm-pricing auth set'",
+                ),
+                _ => Check::warn(
+                    NAME,
+                    format!("{}, but couldn't be verified: {e:#}", source.describe()),
+                    "Check your network connection and run 'llm-pricing doctor' again",
+                ),
+            }
+        }
+    }
+}
+
+async fn check_connectivity() -> Check {
+    const NAME: &str = "OpenRouter API";
+    let started = Instant::now();
+    match fetch_catalog().await {
+        Ok(catalog) => Check::ok(
+            NAME,
+            format!(
+                "reachable, {} models in {}ms",
+                catalog.models.len(),
+                started.elapsed().as_millis()
+            ),
+        ),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{e:#}"),
+            "Check your network connection, proxy settings and https://status.openrouter.ai",
+        ),
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Context;
+use plotters::{
+    coord::Shift,
+    prelude::*,
+    style::{register_font, FontDesc},
+};
+use std::{fs, path::Path, sync::Once};
+
+/// DejaVu Sans and Sans Mono, bundled so PNG images don't depend on installed fonts and
+/// can show accented, Greek and Cyrillic model names. Their license is in data/fonts/LICENSE.
+const SANS_SERIF: &[u8] = include_bytes!("../data/fonts/DejaVuSans.ttf");
+const MONOSPACE: &[u8] = include_bytes!("../data/fonts/DejaVuSansMono.ttf");
+
+/// Image formats that can be drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Svg,
+    Png,
+}
+
+/// An image drawn the same way whichever format it's saved in
+pub trait Drawing {
+    /// Width and height in pixels
+    fn size(&self) -> (u32, u32);
+
+    fn draw<DB: DrawingBackend>(&self, area: &DrawingArea<DB, Shift>) -> anyhow::Result<()>
+    where
+        DB::ErrorType: 'static;
+}
+
+/// The bundled font of `family` at `size` pixels
+pub fn font(family: FontFamily<'static>, size: f64) -> FontDesc<'static> {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        for (name, data) in [("sans-serif", SANS_SERIF), ("monospace", MONOSPACE)] {
+            assert!(
+                register_font(name, FontStyle::Normal, data).is_ok(),
+                "bundled {name} font is invalid"
+            );
+        }
+    });
+    FontDesc::new(family, size, FontStyle::Normal)
+}
+
+/// How wide `text` is in `font`, in pixels
+pub fn text_width(font: &FontDesc, text: &str) -> u32 {
+    font.box_size(text).map_or(0, |(width, _)| width)
+}
+
+/// Draw `drawing` into the file at `path`
+pub fn save(drawing: &impl Drawing, path: &Path, format: Format) -> anyhow::Result<()> {
+    let size = drawing.size();
+    match format {
+        Format::Svg => {
+            let mut svg = String::new();
+            {
+                let area = SVGBackend::with_string(&mut svg, size).into_drawing_area();
+                drawing.draw(&area)?;
+                area.present()?;
+            }
+            fs::write(path, svg).with_context(|| format!("Failed to write {}", path.display()))
+        }
+        Format::Png => {
+            let area = BitMapBackend::new(path, size).into_drawing_area();
+            drawing.draw(&area)?;
+            area.present()
+                .with_context(|| format!("Failed to write {}", path.display()))
+        }
+    }
+}
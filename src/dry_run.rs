@@ -0,0 +1,178 @@
+use crate::{
+    cache, config::Config, format_duration, parse_duration, paths, query_argv, resolve_query, run_overrides,
+    table::{Cell, Table},
+    AlertAction, Args, Commands, ResultsAction, MODELS_URL,
+};
+use chrono::Utc;
+use clap::{parser::ValueSource, ArgAction, ArgMatches, CommandFactory, ValueEnum};
+
+/// The arguments as typed, quoted where a shell would need it
+fn command_line(argv: &[String]) -> String {
+    let words: Vec<String> = argv
+        .iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("'{arg}'")
+            } else {
+                arg.clone()
+            }
+        })
+        .collect();
+    format!("llm-pricing {}", words.join(" "))
+}
+
+/// One row per argument of `command` that has a value, recursing into subcommands. Flags left
+/// at their default are skipped, as they change nothing.
+fn push_arguments(table: &mut Table, command: &clap::Command, matches: &ArgMatches, typed: &str, path: &str) {
+    for arg in command.get_arguments() {
+        let id = arg.get_id().as_str();
+        // Globals are listed once, from the top-level command, except the filters, which a
+        // subcommand lists as its own
+        if !path.is_empty() && arg.is_global_set() {
+            continue;
+        }
+        if path.is_empty() && arg.is_positional() && matches.subcommand().is_some() {
+            continue;
+        }
+        let Some(source) = matches.value_source(id) else {
+            continue;
+        };
+        let is_flag = matches!(arg.get_action(), ArgAction::SetTrue | ArgAction::SetFalse | ArgAction::Count);
+        if matches!(arg.get_action(), ArgAction::Help | ArgAction::Version)
+            || id == "dry_run"
+            || (is_flag && source == ValueSource::DefaultValue)
+        {
+            continue;
+        }
+        let Ok(Some(values)) = matches.try_get_raw(id) else {
+            continue;
+        };
+        let values: Vec<String> = values.map(|value| value.to_string_lossy().into_owned()).collect();
+        let name = match arg.get_long() {
+            Some(long) => format!("{path}--{long}"),
+            None => format!("{path}{id}"),
+        };
+        let from = match source {
+            ValueSource::DefaultValue => "default",
+            ValueSource::EnvVariable => "environment",
+            _ => typed,
+        };
+        table.push(vec![Cell::text(name), Cell::text(values.join(", ")), Cell::text(from)]);
+    }
+
+    if let Some((name, sub_matches)) = matches.subcommand() {
+        if let Some(subcommand) = command.find_subcommand(name) {
+            table.push(vec![Cell::text(format!("{path}command")), Cell::text(name), Cell::text(typed)]);
+            push_arguments(table, subcommand, sub_matches, typed, &format!("{path}{name} "));
+        }
+    }
+}
+
+/// How a value is written on the command line
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map_or_else(String::new, |value| value.get_name().to_string())
+}
+
+/// Whether the command reads the model list at all
+fn uses_prices(command: &Option<Commands>) -> bool {
+    !matches!(
+        command,
+        Some(
+            Commands::SaveQuery { .. }
+                | Commands::Run { name: None }
+                | Commands::Doctor
+                | Commands::Init
+                | Commands::Auth { .. }
+                | Commands::Account
+                | Commands::Approve { .. }
+                | Commands::Results { action: ResultsAction::List }
+                | Commands::Alert {
+                    action: AlertAction::Add { .. } | AlertAction::List | AlertAction::Remove { .. }
+                }
+        )
+    )
+}
+
+/// Where the model list would come from, judged from the cache file's age without fetching
+fn price_source(config: &Config, max_age: Option<&str>) -> anyhow::Result<String> {
+    if !config.cache {
+        return Ok(format!("fetched from {MODELS_URL} (caching is off in the config file)"));
+    }
+    let max_age = max_age.map(parse_duration).transpose()?;
+    let ttl = max_age.map_or(cache::CATALOG_TTL, |max_age| max_age.min(cache::CATALOG_TTL));
+    Ok(match cache::catalog_fetched_at() {
+        Some(fetched_at) if Utc::now() - fetched_at <= ttl => format!(
+            "the cached model list, fetched {} ago (reused until it's {} old)",
+            format_duration(Utc::now() - fetched_at),
+            format_duration(ttl)
+        ),
+        Some(fetched_at) => format!(
+            "fetched from {MODELS_URL}, as the cached model list is {} old (reused for {})",
+            format_duration(Utc::now() - fetched_at),
+            format_duration(ttl)
+        ),
+        None => format!("fetched from {MODELS_URL}, as nothing is cached yet"),
+    })
+}
+
+/// Print the invocation as it would run, without fetching or computing anything: what a saved
+/// query expands to, every argument with its value and whether it was typed or defaulted, the
+/// defaults taken from the config file, and where prices would come from
+pub fn print(args: &Args) -> anyhow::Result<()> {
+    let config = Config::load()?;
+    let typed: Vec<String> = std::env::args().skip(1).collect();
+    println!("Invocation: {}", command_line(&typed));
+
+    let query;
+    let (resolved, argv, from) = match &args.command {
+        Some(Commands::Run { name: Some(name) }) => {
+            query = resolve_query(name)?;
+            let argv = query_argv(&config, name)?;
+            println!("Saved query '{name}' expands to: {}", command_line(&config.queries[name.as_str()]));
+            let overrides = run_overrides(name);
+            if !overrides.is_empty() {
+                println!("Flags given with 'run' apply on top: {}", command_line(&overrides));
+            }
+            (&query, argv, format!("saved query '{name}'"))
+        }
+        _ => (args, typed, "command line".to_string()),
+    };
+    println!();
+
+    let command = Args::command();
+    let matches = command
+        .clone()
+        .try_get_matches_from(std::iter::once("llm-pricing".to_string()).chain(argv))?;
+    let mut table = Table::new(["Setting", "Value", "From"]);
+    push_arguments(&mut table, &command, &matches, &from, "");
+
+    let config_file = format!("config file ({})", paths::config_file()?.display());
+    if resolved.format.is_none() {
+        let (value, from) = match config.output {
+            Some(format) => (value_name(format), config_file.as_str()),
+            None => ("table".to_string(), "default"),
+        };
+        table.push(vec![Cell::text("--output"), Cell::text(value), Cell::text(from)]);
+    }
+    if resolved.lang.is_none() {
+        let (value, from) = match config.lang {
+            Some(lang) => (value_name(lang), config_file.as_str()),
+            None => ("en".to_string(), "default"),
+        };
+        table.push(vec![Cell::text("--lang"), Cell::text(value), Cell::text(from)]);
+    }
+    table.push(vec![
+        Cell::text("cache"),
+        Cell::text(if config.cache { "on" } else { "off" }),
+        Cell::text(config_file.as_str()),
+    ]);
+    table.print();
+
+    if uses_prices(&resolved.command) {
+        println!();
+        println!("Prices: {}", price_source(&config, resolved.max_age.as_deref())?);
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use crate::{
+    format_price_per_unit, output, price_header, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, Pricing, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+const ENDPOINTS_URL: &str = "https://openrouter.ai/api/v1/models/{id}/endpoints";
+
+/// Endpoint lookups run at once when sweeping many models
+const CONCURRENT_REQUESTS: usize = 8;
+
+#[derive(Deserialize)]
+struct EndpointsResponse {
+    data: EndpointList,
+}
+
+#[derive(Deserialize)]
+struct EndpointList {
+    endpoints: Vec<Endpoint>,
+}
+
+/// One upstream provider serving a model, with its own prices and limits
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Endpoint {
+    provider_name: String,
+    #[serde(default)]
+    tag: Option<String>,
+    pricing: Pricing,
+    #[serde(default)]
+    context_length: Option<u64>,
+    #[serde(default)]
+    max_completion_tokens: Option<u64>,
+    /// e.g. "fp8", or "unknown" when the provider doesn't say
+    #[serde(default)]
+    quantization: Option<String>,
+    /// Percentage of requests that succeeded over the last 30 minutes
+    #[serde(default)]
+    uptime_last_30m: Option<f64>,
+}
+
+impl Endpoint {
+    /// The provider's name, with the endpoint's variant when it runs more than one (e.g.
+    /// "DeepInfra (deepinfra/fp8)")
+    pub fn provider(&self) -> String {
+        match &self.tag {
+            Some(tag) if tag.contains('/') => format!("{} ({tag})", self.provider_name),
+            _ => self.provider_name.clone(),
+        }
+    }
+
+    /// The provider's name as OpenRouter lists it (e.g. "DeepInfra")
+    pub fn provider_name(&self) -> &str {
+        &self.provider_name
+    }
+
+    /// The endpoint's variant (e.g. "deepinfra/fp8")
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// What `input` and `output` tokens cost on this endpoint, if its prices are numbers
+    pub fn cost(&self, input: u64, output: u64) -> Option<f64> {
+        let input_price = self.pricing.prompt.parse::<f64>().ok()?;
+        let output_price = self.pricing.completion.parse::<f64>().ok()?;
+        Some(input as f64 * input_price + output as f64 * output_price)
+    }
+
+    fn input_price(&self) -> f64 {
+        self.pricing.prompt.parse().unwrap_or(f64::INFINITY)
+    }
+
+    fn output_price(&self) -> f64 {
+        self.pricing.completion.parse().unwrap_or(f64::INFINITY)
+    }
+}
+
+/// Where endpoint prices come from, fetched just now
+pub fn source() -> SourceInfo {
+    SourceInfo {
+        name: "OpenRouter endpoints API",
+        url: ENDPOINTS_URL,
+        fetched_at: Utc::now(),
+    }
+}
+
+async fn fetch_endpoints(client: &reqwest::Client, id: &str) -> anyhow::Result<Vec<Endpoint>> {
+    let response = client
+        .get(ENDPOINTS_URL.replace("{id}", id))
+        .send()
+        .await?
+        .error_for_status()?;
+    let endpoints: EndpointsResponse = response
+        .json()
+        .await
+        .context("Unexpected response from the OpenRouter endpoints API")?;
+    Ok(endpoints.data.endpoints)
+}
+
+/// Endpoints of every model in `ids`, keyed by model ID. Models whose endpoints can't be
+/// fetched are left out with a warning.
+pub async fn fetch_all(ids: Vec<String>) -> Vec<(String, Vec<Endpoint>)> {
+    eprintln!("Fetching endpoints for {} models...", ids.len());
+    let client = reqwest::Client::new();
+    let permits = Arc::new(Semaphore::new(CONCURRENT_REQUESTS));
+    let mut lookups = JoinSet::new();
+    for id in ids {
+        let client = client.clone();
+        let permits = permits.clone();
+        lookups.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let endpoints = fetch_endpoints(&client, &id).await;
+            (id, endpoints)
+        });
+    }
+
+    let mut fetched = Vec::new();
+    let mut failures = 0;
+    while let Some(Ok((id, endpoints))) = lookups.join_next().await {
+        match endpoints {
+            Ok(endpoints) => fetched.push((id, endpoints)),
+            Err(_) => failures += 1,
+        }
+    }
+    if failures > 0 {
+        eprintln!("Warning: couldn't fetch endpoints for {failures} models");
+    }
+    fetched.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fetched
+}
+
+/// Print each provider serving `model` with what it charges, cheapest input first, since the
+/// model's listed price is only what OpenRouter's default routing tends to pay
+pub async fn print(model: &Model, format: OutputFormat, stale_after: TimeDelta) -> anyhow::Result<()> {
+    let mut endpoints = fetch_endpoints(&reqwest::Client::new(), &model.id).await?;
+    if endpoints.is_empty() {
+        return Err(anyhow::anyhow!("OpenRouter lists no endpoints for {}", model.id));
+    }
+    endpoints.sort_by(|a, b| {
+        a.input_price()
+            .total_cmp(&b.input_price())
+            .then(a.output_price().total_cmp(&b.output_price()))
+            .then_with(|| a.provider_name.cmp(&b.provider_name))
+    });
+    let source = source();
+    if matches!(format, OutputFormat::Json | OutputFormat::Ndjson) {
+        return output::print_endpoints_json(&model.id, &endpoints, format, &source, stale_after);
+    }
+
+    let mut table = Table::new([
+        "Provider".to_string(),
+        price_header("Input"),
+        price_header("Output"),
+        price_header("Cache Read"),
+        price_header("Cache Write"),
+        "Context".to_string(),
+        "Max Completion".to_string(),
+        "Quantization".to_string(),
+        "Uptime 30m %".to_string(),
+    ]);
+    let price = |price: Option<&String>| price.map_or(Cell::Missing, |price| Cell::price_str(price));
+    let tokens = |tokens: Option<u64>| tokens.map_or(Cell::Missing, |tokens| Cell::Number(tokens as f64, 0));
+    for endpoint in &endpoints {
+        table.push(vec![
+            Cell::text(endpoint.provider()),
+            price(Some(&endpoint.pricing.prompt)),
+            price(Some(&endpoint.pricing.completion)),
+            price(endpoint.pricing.input_cache_read.as_ref()),
+            price(endpoint.pricing.input_cache_write.as_ref()),
+            tokens(endpoint.context_length),
+            tokens(endpoint.max_completion_tokens),
+            endpoint
+                .quantization
+                .as_ref()
+                .filter(|quantization| *quantization != "unknown")
+                .map_or(Cell::Missing, Cell::text),
+            endpoint
+                .uptime_last_30m
+                .map_or(Cell::Missing, |uptime| Cell::Number(uptime, 1)),
+        ]);
+    }
+
+    print_titled_table(
+        &format!("Endpoints for {}", model.id),
+        &table,
+        Some(&format!(
+            "Prices in USD. OpenRouter lists {} at {} input and {} output.",
+            model.id,
+            format_price_per_unit(&model.pricing.prompt),
+            format_price_per_unit(&model.pricing.completion),
+        )),
+        format,
+        &source,
+        stale_after,
+    )
+}
@@ -0,0 +1,325 @@
+use crate::{
+    budget::{self, Usage},
+    group_models, group_stats_table, model_link, price_header, results, sql,
+    table::{Cell, Table},
+    xlsx, GroupBy, Metadata, Model, SourceInfo,
+};
+use anyhow::Context;
+use arrow_array::{
+    ArrayRef, Decimal128Array, RecordBatch, StringArray, TimestampSecondArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use std::{collections::HashMap, fs::File, path::Path, sync::Arc};
+
+/// Prices are stored exactly as decimals with this many places, enough for the smallest
+/// per-token prices
+const PRICE_SCALE: i8 = 18;
+const PRICE_PRECISION: u8 = 38;
+
+/// File formats for analytic export
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// Apache Parquet, compressed with Snappy
+    Parquet,
+    /// Arrow IPC file (also known as Feather v2)
+    Arrow,
+    /// SQLite database with the tables the sql command queries
+    Sqlite,
+    /// Excel workbook with sheets of models, provider price ranges and saved calc results
+    Xlsx,
+    /// Terraform variable definitions with prices per 1M tokens and, with --requests, monthly
+    /// budgets per model
+    Tfvars,
+    /// JSON with prices per 1M tokens and, with --requests, monthly budgets per model, for
+    /// infrastructure-as-code tools that read JSON
+    JsonBudget,
+}
+
+impl ExportFormat {
+    /// Whether the format has budgets projected from --requests
+    pub fn has_budgets(self) -> bool {
+        matches!(self, ExportFormat::Tfvars | ExportFormat::JsonBudget)
+    }
+}
+
+/// Parse a decimal price string into an integer scaled by 10^PRICE_SCALE, exactly when it's
+/// written out in plain digits
+fn parse_price(price: &str) -> Option<i128> {
+    let price = price.trim();
+    let (negative, digits) = match price.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, price),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let plain = !whole.is_empty()
+        && whole.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+        && fraction.len() <= PRICE_SCALE as usize;
+    let scaled = if plain {
+        let padded = format!("{whole}{fraction:0<width$}", width = PRICE_SCALE as usize);
+        padded.parse::<i128>().ok()?
+    } else {
+        // Exponent notation or more places than fit: round instead
+        let value: f64 = digits.parse().ok()?;
+        (value * 10f64.powi(PRICE_SCALE as i32)).round() as i128
+    };
+    Some(if negative { -scaled } else { scaled })
+}
+
+fn price_field(name: &str, unit: &str) -> Field {
+    Field::new(name, DataType::Decimal128(PRICE_PRECISION, PRICE_SCALE), true)
+        .with_metadata(HashMap::from([("unit".to_string(), format!("USD per {unit}"))]))
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Second, Some("UTC".into()))
+}
+
+fn schema(source: &SourceInfo) -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("provider", DataType::Utf8, false),
+        price_field("input_price", "token"),
+        price_field("output_price", "token"),
+        price_field("cache_read_price", "token"),
+        price_field("cache_write_price", "token"),
+        price_field("request_price", "request"),
+        price_field("image_price", "image"),
+        Field::new("context_length", DataType::UInt64, true),
+        Field::new("max_completion_tokens", DataType::UInt64, true),
+        Field::new("modality", DataType::Utf8, true),
+        Field::new("tokenizer", DataType::Utf8, true),
+        Field::new("created", timestamp_type(), true),
+        Field::new("fetched_at", timestamp_type(), false),
+    ])
+    .with_metadata(HashMap::from([
+        ("source".to_string(), source.name.to_string()),
+        ("source_url".to_string(), source.url.to_string()),
+    ]))
+}
+
+fn record_batch(models: &[&Model], source: &SourceInfo) -> anyhow::Result<RecordBatch> {
+    let text = |value: fn(&Model) -> Option<&str>| -> ArrayRef {
+        Arc::new(StringArray::from(
+            models.iter().map(|model| value(model)).collect::<Vec<_>>(),
+        ))
+    };
+    let price = |value: fn(&Model) -> Option<&String>| -> anyhow::Result<ArrayRef> {
+        let prices: Decimal128Array = models
+            .iter()
+            .map(|model| value(model).and_then(|price| parse_price(price)))
+            .collect();
+        Ok(Arc::new(
+            prices.with_precision_and_scale(PRICE_PRECISION, PRICE_SCALE)?,
+        ))
+    };
+    let count = |value: fn(&Model) -> Option<u64>| -> ArrayRef {
+        Arc::new(UInt64Array::from(
+            models.iter().map(|model| value(model)).collect::<Vec<_>>(),
+        ))
+    };
+
+    let columns: Vec<ArrayRef> = vec![
+        text(|model| Some(&model.id)),
+        text(|model| model.name.as_deref()),
+        text(|model| model.id.split('/').next()),
+        price(|model| Some(&model.pricing.prompt))?,
+        price(|model| Some(&model.pricing.completion))?,
+        price(|model| model.pricing.input_cache_read.as_ref())?,
+        price(|model| model.pricing.input_cache_write.as_ref())?,
+        price(|model| model.pricing.request.as_ref())?,
+        price(|model| model.pricing.image.as_ref())?,
+        count(|model| model.context_length),
+        count(|model| {
+            model
+                .top_provider
+                .as_ref()
+                .and_then(|provider| provider.max_completion_tokens)
+        }),
+        text(|model| {
+            model
+                .architecture
+                .as_ref()
+                .and_then(|arch| arch.modality.as_deref())
+        }),
+        text(|model| {
+            model
+                .architecture
+                .as_ref()
+                .and_then(|arch| arch.tokenizer.as_deref())
+        }),
+        Arc::new(
+            TimestampSecondArray::from(
+                models
+                    .iter()
+                    .map(|model| model.created.map(|created| created as i64))
+                    .collect::<Vec<_>>(),
+            )
+            .with_timezone("UTC"),
+        ),
+        Arc::new(
+            TimestampSecondArray::from(vec![source.fetched_at.timestamp(); models.len()])
+                .with_timezone("UTC"),
+        ),
+    ];
+
+    Ok(RecordBatch::try_new(Arc::new(schema(source)), columns)?)
+}
+
+/// Write a SQLite database with the `models` and `results` tables, prices in USD per token
+fn write_sqlite(models: &[&Model], metadata: &Metadata, path: &Path) -> anyhow::Result<()> {
+    // Replace the file like the other formats do, rather than adding to an old database
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+    }
+    let connection = rusqlite::Connection::open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    sql::load(&connection, models, metadata, 1.0)
+}
+
+/// Every price and limit of each model, prices in USD per --per unit, for spreadsheets
+pub fn model_sheet(models: &[&Model], metadata: &Metadata) -> Table {
+    let mut table = Table::new([
+        "Model".to_string(),
+        "Name".to_string(),
+        "Provider".to_string(),
+        price_header("Input"),
+        price_header("Output"),
+        price_header("Cache Read"),
+        price_header("Cache Write"),
+        "Request".to_string(),
+        "Image".to_string(),
+        "Context".to_string(),
+        "Max Completion".to_string(),
+        "Modality".to_string(),
+        "License".to_string(),
+        "Created".to_string(),
+    ]);
+    let parse = |price: Option<&String>| price.and_then(|price| price.parse::<f64>().ok());
+    let count = |count: Option<u64>| count.map_or(Cell::Missing, |count| Cell::Number(count as f64, 0));
+    for model in models {
+        let pricing = &model.pricing;
+        table.push(vec![
+            model_link(&model.id),
+            model.name.as_deref().map_or(Cell::Missing, Cell::text),
+            Cell::text(model.id.split('/').next().unwrap_or("unknown")),
+            parse(Some(&pricing.prompt)).map_or(Cell::Missing, Cell::Price),
+            parse(Some(&pricing.completion)).map_or(Cell::Missing, Cell::Price),
+            parse(pricing.input_cache_read.as_ref()).map_or(Cell::Missing, Cell::Price),
+            parse(pricing.input_cache_write.as_ref()).map_or(Cell::Missing, Cell::Price),
+            parse(pricing.request.as_ref()).map_or(Cell::Missing, Cell::Cost),
+            parse(pricing.image.as_ref()).map_or(Cell::Missing, Cell::Cost),
+            count(model.context_length),
+            count(
+                model
+                    .top_provider
+                    .as_ref()
+                    .and_then(|provider| provider.max_completion_tokens),
+            ),
+            model
+                .architecture
+                .as_ref()
+                .and_then(|arch| arch.modality.as_deref())
+                .map_or(Cell::Missing, Cell::text),
+            Cell::text(metadata.license(model)),
+            model
+                .created
+                .and_then(|created| DateTime::from_timestamp(created as i64, 0))
+                .map_or(Cell::Missing, |created| {
+                    Cell::text(created.format("%Y-%m-%d").to_string())
+                }),
+        ]);
+    }
+    table
+}
+
+/// Write an Excel workbook with sheets of models, per-provider price ranges and saved calc
+/// results, prices in USD per --per unit
+fn write_xlsx(models: &[&Model], metadata: &Metadata, path: &Path) -> anyhow::Result<()> {
+    let model_sheet = model_sheet(models, metadata);
+    let grouped = group_models(
+        models.iter().map(|model| (*model).clone()).collect(),
+        GroupBy::Provider,
+    );
+    let provider_sheet = group_stats_table(&grouped, GroupBy::Provider);
+
+    let mut results_sheet = Table::new([
+        "Result",
+        "Saved",
+        "Input Tokens",
+        "Output Tokens",
+        "Model",
+        "Total Cost",
+    ]);
+    for cost in results::saved_costs()? {
+        results_sheet.push(vec![
+            Cell::text(cost.name),
+            Cell::text(cost.saved_at.format("%Y-%m-%d %H:%M UTC").to_string()),
+            Cell::Number(cost.input as f64, 0),
+            Cell::Number(cost.output as f64, 0),
+            model_link(&cost.model),
+            Cell::Cost(cost.total_cost),
+        ]);
+    }
+
+    xlsx::save(
+        &[
+            ("Models", &model_sheet),
+            ("Providers", &provider_sheet),
+            ("Calc Results", &results_sheet),
+        ],
+        path,
+    )
+}
+
+fn create(path: &Path) -> anyhow::Result<File> {
+    File::create(path).with_context(|| format!("Failed to create {}", path.display()))
+}
+
+/// Write one row per model, with exact decimal prices per token and UTC timestamps, so the
+/// file loads into DuckDB, Pandas or Polars with its types intact. Workbooks are for people
+/// rather than tools, so they use the --per unit like the terminal does. Budget formats give
+/// prices per 1M tokens, with monthly budgets projected from `usage`.
+pub fn write(
+    models: &[&Model],
+    metadata: &Metadata,
+    source: &SourceInfo,
+    format: ExportFormat,
+    usage: Option<&Usage>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    match format {
+        ExportFormat::Parquet => {
+            let batch = record_batch(models, source)?;
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let mut writer = ArrowWriter::try_new(create(path)?, batch.schema(), Some(properties))?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        ExportFormat::Arrow => {
+            let batch = record_batch(models, source)?;
+            let mut writer = arrow_ipc::writer::FileWriter::try_new(create(path)?, &batch.schema())?;
+            writer.write(&batch)?;
+            writer.finish()?;
+        }
+        ExportFormat::Sqlite => write_sqlite(models, metadata, path)?,
+        ExportFormat::Xlsx => write_xlsx(models, metadata, path)?,
+        ExportFormat::Tfvars | ExportFormat::JsonBudget => {
+            let contents = if format == ExportFormat::Tfvars {
+                budget::tfvars(models, usage, source, Utc::now())?
+            } else {
+                budget::json(models, usage, source, Utc::now())?
+            };
+            std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::{
+    calculate_cost, model_link, print_titled_table,
+    table::{Cell, Table},
+    Metadata, Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+use std::{fmt, str::FromStr};
+
+/// Percentiles reported, with their standard normal quantiles
+const PERCENTILES: [(&str, f64); 3] = [("P50", 0.0), ("P90", 1.281_551_6), ("P99", 2.326_347_9)];
+
+/// An average token count per request with its uncertainty, written as MEAN±SPREAD
+/// (or MEAN+-SPREAD), where the spread is one standard deviation
+#[derive(Debug, Clone, Copy)]
+pub struct TokenEstimate {
+    pub mean: u64,
+    pub spread: u64,
+}
+
+impl FromStr for TokenEstimate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid token estimate: '{}'. Expected TOKENS or TOKENS±SPREAD (e.g., '300±150')",
+                s
+            )
+        };
+        let (mean, spread) = match s.split_once('±').or_else(|| s.split_once("+-")) {
+            Some((mean, spread)) => (mean, spread.trim().parse().map_err(|_| invalid())?),
+            None => (s, 0),
+        };
+        Ok(Self {
+            mean: mean.trim().parse().map_err(|_| invalid())?,
+            spread,
+        })
+    }
+}
+
+impl fmt::Display for TokenEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.spread == 0 {
+            write!(f, "{}", self.mean)
+        } else {
+            write!(f, "{}±{}", self.mean, self.spread)
+        }
+    }
+}
+
+/// A monthly workload to forecast
+#[derive(Debug, Clone, Copy)]
+pub struct Forecast {
+    pub requests: u64,
+    pub input: TokenEstimate,
+    pub output: TokenEstimate,
+}
+
+/// Print P50/P90/P99 monthly cost per model, plus estimated monthly emissions when
+/// `emissions` is given. Token counts are treated as independent normal distributions, so
+/// the cost (linear in tokens) is normal too.
+pub fn print(
+    models: &[&Model],
+    forecast: Forecast,
+    emissions: Option<&Metadata>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let Forecast { requests, input, output } = forecast;
+    let mut rows = Vec::new();
+    for model in models {
+        let cost = calculate_cost(model, input.mean, output.mean, None, 5)?;
+        let spread = (cost.input.rate * input.spread as f64)
+            .hypot(cost.output.rate * output.spread as f64);
+        let monthly: Vec<f64> = PERCENTILES
+            .iter()
+            .map(|(_, z)| ((cost.total_cost + z * spread) * requests as f64).max(0.0))
+            .collect();
+        rows.push((model, monthly));
+    }
+    rows.sort_by(|(_, a), (_, b)| a[0].total_cmp(&b[0]));
+
+    let mut headers = vec!["Model".to_string()];
+    headers.extend(PERCENTILES.iter().map(|(label, _)| format!("{label} Monthly")));
+    if emissions.is_some() {
+        headers.push("kg CO2e Monthly".to_string());
+    }
+    let mut table = Table::new(headers);
+    let million_tokens = requests as f64 * (input.mean + output.mean) as f64 / 1e6;
+    for (model, monthly) in rows {
+        let mut cells = vec![model_link(&model.id)];
+        cells.extend(monthly.into_iter().map(Cell::Cost));
+        if let Some(metadata) = emissions {
+            let params = metadata.hugging_face(model).and_then(|info| info.params);
+            cells.push(
+                metadata
+                    .emissions
+                    .per_million(model, params)
+                    .map(|grams| Cell::Number(grams * million_tokens / 1000.0, 1))
+                    .unwrap_or(Cell::Missing),
+            );
+        }
+        table.push(cells);
+    }
+
+    let heading = format!(
+        "Monthly forecast: {} requests of {} input + {} output tokens",
+        requests, input, output
+    );
+    let mut note = "Spreads are one standard deviation of the average tokens per request. P90 \
+                    and P99 are the monthly costs you stay under 90% and 99% of the time."
+        .to_string();
+    if emissions.is_some() {
+        note.push_str(
+            " Emissions are rough estimates at average token counts, from parameter counts or \
+             the [carbon] config section, and are missing where neither is known.",
+        );
+    }
+
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
@@ -0,0 +1,65 @@
+use anyhow::Context;
+use std::{env, fs::OpenOptions, io::Write, path::PathBuf};
+
+/// Set by GitHub Actions to the file whose Markdown is shown on the run's summary page
+const SUMMARY_VAR: &str = "GITHUB_STEP_SUMMARY";
+
+/// Set by GitHub Actions to the file that step outputs are written to
+const OUTPUT_VAR: &str = "GITHUB_OUTPUT";
+
+fn append(var: &str, text: &str) -> anyhow::Result<bool> {
+    let Some(path) = env::var_os(var).map(PathBuf::from) else {
+        return Ok(false);
+    };
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .with_context(|| format!("Failed to write to {} ({})", path.display(), var))?;
+    Ok(true)
+}
+
+/// Add a Markdown section to the job summary, or print it when not running in GitHub Actions
+pub fn summary(markdown: &str) -> anyhow::Result<()> {
+    // Sections from several steps follow each other on one page
+    if !append(SUMMARY_VAR, &format!("{markdown}\n"))? {
+        print!("{markdown}");
+    }
+    Ok(())
+}
+
+/// Set a step output for later steps to read as `steps.<id>.outputs.<name>`. Outside GitHub
+/// Actions there's nowhere for it to go, so it's dropped.
+pub fn set_output(name: &str, value: impl std::fmt::Display) -> anyhow::Result<()> {
+    let value = value.to_string();
+    let line = if value.contains('\n') {
+        format!("{name}<<LLM_PRICING_EOF\n{value}\nLLM_PRICING_EOF\n")
+    } else {
+        format!("{name}={value}\n")
+    };
+    append(OUTPUT_VAR, &line)?;
+    Ok(())
+}
+
+/// Escape a workflow command's message, which ends at a newline
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property, which also ends at a comma or colon
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Annotate the run with an error, shown on the workflow's summary and checks pages
+pub fn error(title: &str, message: &str) {
+    println!("::error title={}::{}", escape_property(title), escape_data(message));
+}
+
+/// Annotate the run with a warning, shown on the workflow's summary and checks pages
+pub fn warning(title: &str, message: &str) {
+    println!("::warning title={}::{}", escape_property(title), escape_data(message));
+}
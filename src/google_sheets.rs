@@ -0,0 +1,203 @@
+use crate::table::Table;
+use anyhow::Context;
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use chrono::Utc;
+use ring::{
+    rand::SystemRandom,
+    signature::{RsaKeyPair, RSA_PKCS1_SHA256},
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::{fs, path::Path};
+
+const SHEETS_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets/";
+
+/// Read and write access to spreadsheets the service account has been shared on
+const SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+
+/// How long the signed token request is valid for; Google accepts at most an hour
+const ASSERTION_LIFETIME_SECS: i64 = 3600;
+
+/// The parts of a service account key file (as downloaded from the Google Cloud console)
+/// needed to get an access token
+#[derive(Deserialize)]
+struct ServiceAccount {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct Spreadsheet {
+    #[serde(default)]
+    sheets: Vec<Sheet>,
+}
+
+#[derive(Deserialize)]
+struct Sheet {
+    properties: SheetProperties,
+}
+
+#[derive(Deserialize)]
+struct SheetProperties {
+    title: String,
+}
+
+fn load_service_account(path: &Path) -> anyhow::Result<ServiceAccount> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read service account key {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid service account key {}", path.display()))
+}
+
+/// A JWT asking for an access token, signed with the service account's private key
+fn signed_assertion(account: &ServiceAccount) -> anyhow::Result<String> {
+    let invalid_key = || anyhow::anyhow!("Invalid private key in the service account key file");
+    let pem_body: String = account
+        .private_key
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let der = STANDARD.decode(pem_body.trim()).map_err(|_| invalid_key())?;
+    let key = RsaKeyPair::from_pkcs8(&der).map_err(|_| invalid_key())?;
+
+    let now = Utc::now().timestamp();
+    let header = json!({ "alg": "RS256", "typ": "JWT" });
+    let claims = json!({
+        "iss": account.client_email,
+        "scope": SCOPE,
+        "aud": account.token_uri,
+        "iat": now,
+        "exp": now + ASSERTION_LIFETIME_SECS,
+    });
+    let message = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    );
+    let mut signature = vec![0; key.public().modulus_len()];
+    key.sign(&RSA_PKCS1_SHA256, &SystemRandom::new(), message.as_bytes(), &mut signature)
+        .map_err(|_| anyhow::anyhow!("Failed to sign the Google token request"))?;
+    Ok(format!("{message}.{}", URL_SAFE_NO_PAD.encode(signature)))
+}
+
+async fn access_token(client: &reqwest::Client, account: &ServiceAccount) -> anyhow::Result<String> {
+    let assertion = signed_assertion(account)?;
+    let response: TokenResponse = client
+        .post(&account.token_uri)
+        .form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", &assertion),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Unexpected response from Google's token endpoint")?;
+    Ok(response.access_token)
+}
+
+/// Send a Sheets API request, explaining the usual cause when the spreadsheet can't be opened
+async fn send(
+    request: reqwest::RequestBuilder,
+    spreadsheet: &str,
+    account: &ServiceAccount,
+) -> anyhow::Result<reqwest::Response> {
+    let response = request.send().await?;
+    if matches!(
+        response.status(),
+        reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND
+    ) {
+        return Err(anyhow::anyhow!(
+            "Couldn't open Google spreadsheet {}. Check the ID, and share the spreadsheet with {} \
+             as an editor",
+            spreadsheet,
+            account.client_email
+        ));
+    }
+    Ok(response.error_for_status()?)
+}
+
+/// A range covering a whole tab, quoted as the Sheets API expects
+fn tab_range(tab: &str) -> String {
+    format!("'{}'", tab.replace('\'', "''"))
+}
+
+/// Replace the contents of tabs of a Google spreadsheet with tables, adding tabs that don't
+/// exist yet. Other tabs, such as charts built on these, are left alone.
+pub async fn push(
+    spreadsheet: &str,
+    credentials: &Path,
+    tabs: &[(&str, &Table)],
+) -> anyhow::Result<()> {
+    let account = load_service_account(credentials)?;
+    let client = reqwest::Client::new();
+    let token = access_token(&client, &account).await?;
+    let url = format!("{SHEETS_API_URL}{spreadsheet}");
+
+    let existing: Spreadsheet = send(
+        client
+            .get(&url)
+            .query(&[("fields", "sheets.properties.title")])
+            .bearer_auth(&token),
+        spreadsheet,
+        &account,
+    )
+    .await?
+    .json()
+    .await
+    .context("Unexpected response from the Google Sheets API")?;
+    let new_tabs: Vec<_> = tabs
+        .iter()
+        .filter(|(name, _)| !existing.sheets.iter().any(|sheet| sheet.properties.title == *name))
+        .map(|(name, _)| json!({ "addSheet": { "properties": { "title": name } } }))
+        .collect();
+    if !new_tabs.is_empty() {
+        send(
+            client
+                .post(format!("{url}:batchUpdate"))
+                .bearer_auth(&token)
+                .json(&json!({ "requests": new_tabs })),
+            spreadsheet,
+            &account,
+        )
+        .await?;
+    }
+
+    let ranges: Vec<String> = tabs.iter().map(|(name, _)| tab_range(name)).collect();
+    send(
+        client
+            .post(format!("{url}/values:batchClear"))
+            .bearer_auth(&token)
+            .json(&json!({ "ranges": ranges })),
+        spreadsheet,
+        &account,
+    )
+    .await?;
+    let data: Vec<_> = tabs
+        .iter()
+        .zip(&ranges)
+        .map(|((_, table), range)| {
+            json!({ "range": format!("{range}!A1"), "values": table.sheet_values() })
+        })
+        .collect();
+    send(
+        client
+            .post(format!("{url}/values:batchUpdate"))
+            .bearer_auth(&token)
+            .json(&json!({ "valueInputOption": "RAW", "data": data })),
+        spreadsheet,
+        &account,
+    )
+    .await?;
+    Ok(())
+}
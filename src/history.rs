@@ -0,0 +1,330 @@
+use crate::{
+    paths, print_titled_table,
+    table::{Cell, Table},
+    terminal, Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeDelta, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Sparkline levels from lowest to highest price
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The same levels in ASCII, for plain output
+const PLAIN_SPARK_LEVELS: [char; 8] = ['_', '.', ',', '-', '=', '+', '*', '#'];
+
+/// Every fetch is logged in `fetches`. Prices are only stored when they change, so `prices`
+/// has a row when a model is first seen, when its price changes, and, with NULL prices, when
+/// it stops being listed.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS fetches (
+    fetched_at TEXT PRIMARY KEY,
+    models INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS prices (
+    model TEXT NOT NULL,
+    fetched_at TEXT NOT NULL,
+    input TEXT,
+    output TEXT,
+    PRIMARY KEY (model, fetched_at)
+);
+";
+
+/// Input and output prices per token, as the API gives them
+#[derive(Serialize, PartialEq, Clone)]
+pub struct Prices {
+    pub input: String,
+    pub output: String,
+}
+
+impl Prices {
+    pub fn of(model: &Model) -> Self {
+        Self {
+            input: model.pricing.prompt.clone(),
+            output: model.pricing.completion.clone(),
+        }
+    }
+}
+
+/// When a fetch happened and every model listed in it with its prices
+pub type PricesAsOf = (DateTime<Utc>, BTreeMap<String, Prices>);
+
+fn history_file() -> anyhow::Result<PathBuf> {
+    Ok(paths::data_dir()?.join("price-history.db"))
+}
+
+/// Timestamps are stored with a fixed number of digits so they sort as text
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn parse_timestamp(text: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+/// Open the history database, creating it the first time
+fn open() -> anyhow::Result<Connection> {
+    let path = history_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create data directory {}", parent.display()))?;
+    }
+    let connection = Connection::open(&path)
+        .with_context(|| format!("Failed to open price history {}", path.display()))?;
+    connection.execute_batch(SCHEMA)?;
+    Ok(connection)
+}
+
+/// Each model's latest recorded prices, leaving out models no longer listed
+fn latest(connection: &Connection) -> rusqlite::Result<BTreeMap<String, Prices>> {
+    let mut statement = connection.prepare(
+        "SELECT model, input, output FROM prices AS p
+         WHERE fetched_at = (SELECT MAX(fetched_at) FROM prices WHERE model = p.model)
+         AND input IS NOT NULL",
+    )?;
+    let rows = statement.query_map([], |row| {
+        Ok((row.get(0)?, Prices { input: row.get(1)?, output: row.get(2)? }))
+    })?;
+    rows.collect()
+}
+
+/// Log a fetch and store the prices that changed since the last one. Fetches older than the
+/// newest recorded one, such as a cached list, are skipped.
+fn insert(transaction: &Transaction, fetched_at: DateTime<Utc>, prices: &BTreeMap<String, Prices>) -> anyhow::Result<()> {
+    let time = timestamp(fetched_at);
+    let newest: Option<String> = transaction.query_row("SELECT MAX(fetched_at) FROM fetches", [], |row| row.get(0))?;
+    if newest.is_some_and(|newest| newest >= time) {
+        return Ok(());
+    }
+    transaction.execute("INSERT INTO fetches VALUES (?1, ?2)", params![time, prices.len()])?;
+
+    let previous = latest(transaction)?;
+    let mut statement = transaction.prepare("INSERT INTO prices VALUES (?1, ?2, ?3, ?4)")?;
+    for (id, prices) in prices {
+        if previous.get(id) != Some(prices) {
+            statement.execute(params![id, time, prices.input, prices.output])?;
+        }
+    }
+    for id in previous.keys().filter(|id| !prices.contains_key(*id)) {
+        statement.execute(params![id, time, None::<String>, None::<String>])?;
+    }
+    Ok(())
+}
+
+/// Add freshly fetched prices to the history database
+pub fn record(models: &[Model], fetched_at: DateTime<Utc>) -> anyhow::Result<()> {
+    let prices: BTreeMap<String, Prices> = models
+        .iter()
+        .map(|model| (model.id.clone(), Prices::of(model)))
+        .collect();
+    let mut connection = open()?;
+    let transaction = connection.transaction()?;
+    insert(&transaction, fetched_at, &prices)?;
+    transaction.commit()?;
+    Ok(())
+}
+
+/// Every model's prices as they stood on `date`: as of the last fetch recorded on or before
+/// it, with when that fetch was
+pub fn prices_on(date: NaiveDate) -> anyhow::Result<PricesAsOf> {
+    let connection = open()?;
+    let oldest: Option<String> = connection.query_row("SELECT MIN(fetched_at) FROM fetches", [], |row| row.get(0))?;
+    let oldest = oldest.ok_or_else(|| anyhow::anyhow!("No price history recorded yet. Each fetch adds to it."))?;
+    let end = date
+        .succ_opt()
+        .and_then(|next| next.and_hms_opt(0, 0, 0))
+        .map(|next| timestamp(next.and_utc()))
+        .context("Date out of range")?;
+    prices_before(&connection, &end)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No price history recorded on or before {date}; it starts on {}",
+            &oldest[..10]
+        )
+    })
+}
+
+/// Every model's prices as of the last fetch at or before `time`, or None when the history
+/// doesn't reach back that far
+pub fn prices_at(time: DateTime<Utc>) -> anyhow::Result<Option<PricesAsOf>> {
+    prices_before(&open()?, &timestamp(time + TimeDelta::microseconds(1)))
+}
+
+/// The last fetch before `end` and every model listed then with its prices
+fn prices_before(
+    connection: &Connection,
+    end: &str,
+) -> anyhow::Result<Option<PricesAsOf>> {
+    let fetched_at: Option<String> = connection
+        .query_row(
+            "SELECT MAX(fetched_at) FROM fetches WHERE fetched_at < ?1 HAVING COUNT(*) > 0",
+            [end],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(fetched_at) = fetched_at else {
+        return Ok(None);
+    };
+
+    let mut statement = connection.prepare(
+        "SELECT model, input, output FROM prices AS p
+         WHERE fetched_at = (SELECT MAX(fetched_at) FROM prices WHERE model = p.model AND fetched_at <= ?1)
+         AND input IS NOT NULL",
+    )?;
+    let prices = statement
+        .query_map([&fetched_at], |row| {
+            Ok((row.get(0)?, Prices { input: row.get(1)?, output: row.get(2)? }))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(Some((parse_timestamp(&fetched_at)?, prices)))
+}
+
+/// One recorded price change of a model: its new prices, or None when it stopped being listed
+struct Change {
+    fetched_at: DateTime<Utc>,
+    prices: Option<Prices>,
+}
+
+/// Every recorded price change of a model, oldest first
+fn changes(connection: &Connection, id: &str) -> anyhow::Result<Vec<Change>> {
+    let mut statement =
+        connection.prepare("SELECT fetched_at, input, output FROM prices WHERE model = ?1 ORDER BY fetched_at")?;
+    let changes = statement
+        .query_map([id], |row| {
+            let input: Option<String> = row.get(1)?;
+            let output: Option<String> = row.get(2)?;
+            Ok(Change {
+                fetched_at: parse_timestamp(&row.get::<_, String>(0)?)?,
+                prices: input.zip(output).map(|(input, output)| Prices { input, output }),
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(changes)
+}
+
+/// The first and last fetch the history covers
+fn span(connection: &Connection) -> anyhow::Result<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+    let (first, last): (Option<String>, Option<String>) = connection.query_row(
+        "SELECT MIN(fetched_at), MAX(fetched_at) FROM fetches",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok(match (first, last) {
+        (Some(first), Some(last)) => Some((parse_timestamp(&first)?, parse_timestamp(&last)?)),
+        _ => None,
+    })
+}
+
+/// Print a model's recorded price changes with when each was first seen and how much each
+/// price moved from the one before
+pub fn print(id: &str, format: OutputFormat, source: &SourceInfo, stale_after: TimeDelta) -> anyhow::Result<()> {
+    let connection = open()?;
+    let changes = changes(&connection, id)?;
+    let Some((first, last)) = span(&connection)?.filter(|_| !changes.is_empty()) else {
+        return Err(anyhow::anyhow!(
+            "No price history recorded for {id}. Each fetch adds to the history, so it fills in over time."
+        ));
+    };
+
+    let percent = |old: &str, new: &str| match (old.parse::<f64>(), new.parse::<f64>()) {
+        (Ok(old), Ok(new)) if old > 0.0 => Cell::Number((new - old) / old * 100.0, 1),
+        _ => Cell::Missing,
+    };
+    let mut table = Table::new(["Date", "Change", "Input", "Output", "Input %", "Output %"]);
+    let mut previous: Option<&Prices> = None;
+    let mut listed_before = false;
+    for change in &changes {
+        let date = Cell::text(change.fetched_at.format("%Y-%m-%d %H:%M").to_string());
+        let row = match (&change.prices, previous) {
+            (Some(prices), Some(previous)) => vec![
+                date,
+                Cell::text("Changed"),
+                Cell::price_str(&prices.input),
+                Cell::price_str(&prices.output),
+                percent(&previous.input, &prices.input),
+                percent(&previous.output, &prices.output),
+            ],
+            (Some(prices), None) => vec![
+                date,
+                Cell::text(if listed_before { "Relisted" } else { "Listed" }),
+                Cell::price_str(&prices.input),
+                Cell::price_str(&prices.output),
+                Cell::Missing,
+                Cell::Missing,
+            ],
+            (None, _) => vec![
+                date,
+                Cell::text("Removed"),
+                Cell::Missing,
+                Cell::Missing,
+                Cell::Missing,
+                Cell::Missing,
+            ],
+        };
+        table.push(row);
+        listed_before = true;
+        previous = change.prices.as_ref();
+    }
+
+    let note = format!(
+        "Prices in USD. The history covers fetches from {} to {}; \"Listed\" is the first fetch \
+         that included the model.",
+        first.format("%Y-%m-%d"),
+        last.format("%Y-%m-%d")
+    );
+    print_titled_table(&format!("Price history of {id}"), &table, Some(&note), format, source, stale_after)
+}
+
+/// Each model's input price per token in the last `count` snapshots, oldest first, where a
+/// snapshot is a fetch that changed any price. Models missing from a snapshot, such as ones
+/// added since, just have fewer prices. Missing or unreadable history is treated as empty.
+pub fn input_trends(count: usize) -> BTreeMap<String, Vec<f64>> {
+    let rows = || -> anyhow::Result<Vec<(String, String, Option<String>)>> {
+        let connection = open()?;
+        let mut statement = connection.prepare("SELECT fetched_at, model, input FROM prices ORDER BY fetched_at")?;
+        let rows = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(rows)
+    };
+    let rows = rows().unwrap_or_default();
+
+    let mut snapshots: Vec<&str> = rows.iter().map(|(fetched_at, _, _)| fetched_at.as_str()).collect();
+    snapshots.dedup();
+    let first_kept = snapshots.len().saturating_sub(count);
+    let mut current: BTreeMap<&str, f64> = BTreeMap::new();
+    let mut trends: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut rows = rows.iter().peekable();
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        while let Some((_, model, input)) = rows.next_if(|(fetched_at, _, _)| fetched_at == snapshot) {
+            match input.as_deref().and_then(|input| input.parse::<f64>().ok()) {
+                Some(price) => current.insert(model, price),
+                None => current.remove(model.as_str()),
+            };
+        }
+        if index >= first_kept {
+            for (model, price) in &current {
+                trends.entry(model.to_string()).or_default().push(*price);
+            }
+        }
+    }
+    trends
+}
+
+/// A sparkline with one character per price, scaled between the lowest and highest. A flat
+/// trend sits on the bottom level.
+pub fn sparkline(prices: &[f64]) -> String {
+    let levels = if terminal::is_plain() { &PLAIN_SPARK_LEVELS } else { &SPARK_LEVELS };
+    let min = prices.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    prices
+        .iter()
+        .map(|price| {
+            let share = if max > min { (price - min) / (max - min) } else { 0.0 };
+            levels[(share * (levels.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
@@ -0,0 +1,65 @@
+use crate::table::Table;
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = r#"
+  body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #222; margin: 2rem; }
+  h1 { margin-bottom: 0.2rem; }
+  p.intro { color: #666; margin: 0.2rem 0; }
+  table { border-collapse: collapse; width: 100%; font-size: 0.9rem; margin-top: 1rem; }
+  th, td { padding: 0.35rem 0.6rem; border-bottom: 1px solid #ddd; text-align: left; }
+  th { border-bottom: 2px solid #444; cursor: pointer; user-select: none; white-space: nowrap; }
+  th[aria-sort="ascending"]::after { content: " \25B2"; }
+  th[aria-sort="descending"]::after { content: " \25BC"; }
+  td.number { text-align: right; font-variant-numeric: tabular-nums; }
+  tfoot td { font-weight: 600; border-bottom: none; }
+  tfoot tr:first-child td { border-top: 2px solid #444; }
+  footer { margin-top: 1rem; color: #666; font-size: 0.8rem; }
+  @media print { body { margin: 0; } tr { break-inside: avoid; } th::after { content: none; } }
+"#;
+
+/// Sorts by a cell's data-value when it has one (numbers), otherwise by its text
+const SORT_SCRIPT: &str = r#"
+  document.querySelectorAll("th").forEach((th, column) => th.addEventListener("click", () => {
+    const tbody = th.closest("table").tBodies[0];
+    const ascending = th.getAttribute("aria-sort") !== "ascending";
+    th.parentNode.querySelectorAll("th").forEach(other => other.removeAttribute("aria-sort"));
+    th.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+    const key = row => {
+      const cell = row.cells[column];
+      return cell.dataset.value !== undefined ? cell.dataset.value : cell.textContent;
+    };
+    const compare = (a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const numeric = x !== "" && y !== "" && !isNaN(x) && !isNaN(y);
+      if (x === "" || y === "") return (x === "") - (y === "");
+      return numeric ? x - y : x.localeCompare(y);
+    };
+    Array.from(tbody.rows)
+      .sort((a, b) => ascending ? compare(a, b) : compare(b, a))
+      .forEach(row => tbody.appendChild(row));
+  }));
+"#;
+
+/// A standalone HTML page with a heading, intro lines, a sortable table and a footer
+pub fn page(title: &str, intro: &[String], table: &Table, footer: Option<&str>) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+        title = escape(title)
+    );
+    for line in intro {
+        html.push_str(&format!("<p class=\"intro\">{}</p>\n", escape(line)));
+    }
+    html.push_str(&table.to_html());
+    if let Some(footer) = footer {
+        html.push_str(&format!("<footer>{}</footer>\n", escape(footer)));
+    }
+    html.push_str(&format!("<script>{SORT_SCRIPT}</script>\n</body>\n</html>\n"));
+    html
+}
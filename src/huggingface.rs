@@ -0,0 +1,148 @@
+use crate::{cache, Model};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+const MODEL_API_URL: &str = "https://huggingface.co/api/models/";
+
+/// Lookups run at once, to stay well under Hugging Face's rate limits
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// What Hugging Face knows about a model's weights
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Total parameter count, from the safetensors metadata
+    pub params: Option<u64>,
+    /// Architecture family, e.g. 'llama' or 'qwen2'
+    pub family: Option<String>,
+    /// When the repository was created
+    pub released: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiModel {
+    created_at: Option<DateTime<Utc>>,
+    config: Option<ApiConfig>,
+    safetensors: Option<ApiSafetensors>,
+}
+
+#[derive(Deserialize)]
+struct ApiConfig {
+    model_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiSafetensors {
+    total: Option<u64>,
+}
+
+async fn fetch(client: &reqwest::Client, id: &str) -> anyhow::Result<ModelInfo> {
+    let response = client.get(format!("{MODEL_API_URL}{id}")).send().await?;
+    // Private or deleted repositories have nothing to add, and shouldn't be looked up again
+    if response.status() == reqwest::StatusCode::NOT_FOUND
+        || response.status() == reqwest::StatusCode::UNAUTHORIZED
+    {
+        return Ok(ModelInfo::default());
+    }
+    let model: ApiModel = response.error_for_status()?.json().await?;
+    Ok(ModelInfo {
+        params: model.safetensors.and_then(|safetensors| safetensors.total),
+        family: model.config.and_then(|config| config.model_type),
+        released: model.created_at.map(|created_at| created_at.date_naive()),
+    })
+}
+
+/// Hugging Face metadata for every model with a `hugging_face_id`, keyed by that ID. Lookups
+/// are cached for a week; models that can't be looked up are left out with a warning.
+pub async fn load(models: &[Model]) -> BTreeMap<String, ModelInfo> {
+    let mut cached = cache::load_hugging_face();
+    let now = Utc::now();
+    let mut missing: Vec<String> = models
+        .iter()
+        .filter_map(|model| model.hugging_face_id.as_deref())
+        .filter(|id| !id.is_empty())
+        .filter(|id| {
+            cached
+                .get(*id)
+                .is_none_or(|entry| now - entry.fetched_at > cache::HUGGING_FACE_TTL)
+        })
+        .map(str::to_string)
+        .collect();
+    missing.sort();
+    missing.dedup();
+
+    if !missing.is_empty() {
+        eprintln!("Fetching {} models from Hugging Face...", missing.len());
+        let client = reqwest::Client::new();
+        let permits = Arc::new(Semaphore::new(CONCURRENT_REQUESTS));
+        let mut lookups = JoinSet::new();
+        for id in missing {
+            let client = client.clone();
+            let permits = permits.clone();
+            lookups.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let info = fetch(&client, &id).await;
+                (id, info)
+            });
+        }
+
+        let mut failures = 0;
+        while let Some(Ok((id, info))) = lookups.join_next().await {
+            match info {
+                Ok(info) => {
+                    cached.insert(id, cache::CachedModelInfo { fetched_at: now, info });
+                }
+                Err(_) => failures += 1,
+            }
+        }
+        if failures > 0 {
+            eprintln!("Warning: couldn't fetch {failures} models from Hugging Face");
+        }
+        if let Err(e) = cache::store_hugging_face(&cached) {
+            eprintln!("Warning: {e:#}");
+        }
+    }
+
+    cached
+        .into_iter()
+        .map(|(id, entry)| (id, entry.info))
+        .collect()
+}
+
+/// Parse a parameter count such as '70b', '1.5B', '500m' or '7000000000'
+pub fn parse_params(s: &str) -> anyhow::Result<u64> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid parameter count: '{}'. Expected a number with an optional k, m, b or t \
+             suffix (e.g., '70b')",
+            s
+        )
+    };
+    let lower = s.trim().to_lowercase();
+    let (number, scale) = match lower.char_indices().last() {
+        Some((i, 'k')) => (&lower[..i], 1e3),
+        Some((i, 'm')) => (&lower[..i], 1e6),
+        Some((i, 'b')) => (&lower[..i], 1e9),
+        Some((i, 't')) => (&lower[..i], 1e12),
+        _ => (lower.as_str(), 1.0),
+    };
+    let value: f64 = number.trim().parse().map_err(|_| invalid())?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(invalid());
+    }
+    Ok((value * scale).round() as u64)
+}
+
+/// A parameter count in the style models are named with, e.g. '70B' or '1.5B'
+pub fn format_params(params: u64) -> String {
+    let (value, suffix) = match params {
+        p if p >= 1_000_000_000_000 => (p as f64 / 1e12, "T"),
+        p if p >= 1_000_000_000 => (p as f64 / 1e9, "B"),
+        p if p >= 1_000_000 => (p as f64 / 1e6, "M"),
+        p => return p.to_string(),
+    };
+    let rounded = format!("{value:.1}");
+    format!("{}{suffix}", rounded.trim_end_matches(".0"))
+}
@@ -0,0 +1,135 @@
+use crate::table::{self, PriceUnit};
+use clap::ValueEnum;
+use fluent::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    OnceLock,
+};
+
+/// Languages that table headers, report text and the setup questions can be shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    /// English
+    #[default]
+    En,
+    /// German
+    De,
+    /// French
+    Fr,
+    /// Japanese
+    Ja,
+}
+
+impl Lang {
+    const ALL: [Lang; 4] = [Lang::En, Lang::De, Lang::Fr, Lang::Ja];
+
+    fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::De => "de",
+            Lang::Fr => "fr",
+            Lang::Ja => "ja",
+        }
+    }
+
+    fn messages(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../data/i18n/en.ftl"),
+            Lang::De => include_str!("../data/i18n/de.ftl"),
+            Lang::Fr => include_str!("../data/i18n/fr.ftl"),
+            Lang::Ja => include_str!("../data/i18n/ja.ftl"),
+        }
+    }
+
+    /// Whether the language is written in Latin script, which is all the PDF fonts can draw
+    fn is_latin(self) -> bool {
+        self != Lang::Ja
+    }
+}
+
+static LANG: AtomicU8 = AtomicU8::new(Lang::En as u8);
+
+/// Show output in this language, as requested with --lang or in the config file
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+fn lang() -> Lang {
+    Lang::ALL[LANG.load(Ordering::Relaxed) as usize]
+}
+
+/// One bundle per language, in the order of Lang::ALL
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        Lang::ALL
+            .iter()
+            .map(|lang| {
+                let id = lang.code().parse().expect("language codes are valid identifiers");
+                let mut bundle = FluentBundle::new_concurrent(vec![id]);
+                // Unicode isolation marks around arguments show up as junk in terminals and CSV
+                bundle.set_use_isolating(false);
+                let resource = FluentResource::try_new(lang.messages().to_string())
+                    .unwrap_or_else(|(_, errors)| panic!("Invalid {} messages: {errors:?}", lang.code()));
+                bundle
+                    .add_resource(resource)
+                    .unwrap_or_else(|errors| panic!("Duplicate {} messages: {errors:?}", lang.code()));
+                bundle
+            })
+            .collect()
+    })
+}
+
+/// The message with this ID in the chosen language
+pub fn t(id: &str) -> String {
+    t_args(id, &[])
+}
+
+/// The message with this ID in the chosen language, with its arguments filled in. Messages
+/// missing from a translation fall back to English.
+pub fn t_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    let bundles = bundles();
+    [lang(), Lang::En]
+        .iter()
+        .find_map(|lang| {
+            let bundle = &bundles[*lang as usize];
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// What prices are quoted per, e.g. "1M tokens"
+pub fn price_unit() -> String {
+    t(match table::price_unit() {
+        PriceUnit::Token => "unit-token",
+        PriceUnit::Thousand => "unit-thousand",
+        PriceUnit::Million => "unit-million",
+    })
+}
+
+/// Run `f` with output in English
+pub fn english<T>(f: impl FnOnce() -> T) -> T {
+    let chosen = lang();
+    set_lang(Lang::En);
+    let result = f();
+    set_lang(chosen);
+    result
+}
+
+/// Run `f` with output in English if the chosen language isn't written in Latin script, for
+/// the PDF renderer whose standard fonts can't draw anything else
+pub fn latin_only<T>(f: impl FnOnce() -> T) -> T {
+    if lang().is_latin() {
+        f()
+    } else {
+        english(f)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{paths, Args};
+use anyhow::Context;
+use clap::{CommandFactory, ValueEnum};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const BIN_NAME: &str = "llm-pricing";
+
+/// Model the prompt and status-line snippets show when --model isn't given
+pub const DEFAULT_PROMPT_MODEL: &str = "anthropic/claude-sonnet-4";
+
+/// Shells that completions and prompt snippets can be installed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    /// The login shell from $SHELL, if it's one completions can be installed for
+    fn detect() -> Option<Self> {
+        let shell = PathBuf::from(std::env::var_os("SHELL")?);
+        Shell::from_str(shell.file_name()?.to_str()?, true).ok()
+    }
+
+    fn generator(self) -> clap_complete::Shell {
+        match self {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+        }
+    }
+
+    /// Where completions are loaded from: the user's own directories, or the `share/` layout
+    /// under `prefix` that distribution packages and Homebrew use
+    fn completion_file(self, prefix: Option<&Path>) -> anyhow::Result<PathBuf> {
+        Ok(match (self, prefix) {
+            (Shell::Bash, Some(prefix)) => prefix.join("share/bash-completion/completions").join(BIN_NAME),
+            (Shell::Zsh, Some(prefix)) => prefix.join("share/zsh/site-functions").join(format!("_{BIN_NAME}")),
+            (Shell::Fish, Some(prefix)) => {
+                prefix.join("share/fish/vendor_completions.d").join(format!("{BIN_NAME}.fish"))
+            }
+            (Shell::Bash, None) => xdg_data_home()?.join("bash-completion/completions").join(BIN_NAME),
+            // zsh has no per-user completion directory, so this one needs adding to $fpath
+            (Shell::Zsh, None) => home_dir()?.join(".zfunc").join(format!("_{BIN_NAME}")),
+            (Shell::Fish, None) => xdg_config_home()?.join("fish/completions").join(format!("{BIN_NAME}.fish")),
+        })
+    }
+}
+
+fn home_dir() -> anyhow::Result<PathBuf> {
+    Ok(directories::BaseDirs::new()
+        .context("Could not determine the home directory for this platform")?
+        .home_dir()
+        .to_path_buf())
+}
+
+/// Shells look in the XDG directories on every platform, including macOS, so these don't use
+/// the platform directories the rest of the tool does
+fn xdg_data_home() -> anyhow::Result<PathBuf> {
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(home_dir()?.join(".local/share")),
+    }
+}
+
+fn xdg_config_home() -> anyhow::Result<PathBuf> {
+    match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(home_dir()?.join(".config")),
+    }
+}
+
+/// What to install, from the `install-integrations` arguments
+pub struct Options<'a> {
+    pub shells: &'a [Shell],
+    pub prompt: bool,
+    pub status_line: bool,
+    pub model: &'a str,
+    pub prefix: Option<&'a Path>,
+    pub dry_run: bool,
+}
+
+/// Files to write, with what they are and where they go for the summary
+struct Install {
+    what: String,
+    path: PathBuf,
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl Install {
+    fn file(what: impl Into<String>, path: PathBuf, contents: impl Into<Vec<u8>>) -> Self {
+        Self {
+            what: what.into(),
+            files: vec![(path.clone(), contents.into())],
+            path,
+        }
+    }
+}
+
+/// Install completions and the man page, plus the prompt and status-line snippets if asked
+/// for, then print where each file went and any line the user still has to add themselves
+pub fn run(options: &Options) -> anyhow::Result<()> {
+    let shells = match options.shells {
+        [] => vec![Shell::detect().ok_or_else(|| {
+            anyhow::anyhow!("Could not tell which shell you use from $SHELL; pass --shell bash, zsh or fish")
+        })?],
+        shells => shells.to_vec(),
+    };
+
+    let mut installs = Vec::new();
+    for &shell in &shells {
+        let mut contents = Vec::new();
+        clap_complete::generate(shell.generator(), &mut Args::command(), BIN_NAME, &mut contents);
+        installs.push(Install::file(
+            format!("{} completions", shell.name()),
+            shell.completion_file(options.prefix)?,
+            contents,
+        ));
+    }
+    if !cfg!(windows) {
+        installs.push(man_pages(options.prefix)?);
+    }
+
+    let mut follow_ups = Vec::new();
+    if shells.contains(&Shell::Zsh) && options.prefix.is_none() {
+        follow_ups.push(format!(
+            "Add to ~/.zshrc, before compinit: fpath+=({})",
+            home_dir()?.join(".zfunc").display()
+        ));
+    }
+    if options.prompt || options.status_line {
+        let dir = paths::data_dir()?.join("integrations");
+        let status_script = dir.join("status.sh");
+        installs.push(Install::file(
+            "status script",
+            status_script.clone(),
+            status_script_contents(options.model)?,
+        ));
+        if options.prompt {
+            for &shell in &shells {
+                let (install, follow_up) = prompt_snippet(shell, &dir, &status_script)?;
+                installs.push(install);
+                follow_ups.extend(follow_up);
+            }
+        }
+        if options.status_line {
+            let path = dir.join("tmux.conf");
+            let contents = format!(
+                "# Shows llm-pricing prices on the right of the tmux status line\n\
+                 set -ag status-right \" #(sh {})\"\n",
+                shell_quote(&status_script)
+            );
+            installs.push(Install::file("tmux status line", path.clone(), contents));
+            follow_ups.push(format!("Add to ~/.tmux.conf: source-file {}", shell_quote(&path)));
+        }
+    }
+
+    let width = installs.iter().map(|install| install.what.len()).max().unwrap_or(0);
+    for install in &installs {
+        if !options.dry_run {
+            for (path, contents) in &install.files {
+                write_file(path, contents)?;
+            }
+        }
+        println!("  {:<width$}  {}", install.what, install.path.display());
+    }
+    if options.dry_run {
+        println!();
+        println!("Dry run: nothing was written");
+    }
+    if !follow_ups.is_empty() {
+        println!();
+        for follow_up in follow_ups {
+            println!("{follow_up}");
+        }
+    }
+    Ok(())
+}
+
+/// A man page for the tool and one per subcommand, as `llm-pricing-<subcommand>.1`
+fn man_pages(prefix: Option<&Path>) -> anyhow::Result<Install> {
+    let dir = match prefix {
+        Some(prefix) => prefix.join("share/man/man1"),
+        None => xdg_data_home()?.join("man/man1"),
+    };
+    // Building propagates the global options, so each subcommand's page lists them too
+    let mut command = Args::command();
+    command.build();
+    let mut pages = Vec::new();
+    let mut render = |name: String, command: clap::Command| -> anyhow::Result<()> {
+        let mut contents = Vec::new();
+        clap_mangen::Man::new(command).render(&mut contents)?;
+        pages.push((dir.join(format!("{name}.1")), contents));
+        Ok(())
+    };
+    render(BIN_NAME.to_string(), command.clone())?;
+    for subcommand in command.get_subcommands().filter(|subcommand| !subcommand.is_hide_set()) {
+        let name = format!("{BIN_NAME}-{}", subcommand.get_name());
+        let page = subcommand
+            .clone()
+            .display_name(&name)
+            .bin_name(format!("{BIN_NAME} {}", subcommand.get_name()));
+        render(name, page)?;
+    }
+    Ok(Install {
+        what: format!("{} man pages", pages.len()),
+        path: dir,
+        files: pages,
+    })
+}
+
+/// A POSIX shell script printing `$input/$output` per 1M tokens for `model` (or
+/// $LLM_PRICING_MODEL). It prints the last fetched prices at once and refreshes them in the
+/// background at most once an hour, so a prompt or status line never waits on the network.
+fn status_script_contents(model: &str) -> anyhow::Result<String> {
+    let cache_file = paths::cache_dir()?.join("status.txt");
+    Ok(format!(
+        r#"#!/bin/sh
+# Prints the input/output price per 1M tokens of $LLM_PRICING_MODEL for a prompt or status line
+model="${{LLM_PRICING_MODEL:-{model}}}"
+file={cache_file}
+# The first line holds the prices and the second the model they're for. Marking the file as
+# fresh before fetching keeps prompts drawn in the meantime from starting more fetches.
+if [ "$(sed -n 2p "$file" 2>/dev/null)" != "$model" ]; then
+    mkdir -p "$(dirname "$file")" && printf '\n%s\n' "$model" > "$file" && refresh=1
+elif [ -n "$(find "$file" -mmin +60)" ]; then
+    touch "$file" && refresh=1
+fi
+if [ -n "$refresh" ]; then
+    (
+        prices=$({BIN_NAME} list "$model" --quiet --sort name --top 1 --columns input,output --no-pager 2>/dev/null \
+            | awk -F '\t' 'NF == 2 {{ printf "$%s/$%s", $1, $2 }}')
+        [ -n "$prices" ] && printf '%s\n%s\n' "$prices" "$model" > "$file"
+    ) >/dev/null 2>&1 &
+fi
+sed -n 1p "$file"
+"#,
+        cache_file = shell_quote(&cache_file),
+    ))
+}
+
+/// A snippet that adds the status script's output to the prompt, and what the user has to add
+/// to their shell config to load it. fish loads conf.d itself, so needs nothing adding.
+fn prompt_snippet(shell: Shell, dir: &Path, status_script: &Path) -> anyhow::Result<(Install, Option<String>)> {
+    let script = shell_quote(status_script);
+    let (path, contents, rc_file) = match shell {
+        Shell::Bash => (
+            dir.join("prompt.bash"),
+            format!("llm_pricing_prompt() {{ sh {script}; }}\nPS1='$(llm_pricing_prompt) '\"$PS1\"\n"),
+            Some("~/.bashrc"),
+        ),
+        Shell::Zsh => (
+            dir.join("prompt.zsh"),
+            format!(
+                "llm_pricing_prompt() {{ sh {script}; }}\n\
+                 setopt prompt_subst\n\
+                 RPROMPT='$(llm_pricing_prompt)'\"${{RPROMPT:+ $RPROMPT}}\"\n"
+            ),
+            Some("~/.zshrc"),
+        ),
+        Shell::Fish => (
+            xdg_config_home()?.join("fish/conf.d").join(format!("{BIN_NAME}.fish")),
+            format!(
+                "function llm_pricing_prompt\n    sh {script}\nend\n\n\
+                 # Leave an existing right prompt alone; call llm_pricing_prompt from it instead\n\
+                 if not functions -q fish_right_prompt\n    \
+                     function fish_right_prompt\n        llm_pricing_prompt\n    end\n\
+                 end\n"
+            ),
+            None,
+        ),
+    };
+    let follow_up = rc_file.map(|rc_file| format!("Add to {rc_file}: source {}", shell_quote(&path)));
+    Ok((Install::file(format!("{} prompt", shell.name()), path, contents), follow_up))
+}
+
+/// Single-quote a path for sh, bash, zsh and fish
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,228 @@
+use crate::{
+    config::InvoiceBranding,
+    html, i18n, model_link,
+    table::{Cell, Table},
+    Model, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Tokens used by one model over the invoice period
+#[derive(Default)]
+pub struct Usage {
+    pub requests: u64,
+    pub input: u64,
+    pub output: u64,
+    pub cached: u64,
+}
+
+/// Split a CSV line into fields, allowing double-quoted fields with commas in them
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().expect("always one field").push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().expect("always one field").push(c),
+        }
+    }
+    fields.into_iter().map(|field| field.trim().to_string()).collect()
+}
+
+/// Total usage per model from a CSV with a header row naming `date`, `model`, `input_tokens`
+/// and `output_tokens` columns, and optionally `cached_tokens` (included in the input tokens)
+/// and `requests`. Only rows whose date starts with `period` count when it's given.
+pub fn read_usage(path: &Path, period: Option<&str>) -> anyhow::Result<BTreeMap<String, Usage>> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = csv_fields(lines.next().unwrap_or_default())
+        .into_iter()
+        .map(|name| name.to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|header| header == name);
+    let required = |name: &str| {
+        column(name).ok_or_else(|| anyhow::anyhow!("{} has no '{name}' column", path.display()))
+    };
+    let (date, model, input, output) = (
+        required("date")?,
+        required("model")?,
+        required("input_tokens")?,
+        required("output_tokens")?,
+    );
+    let (cached, requests) = (column("cached_tokens"), column("requests"));
+
+    let mut usage: BTreeMap<String, Usage> = BTreeMap::new();
+    for (number, line) in lines.enumerate() {
+        let fields = csv_fields(line);
+        let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or_default();
+        if period.is_some_and(|period| !field(date).starts_with(period)) {
+            continue;
+        }
+        // Line 1 is the header
+        let count = |index: Option<usize>, default: u64| -> anyhow::Result<u64> {
+            match index.map(field) {
+                None | Some("") => Ok(default),
+                Some(value) => value.parse().with_context(|| {
+                    format!("{} line {}: invalid token count '{value}'", path.display(), number + 2)
+                }),
+            }
+        };
+        let entry = usage.entry(field(model).to_string()).or_default();
+        entry.requests += count(requests, 1)?;
+        entry.input += count(Some(input), 0)?;
+        entry.output += count(Some(output), 0)?;
+        entry.cached += count(cached, 0)?;
+    }
+    Ok(usage)
+}
+
+/// What usage costs at a model's current prices. Cached tokens are billed at the cache read
+/// price when the model has one, and the rest of the input at the input price.
+fn cost(model: &Model, usage: &Usage) -> f64 {
+    let price = |price: &str| price.parse::<f64>().unwrap_or(0.0);
+    let input_price = price(&model.pricing.prompt);
+    let cache_read_price = model.pricing.input_cache_read.as_deref().map_or(input_price, price);
+    let cached = usage.cached.min(usage.input);
+    (usage.input - cached) as f64 * input_price
+        + cached as f64 * cache_read_price
+        + usage.output as f64 * price(&model.pricing.completion)
+}
+
+/// What to bill and who to
+pub struct Options<'a> {
+    pub usage: &'a Path,
+    /// Month to bill, as YYYY-MM
+    pub period: Option<&'a str>,
+    /// Fraction added on top of cost, e.g. 0.15
+    pub markup: f64,
+    pub client: Option<&'a str>,
+    pub number: Option<&'a str>,
+    pub branding: &'a InvoiceBranding,
+    pub file: Option<&'a Path>,
+}
+
+/// Price a usage CSV and write it as an itemized invoice, one line per model with its cost
+/// plus markup rounded to cents, as Markdown or HTML depending on the file extension, or as
+/// Markdown on stdout
+pub fn write(models: &[Model], source: &SourceInfo, options: &Options) -> anyhow::Result<()> {
+    if let Some(period) = options.period {
+        // chrono accepts "2025-9", which would match no dates
+        if period.len() != 7 || NaiveDate::parse_from_str(&format!("{period}-01"), "%Y-%m-%d").is_err() {
+            return Err(anyhow::anyhow!(
+                "Invalid period '{period}'. Expected a month as YYYY-MM (e.g., '2025-09')"
+            ));
+        }
+    }
+    let usage = read_usage(options.usage, options.period)?;
+    if usage.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No usage in {}{}",
+            options.usage.display(),
+            options.period.map(|period| format!(" for {period}")).unwrap_or_default()
+        ));
+    }
+    let unknown: Vec<&str> = usage
+        .keys()
+        .filter(|id| !models.iter().any(|model| &model.id == *id))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(anyhow::anyhow!("No current prices for: {}", unknown.join(", ")));
+    }
+
+    let mut table = Table::new([
+        i18n::t("col-model"),
+        i18n::t("col-requests"),
+        i18n::t("col-input-tokens"),
+        i18n::t("col-output-tokens"),
+        i18n::t("col-cached-tokens"),
+        i18n::t("col-amount"),
+    ]);
+    let (mut cost_total, mut total) = (0.0, 0.0);
+    for (id, usage) in &usage {
+        let model = models.iter().find(|model| &model.id == id).expect("checked above");
+        let cost = cost(model, usage);
+        let amount = (cost * (1.0 + options.markup) * 100.0).round() / 100.0;
+        cost_total += cost;
+        total += amount;
+        table.push(vec![
+            model_link(id),
+            Cell::Number(usage.requests as f64, 0),
+            Cell::Number(usage.input as f64, 0),
+            Cell::Number(usage.output as f64, 0),
+            Cell::Number(usage.cached as f64, 0),
+            Cell::Number(amount, 2),
+        ]);
+    }
+    let blank = || Cell::text("");
+    table.push_footer(vec![
+        Cell::text(i18n::t("col-total")),
+        blank(),
+        blank(),
+        blank(),
+        blank(),
+        Cell::Number(total, 2),
+    ]);
+
+    let today = Utc::now().date_naive();
+    let number = options
+        .number
+        .map(str::to_string)
+        .unwrap_or_else(|| options.period.unwrap_or(&today.format("%Y-%m-%d").to_string()).to_string());
+    let mut intro: Vec<String> = options.branding.from.iter().cloned().collect();
+    intro.extend(options.branding.address.iter().cloned());
+    intro.push(i18n::t_args("invoice-issued", &[("date", today.format("%Y-%m-%d").to_string().into())]));
+    if let Some(period) = options.period {
+        intro.push(i18n::t_args("invoice-period", &[("period", period.into())]));
+    }
+    if let Some(client) = options.client {
+        intro.push(i18n::t_args("invoice-bill-to", &[("client", client.into())]));
+    }
+    let mut footer = i18n::t_args(
+        "invoice-footer",
+        &[
+            ("source", source.name.into()),
+            ("fetched", source.fetched_at.format("%Y-%m-%d %H:%M UTC").to_string().into()),
+        ],
+    );
+    if let Some(note) = &options.branding.footer {
+        footer = format!("{footer} {note}");
+    }
+    let title = i18n::t_args("invoice-title", &[("number", number.into())]);
+
+    let markdown = || {
+        let intro: String = intro.iter().map(|line| format!("{line}  \n")).collect();
+        format!("## {title}\n\n{intro}\n{}\n_{footer}_\n", table.to_markdown())
+    };
+    let Some(path) = options.file else {
+        print!("{}", markdown());
+        return Ok(());
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let contents = match extension.as_deref() {
+        Some("md" | "markdown") => markdown(),
+        Some("html" | "htm") => html::page(&title, &intro, &table, Some(&footer)),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported invoice file '{}'. Use a .html or .md extension",
+                path.display()
+            ))
+        }
+    };
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!(
+        "Wrote {title} to {}: ${cost_total:.2} at cost, ${total:.2} billed",
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::prefix_map::PrefixMap;
+use anyhow::Context;
+use std::collections::HashMap;
+
+/// Licenses of open-weights models, keyed by Hugging Face ID. Keys ending in `*` match any
+/// ID with that prefix; exact keys take precedence, then the longest matching prefix.
+const BUNDLED_LICENSES: &str = include_str!("../data/licenses.json");
+
+/// License reported for models that don't publish weights
+pub const PROPRIETARY: &str = "Proprietary";
+
+/// License reported for open-weights models missing from the bundled map
+pub const UNKNOWN: &str = "Unknown";
+
+pub struct Licenses(PrefixMap<String>);
+
+impl Licenses {
+    pub fn bundled() -> anyhow::Result<Self> {
+        let entries: HashMap<String, String> =
+            serde_json::from_str(BUNDLED_LICENSES).context("Invalid bundled license data")?;
+
+        // Hugging Face IDs are matched ignoring case
+        let entries = entries.into_iter().map(|(key, license)| (key.to_lowercase(), license));
+        Ok(Self(PrefixMap::new(entries)))
+    }
+
+    /// License for a model given its Hugging Face ID; models without one are proprietary
+    pub fn lookup(&self, hugging_face_id: Option<&str>) -> &str {
+        let hf_id = match hugging_face_id {
+            Some(id) if !id.is_empty() => id.to_lowercase(),
+            _ => return PROPRIETARY,
+        };
+
+        self.0.lookup(&hf_id).map_or(UNKNOWN, String::as_str)
+    }
+}
+
+/// Whether a license satisfies a --license filter. `open` matches any open-weights model;
+/// anything else matches licenses equal to or starting with the filter, ignoring case.
+pub fn matches(license: &str, filter: &str) -> bool {
+    let license = license.to_lowercase();
+    let filter = filter.to_lowercase();
+
+    if filter == "open" {
+        return license != PROPRIETARY.to_lowercase();
+    }
+    license.starts_with(&filter)
+}
@@ -0,0 +1,62 @@
+use anyhow::Context;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+/// Announced deprecation and retirement dates for major models, keyed by model ID
+const BUNDLED_LIFECYCLE: &str = include_str!("../data/lifecycle.json");
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Lifecycle {
+    /// When the deprecation was announced
+    #[serde(default)]
+    pub deprecated: Option<NaiveDate>,
+    /// When the model stops being served
+    #[serde(default)]
+    pub retires: Option<NaiveDate>,
+    /// Suggested model to migrate to
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+impl Lifecycle {
+    /// Short status for table cells, e.g. "retires 2026-01-05"
+    pub fn status(&self, today: NaiveDate) -> String {
+        match (self.retires, self.deprecated) {
+            (Some(retires), _) if retires <= today => format!("retired {retires}"),
+            (Some(retires), _) => format!("retires {retires}"),
+            (None, Some(deprecated)) => format!("deprecated {deprecated}"),
+            (None, None) => "deprecated".to_string(),
+        }
+    }
+
+    /// Full description for verbose output and warnings
+    pub fn describe(&self, today: NaiveDate) -> String {
+        let mut description = self.status(today);
+        if let (Some(deprecated), Some(_)) = (self.deprecated, self.retires) {
+            description = format!("deprecated {deprecated}, {description}");
+        }
+        if let Some(replacement) = &self.replacement {
+            description.push_str(&format!(" (replacement: {replacement})"));
+        }
+        description
+    }
+}
+
+pub type LifecycleMap = HashMap<String, Lifecycle>;
+
+/// Load the bundled lifecycle data, with entries from `override_path` taking precedence
+pub fn load(override_path: Option<&Path>) -> anyhow::Result<LifecycleMap> {
+    let mut lifecycle: LifecycleMap =
+        serde_json::from_str(BUNDLED_LIFECYCLE).context("Invalid bundled lifecycle data")?;
+
+    if let Some(path) = override_path {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read lifecycle file {}", path.display()))?;
+        let overrides: LifecycleMap = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid lifecycle file {}", path.display()))?;
+        lifecycle.extend(overrides);
+    }
+
+    Ok(lifecycle)
+}
@@ -0,0 +1,162 @@
+use crate::{
+    github, model_link, print_titled_table,
+    table::{self, Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+
+/// Model ID prefixes whose text models always charge more for output than input, so a lower
+/// output price points at a data error rather than a pricing decision
+const OUTPUT_PRICIER_FAMILIES: &[&str] = &[
+    "anthropic/",
+    "openai/gpt-",
+    "openai/o",
+    "google/gemini",
+    "x-ai/grok",
+    "deepseek/",
+];
+
+/// A suspicious catalog entry
+struct Issue<'a> {
+    model: &'a Model,
+    check: &'static str,
+    details: String,
+}
+
+fn parse(price: &str) -> Option<f64> {
+    price.trim().parse().ok()
+}
+
+/// Free variants are listed with a ':free' suffix, so anything else is expected to cost money
+fn looks_paid(model: &Model) -> bool {
+    !model.id.ends_with(":free")
+        && !model
+            .name
+            .as_ref()
+            .is_some_and(|name| name.to_lowercase().contains("(free)"))
+}
+
+fn has_text_output(model: &Model) -> bool {
+    model
+        .architecture
+        .as_ref()
+        .and_then(|arch| arch.modality.as_deref())
+        .is_none_or(|modality| modality.ends_with("->text"))
+}
+
+fn check_model(model: &Model) -> Vec<Issue<'_>> {
+    let mut issues = Vec::new();
+    let mut flag = |check, details: String| issues.push(Issue { model, check, details });
+    let pricing = &model.pricing;
+    let input = parse(&pricing.prompt);
+    let output = parse(&pricing.completion);
+
+    if input.is_none() {
+        flag("missing input price", format!("prompt price is '{}'", pricing.prompt));
+    }
+    if output.is_none() {
+        flag(
+            "missing completion price",
+            format!("completion price is '{}'", pricing.completion),
+        );
+    }
+
+    let prices = [
+        ("input", Some(&pricing.prompt)),
+        ("output", Some(&pricing.completion)),
+        ("cache read", pricing.input_cache_read.as_ref()),
+        ("cache write", pricing.input_cache_write.as_ref()),
+        ("request", pricing.request.as_ref()),
+        ("image", pricing.image.as_ref()),
+    ];
+    for (label, price) in prices {
+        if let Some(value) = price.and_then(|price| parse(price)).filter(|value| *value < 0.0) {
+            flag("negative price", format!("{label} price is {value}"));
+        }
+    }
+
+    if looks_paid(model) && input == Some(0.0) && output == Some(0.0) {
+        flag(
+            "zero price",
+            "input and output are free, but the model isn't a ':free' variant".to_string(),
+        );
+    }
+
+    if let (Some(input), Some(cache_read)) =
+        (input, pricing.input_cache_read.as_deref().and_then(parse))
+    {
+        if cache_read > input {
+            flag(
+                "cache read above input",
+                format!(
+                    "cache reads cost ${} but uncached input ${}",
+                    table::format_price(cache_read),
+                    table::format_price(input)
+                ),
+            );
+        }
+    }
+
+    if let (Some(input), Some(output)) = (input, output) {
+        let family = OUTPUT_PRICIER_FAMILIES
+            .iter()
+            .find(|prefix| model.id.starts_with(*prefix));
+        if let Some(family) = family.filter(|_| has_text_output(model) && output < input) {
+            flag(
+                "output below input",
+                format!(
+                    "output costs ${} but input ${}, unusual for {}",
+                    table::format_price(output),
+                    table::format_price(input),
+                    family.trim_end_matches(['/', '-'])
+                ),
+            );
+        }
+    }
+
+    issues
+}
+
+/// Print suspicious catalog entries. Returns whether any were found, so callers can fail
+/// and catch upstream data errors before they reach reports.
+pub fn print(
+    models: &[&Model],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<bool> {
+    let issues: Vec<Issue> = models.iter().flat_map(|model| check_model(model)).collect();
+    if format == OutputFormat::GithubSummary {
+        github::set_output("issues", issues.len())?;
+    }
+    if issues.is_empty() {
+        println!("No data issues found in {} models", models.len());
+        return Ok(false);
+    }
+
+    let mut table = Table::new(["Model", "Check", "Details"]);
+    for issue in &issues {
+        table.push(vec![
+            model_link(&issue.model.id),
+            Cell::text(issue.check),
+            Cell::text(&issue.details),
+        ]);
+    }
+
+    let mut flagged: Vec<&str> = issues.iter().map(|issue| issue.model.id.as_str()).collect();
+    flagged.dedup();
+    let heading = format!(
+        "{} data issues in {} of {} models",
+        issues.len(),
+        flagged.len(),
+        models.len()
+    );
+    let note = format!("Prices per {}", table::price_unit().describe());
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)?;
+    if format == OutputFormat::GithubSummary {
+        for issue in &issues {
+            github::error(&format!("{}: {}", issue.check, issue.model.id), &issue.details);
+        }
+    }
+    Ok(true)
+}
@@ -1,11 +1,93 @@
+mod account;
+mod alerts;
+mod arbitrage;
+mod auth;
+mod backtest;
+mod badge;
+mod benchmarks;
+mod budget;
+mod cache;
+mod capture;
+mod carbon;
+mod chart;
+mod cheapest;
+mod clipboard;
+mod compress;
+mod config;
+mod diff;
+mod doctor;
+mod drawing;
+mod dry_run;
+mod endpoints;
+mod export;
+mod forecast;
+mod github;
+mod google_sheets;
+mod history;
+mod html;
+mod huggingface;
+mod i18n;
+mod integrations;
+mod invoice;
+mod license;
+mod lint;
+mod lifecycle;
+mod onboarding;
+mod output;
+mod pager;
+mod publish;
+mod paths;
+mod planner;
+mod prefix_map;
+mod prompts;
+mod quote;
+mod rank;
+mod rate_limits;
+mod region;
+mod report;
+mod results;
+mod routing;
+mod service_tier;
+mod snapshot;
+mod sql;
+mod stats;
+mod table;
+mod tags;
+mod terminal;
+mod throughput;
+mod watch;
+mod why;
+mod xlsx;
+#[cfg(feature = "self-update")]
+mod self_update;
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, cmp::Ordering, str::FromStr};
+use config::{AlertRule, Approval, Config};
+use license::Licenses;
+use lifecycle::LifecycleMap;
+use benchmarks::Benchmarks;
+use rate_limits::RateLimits;
+use service_tier::{ServiceTier, ServiceTiers};
+use tags::Tags;
+use table::{Cell, PriceUnit, Table, TableStyle};
+use std::{
+    collections::{BTreeMap, HashSet},
+    cmp::Ordering,
+    fmt,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    str::FromStr,
+};
 use strum::{EnumString, VariantNames};
 
 #[derive(Parser, Debug)]
 #[command(name = "llm-pricing")]
 #[command(about = "A CLI tool to visualize OpenRouter model pricing")]
+// A flag given twice takes its last value, so flags typed with 'run' replace the saved ones
+#[command(args_override_self = true)]
 struct Args {
     #[command(subcommand)]
     command: Option<Commands>,
@@ -26,6 +108,564 @@ struct Args {
     /// Reverse the sort order
     #[arg(short, long, global = true)]
     reverse: bool,
+
+    /// Fail if the pricing data is older than this (e.g., '30m', '24h', '7d')
+    #[arg(long, global = true, value_name = "DURATION")]
+    max_age: Option<String>,
+
+    /// Hide models with an announced deprecation or retirement
+    #[arg(long, global = true)]
+    exclude_deprecated: bool,
+
+    /// JSON file of model lifecycle entries overriding the bundled deprecation data
+    #[arg(long, global = true, value_name = "PATH")]
+    lifecycle_file: Option<PathBuf>,
+
+    /// Only show models under these licenses (e.g., 'apache-2.0', 'llama', 'open', 'proprietary')
+    #[arg(long, global = true, value_name = "LICENSE")]
+    license: Vec<String>,
+
+    /// Add a license column to the model table
+    #[arg(long, global = true)]
+    show_license: bool,
+
+    /// Add context length, created date, modality and max completion tokens to the model table
+    #[arg(long, global = true, conflicts_with = "columns")]
+    wide: bool,
+
+    /// Shorten provider prefixes in model IDs (e.g., 'anth/claude-sonnet-4') to fit narrow
+    /// terminals
+    #[arg(long, global = true)]
+    compact: bool,
+
+    /// Add parameter count, architecture family and release date from Hugging Face for
+    /// models with open weights (cached for a week)
+    #[arg(long, global = true)]
+    hugging_face: bool,
+
+    /// Add a sparkline of each model's input price over recent price changes, from the
+    /// history recorded each time prices are fetched
+    #[arg(long, global = true, conflicts_with = "columns")]
+    trend: bool,
+
+    /// How many recorded price snapshots the trend column covers
+    #[arg(long, global = true, value_name = "N", default_value_t = 10)]
+    trend_snapshots: usize,
+
+    /// Only show models with at least this many parameters (e.g., '70b'); implies --hugging-face
+    #[arg(long, global = true, value_name = "SIZE", value_parser = huggingface::parse_params)]
+    min_params: Option<u64>,
+
+    /// Only show models with this approval status (set with 'approve'); may be repeated
+    #[arg(long, global = true, value_enum, value_name = "STATUS")]
+    approval: Vec<Approval>,
+
+    /// Only show models with this tag, derived from their description and capabilities
+    /// (code, vision, roleplay, long-context, reasoning); may be repeated
+    #[arg(long, global = true, value_name = "TAG")]
+    tag: Vec<String>,
+
+    /// Only show models with a provider serving from this region (e.g., 'eu'), judged by each
+    /// endpoint's provider; may be repeated. Models whose residency isn't known are kept and
+    /// marked in the region column.
+    #[arg(long, global = true, value_name = "REGION")]
+    region: Vec<String>,
+
+    /// Plain output without colors or alignment padding (automatic when output is piped)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Print only table rows, tab-separated, without headings, notes or currency symbols, for
+    /// shell scripts
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Leave out the header row of tables and CSV output
+    #[arg(long, global = true)]
+    no_header: bool,
+
+    /// Show prices per token, per 1K tokens or per 1M tokens
+    #[arg(long, global = true, value_enum, default_value = "1m", value_name = "UNIT")]
+    per: PriceUnit,
+
+    /// Decimal places for prices and costs [default: 2 for prices per 1M tokens, 5 per 1K,
+    /// 8 per token, and 6 for costs]
+    #[arg(long, global = true, value_name = "N")]
+    precision: Option<usize>,
+
+    /// How to draw tables in the terminal; long model IDs wrap to fit narrow terminals
+    #[arg(long, global = true, value_enum, default_value = "ascii", value_name = "STYLE")]
+    style: TableStyle,
+
+    /// Show only the first N rows after sorting (list, calc and chart)
+    #[arg(long, global = true, value_name = "N", conflicts_with = "tail")]
+    top: Option<usize>,
+
+    /// Show only the last N rows after sorting (list, calc and chart)
+    #[arg(long, global = true, value_name = "N")]
+    tail: Option<usize>,
+
+    /// How to group models in list output [default: provider]
+    #[arg(long, global = true, value_enum, value_name = "GROUP")]
+    group_by: Option<GroupBy>,
+
+    /// Follow list output with a line per group giving its model count and min/avg/max
+    /// input and output prices
+    #[arg(long, global = true)]
+    group_stats: bool,
+
+    /// Add a footer to tables: min/median/max for model lists, grand totals for calc
+    #[arg(long, global = true)]
+    summary: bool,
+
+    /// Don't send long output through $PAGER
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    /// Fail with the list of candidates when a model reference matches several models,
+    /// instead of asking which one to use (automatic when not run from a terminal)
+    #[arg(long, global = true)]
+    no_interactive: bool,
+
+    /// Copy the output to the system clipboard as well as printing it (without colors or
+    /// links, and never paged)
+    #[arg(long, global = true)]
+    copy: bool,
+
+    /// Don't render model IDs as clickable links, even in terminals that support them
+    #[arg(long, global = true)]
+    no_links: bool,
+
+    /// Language for table headers, report and quote text, and the setup questions [default:
+    /// en, or lang in the config file]
+    #[arg(long, global = true, value_enum, value_name = "LANG")]
+    lang: Option<i18n::Lang>,
+
+    /// Print the fully resolved invocation (saved query expansion, arguments, config defaults
+    /// and where prices would come from) without fetching or computing anything. With
+    /// install-integrations, print where each file would go without writing anything.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Output format [default: table, or as chosen during setup]
+    #[arg(long = "output", global = true, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+
+    /// File to write the workbook to with --output xlsx
+    #[arg(long, global = true, value_name = "PATH")]
+    out_file: Option<PathBuf>,
+
+    /// Columns to show in the model table, replacing the defaults (e.g., 'model,input,output,context')
+    #[arg(long, global = true, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+    columns: Vec<Column>,
+
+    /// Add tokens-per-dollar and GPU-hour equivalent columns for this GPU
+    #[arg(long, global = true, value_enum)]
+    gpu: Option<Gpu>,
+
+    /// Hourly rental price assumed for the GPU in USD (defaults: A100 $1.50, H100 $2.50)
+    #[arg(long, global = true, value_name = "USD", requires = "gpu")]
+    gpu_hourly_rate: Option<f64>,
+
+    /// Output tokens per second assumed when self-hosting on the GPU (defaults: A100 1500, H100 3000)
+    #[arg(long, global = true, value_name = "TOKENS", requires = "gpu")]
+    gpu_throughput: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+    /// Aligned text table (or detailed text with --verbose)
+    Table,
+    /// JSON document with every field from the API
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Standalone HTML page with a sortable table
+    Html,
+    /// GitHub-flavored Markdown table, e.g. for pull request comments
+    Markdown,
+    /// One compact JSON object per model or result, for streaming into line-oriented tools
+    Ndjson,
+    /// Excel workbook written to --out-file, with one sheet per provider for model lists
+    Xlsx,
+    /// Markdown added to the GitHub Actions job summary, with step outputs and annotations for
+    /// failed checks
+    #[serde(rename = "github-summary")]
+    GithubSummary,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Gpu {
+    A100,
+    H100,
+}
+
+impl Gpu {
+    fn default_hourly_rate(self) -> f64 {
+        match self {
+            Gpu::A100 => 1.50,
+            Gpu::H100 => 2.50,
+        }
+    }
+
+    fn default_throughput(self) -> f64 {
+        match self {
+            Gpu::A100 => 1500.0,
+            Gpu::H100 => 3000.0,
+        }
+    }
+}
+
+/// Assumptions for comparing API prices against renting GPUs directly
+#[derive(Debug, Clone, Copy)]
+struct GpuAssumptions {
+    gpu: Gpu,
+    hourly_rate: f64,
+    throughput: f64,
+}
+
+impl GpuAssumptions {
+    /// GPU-hours that the price of 1M tokens would rent
+    fn hours_per_million(&self, price_per_token: f64) -> f64 {
+        price_per_token * 1_000_000.0 / self.hourly_rate
+    }
+
+    /// Cost of generating 1M tokens on a rented GPU at the assumed throughput
+    fn self_host_cost_per_million(&self) -> f64 {
+        1_000_000.0 / (self.throughput * 3600.0) * self.hourly_rate
+    }
+}
+
+/// Optional columns for the default model table
+struct TableOptions {
+    show_license: bool,
+    /// Add context, created, modality and max completion columns
+    wide: bool,
+    /// Abbreviate provider prefixes in model IDs
+    compact: bool,
+    /// Add the Hugging Face columns
+    show_hugging_face: bool,
+    /// Add the input price trend column
+    show_trend: bool,
+    /// Add the region column
+    show_region: bool,
+    gpu: Option<GpuAssumptions>,
+    /// Exact columns chosen with --columns, replacing the defaults
+    columns: Option<Vec<Column>>,
+    /// Add min/median/max footer rows
+    summary: bool,
+    /// Column added to the default columns to show the --group-by group
+    group_column: Option<Column>,
+    /// Print per-group statistics after the models, grouped this way
+    group_stats: Option<GroupBy>,
+}
+
+/// A column of the model table, as named in --columns
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+enum Column {
+    /// Model ID
+    Model,
+    /// Display name
+    Name,
+    Provider,
+    /// Input price per 1M tokens
+    Input,
+    /// Output price per 1M tokens
+    Output,
+    /// Cache read price per 1M tokens
+    CacheRead,
+    /// Cache write price per 1M tokens
+    CacheWrite,
+    /// Price per request
+    Request,
+    /// Price per image
+    Image,
+    /// Context length in tokens
+    Context,
+    /// Maximum completion tokens
+    MaxCompletion,
+    /// Date the model was added
+    Created,
+    Tokenizer,
+    Modality,
+    License,
+    /// Lifecycle status
+    Status,
+    Approval,
+    Notes,
+    /// Derived tags such as code, vision or long-context
+    Tags,
+    /// Output tokens per dollar (requires --gpu)
+    TokensPerDollar,
+    /// GPU-hours that 1M output tokens' worth of spend would rent (requires --gpu)
+    GpuHours,
+    /// Cost of generating 1M tokens on a rented GPU (requires --gpu)
+    SelfHost,
+    /// Parameter count in billions, from Hugging Face
+    Params,
+    /// Architecture family, from Hugging Face
+    Family,
+    /// Date the weights were published, from Hugging Face
+    Released,
+    /// Sparkline of the input price over recent price snapshots
+    Trend,
+    /// Estimated gCO2e per 1M tokens, from Hugging Face parameter counts or the config
+    Carbon,
+    /// Regions the providers serving the model run in, from its OpenRouter endpoints
+    Region,
+}
+
+impl Column {
+    fn needs_gpu(self) -> bool {
+        matches!(self, Column::TokensPerDollar | Column::GpuHours | Column::SelfHost)
+    }
+
+    fn needs_hugging_face(self) -> bool {
+        matches!(
+            self,
+            Column::Params | Column::Family | Column::Released | Column::Carbon
+        )
+    }
+
+    fn header(self, gpu: Option<&GpuAssumptions>) -> String {
+        let id = match self {
+            Column::Model => "col-model",
+            Column::Name => "col-name",
+            Column::Provider => "col-provider",
+            Column::Input => return price_header(&i18n::t("col-input")),
+            Column::Output => return price_header(&i18n::t("col-output")),
+            Column::CacheRead => return price_header(&i18n::t("col-cache-read")),
+            Column::CacheWrite => return price_header(&i18n::t("col-cache-write")),
+            Column::Request => "col-request",
+            Column::Image => "col-image",
+            Column::Context => "col-context",
+            Column::MaxCompletion => "col-max-completion",
+            Column::Created => "col-created",
+            Column::Tokenizer => "col-tokenizer",
+            Column::Modality => "col-modality",
+            Column::License => "col-license",
+            Column::Status => "col-status",
+            Column::Approval => "col-approval",
+            Column::Notes => "col-notes",
+            Column::Tags => "col-tags",
+            Column::TokensPerDollar => "col-tokens-per-dollar",
+            Column::GpuHours => {
+                let gpu = gpu.map_or("GPU".to_string(), |gpu| format!("{:?}", gpu.gpu));
+                return i18n::t_args("col-gpu-hours", &[("gpu", gpu.into())]);
+            }
+            Column::SelfHost => "col-self-host",
+            Column::Params => "col-params",
+            Column::Family => "col-family",
+            Column::Released => "col-released",
+            Column::Trend => "col-trend",
+            Column::Carbon => "col-carbon",
+            Column::Region => "col-region",
+        };
+        i18n::t(id)
+    }
+
+    fn cell(self, model: &Model, metadata: &Metadata, gpu: Option<&GpuAssumptions>) -> Cell {
+        let optional_price = |price: &Option<String>| {
+            price.as_deref().map(Cell::price_str).unwrap_or(Cell::Missing)
+        };
+        let optional_text = |text: Option<&String>| text.map(Cell::text).unwrap_or(Cell::Missing);
+        let optional_cost = |price: &Option<String>| {
+            price
+                .as_deref()
+                .and_then(|price| price.parse::<f64>().ok())
+                .map(Cell::Cost)
+                .unwrap_or(Cell::Missing)
+        };
+        let optional_tokens =
+            |tokens: Option<u64>| tokens.map(|t| Cell::Number(t as f64, 0)).unwrap_or(Cell::Missing);
+        // Generation dominates compute, so compare against the output price
+        let output_price = model
+            .pricing
+            .completion
+            .parse::<f64>()
+            .ok()
+            .filter(|price| *price > 0.0);
+        let hugging_face = metadata.hugging_face(model);
+
+        match self {
+            Column::Model => model_link(&model.id),
+            Column::Name => optional_text(model.name.as_ref()),
+            Column::Provider => Cell::text(model.id.split('/').next().unwrap_or("unknown")),
+            Column::Input => Cell::price_str(&model.pricing.prompt),
+            Column::Output => Cell::price_str(&model.pricing.completion),
+            Column::CacheRead => optional_price(&model.pricing.input_cache_read),
+            Column::CacheWrite => optional_price(&model.pricing.input_cache_write),
+            // Per-request and per-image prices aren't per token, so show them as-is
+            Column::Request => optional_cost(&model.pricing.request),
+            Column::Image => optional_cost(&model.pricing.image),
+            Column::Context => optional_tokens(model.context_length),
+            Column::MaxCompletion => optional_tokens(
+                model
+                    .top_provider
+                    .as_ref()
+                    .and_then(|provider| provider.max_completion_tokens),
+            ),
+            Column::Created => model
+                .created
+                .and_then(|created| DateTime::from_timestamp(created as i64, 0))
+                .map(|created| Cell::text(created.format("%Y-%m-%d").to_string()))
+                .unwrap_or(Cell::Missing),
+            Column::Tokenizer => optional_text(
+                model
+                    .architecture
+                    .as_ref()
+                    .and_then(|arch| arch.tokenizer.as_ref()),
+            ),
+            Column::Modality => optional_text(
+                model
+                    .architecture
+                    .as_ref()
+                    .and_then(|arch| arch.modality.as_ref()),
+            ),
+            Column::License => Cell::text(metadata.license(model)),
+            Column::Status => Cell::text(
+                metadata
+                    .lifecycle
+                    .get(&model.id)
+                    .map(|entry| entry.status(metadata.today))
+                    .unwrap_or_default(),
+            ),
+            Column::Approval => Cell::text(
+                metadata.approval(model).map(Approval::as_str).unwrap_or_default(),
+            ),
+            Column::Notes => Cell::text(metadata.note(model).unwrap_or_default()),
+            Column::Tags => Cell::text(metadata.tags.of(model).join(", ")),
+            Column::TokensPerDollar => output_price
+                .map(|price| Cell::Number(1.0 / price, 0))
+                .unwrap_or(Cell::Missing),
+            Column::GpuHours => match (gpu, output_price) {
+                (Some(gpu), Some(price)) => Cell::Number(gpu.hours_per_million(price), 2),
+                _ => Cell::Missing,
+            },
+            Column::SelfHost => gpu
+                .map(|gpu| Cell::Number(gpu.self_host_cost_per_million(), 2))
+                .unwrap_or(Cell::Missing),
+            Column::Params => hugging_face
+                .and_then(|info| info.params)
+                .map(|params| Cell::Number(params as f64 / 1e9, 1))
+                .unwrap_or(Cell::Missing),
+            Column::Family => optional_text(hugging_face.and_then(|info| info.family.as_ref())),
+            Column::Released => hugging_face
+                .and_then(|info| info.released)
+                .map(|released| Cell::text(released.format("%Y-%m-%d").to_string()))
+                .unwrap_or(Cell::Missing),
+            // A single price has no trend to show
+            Column::Trend => metadata
+                .input_trends
+                .get(&model.id)
+                .filter(|prices| prices.len() > 1)
+                .map(|prices| Cell::text(history::sparkline(prices)))
+                .unwrap_or(Cell::Missing),
+            Column::Carbon => metadata
+                .emissions
+                .per_million(model, hugging_face.and_then(|info| info.params))
+                .map(|grams| Cell::Number(grams, 1))
+                .unwrap_or(Cell::Missing),
+            Column::Region => {
+                let mut regions: Vec<String> = Vec::new();
+                let residency = metadata.residency.get(&model.id);
+                if let Some(residency) = residency {
+                    regions.extend(residency.regions.iter().cloned());
+                }
+                if residency.is_none_or(|residency| !residency.unknown.is_empty()) {
+                    regions.push(i18n::t("region-unknown"));
+                }
+                Cell::text(regions.join(", "))
+            }
+        }
+    }
+}
+
+/// Bundled and user-supplied data that annotates models beyond what the API reports
+struct Metadata {
+    lifecycle: LifecycleMap,
+    licenses: Licenses,
+    notes: BTreeMap<String, String>,
+    approvals: BTreeMap<String, Approval>,
+    tags: Tags,
+    /// Fetched only when a Hugging Face column or filter is in use
+    hugging_face: BTreeMap<String, huggingface::ModelInfo>,
+    /// Input prices per token from recorded snapshots, oldest first, loaded only when the
+    /// trend column is shown
+    input_trends: BTreeMap<String, Vec<f64>>,
+    /// How the carbon column estimates emissions
+    emissions: carbon::Emissions,
+    /// Regions each provider serves from
+    regions: region::Regions,
+    /// Where the providers serving each model run, fetched only when --region or the region
+    /// column is in use
+    residency: BTreeMap<String, region::Residency>,
+    today: NaiveDate,
+}
+
+impl Metadata {
+    fn license(&self, model: &Model) -> &str {
+        self.licenses.lookup(model.hugging_face_id.as_deref())
+    }
+
+    fn note(&self, model: &Model) -> Option<&str> {
+        self.notes.get(&model.id).map(String::as_str)
+    }
+
+    fn approval(&self, model: &Model) -> Option<Approval> {
+        self.approvals.get(&model.id).copied()
+    }
+
+    fn hugging_face(&self, model: &Model) -> Option<&huggingface::ModelInfo> {
+        self.hugging_face.get(model.hugging_face_id.as_deref()?)
+    }
+}
+
+/// What models are grouped by in list output
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum GroupBy {
+    /// Group by the provider prefix of the model ID
+    Provider,
+    /// Group by input and output modality (e.g., 'text+image->text')
+    Modality,
+    /// Group by tokenizer family
+    Tokenizer,
+    /// Don't group; keep the overall sort order
+    None,
+}
+
+impl GroupBy {
+    /// The group a model belongs to, or an empty string when not grouping
+    fn key(self, model: &Model) -> String {
+        let architecture = model.architecture.as_ref();
+        let key = match self {
+            GroupBy::Provider => model.id.split('/').next(),
+            GroupBy::Modality => architecture.and_then(|arch| arch.modality.as_deref()),
+            GroupBy::Tokenizer => architecture.and_then(|arch| arch.tokenizer.as_deref()),
+            GroupBy::None => return String::new(),
+        };
+        key.unwrap_or("unknown").to_string()
+    }
+
+    fn label(self) -> String {
+        i18n::t(match self {
+            GroupBy::Provider => "col-provider",
+            GroupBy::Modality => "col-modality",
+            GroupBy::Tokenizer => "col-tokenizer",
+            GroupBy::None => "col-group",
+        })
+    }
+
+    /// The table column showing the group, when it isn't already evident from the model ID
+    fn column(self) -> Option<Column> {
+        match self {
+            GroupBy::Modality => Some(Column::Modality),
+            GroupBy::Tokenizer => Some(Column::Tokenizer),
+            GroupBy::Provider | GroupBy::None => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, EnumString, ValueEnum, VariantNames)]
@@ -43,6 +683,38 @@ enum SortBy {
     Total,
 }
 
+/// How OpenRouter credits are purchased, which determines the fee charged on top-ups
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum PaymentMethod {
+    /// Card payments (5.5% fee, $0.80 minimum per purchase)
+    Card,
+    /// Crypto payments (5% fee)
+    Crypto,
+}
+
+impl PaymentMethod {
+    fn fee_rate(self) -> f64 {
+        match self {
+            PaymentMethod::Card => 0.055,
+            PaymentMethod::Crypto => 0.05,
+        }
+    }
+
+    /// What `credits` worth of credits costs including the percentage fee. Any minimum fee
+    /// applies once per purchase rather than per request, so it's left to the note
+    fn top_up_cost(self, credits: f64) -> f64 {
+        credits * (1.0 + self.fee_rate())
+    }
+
+    fn fee_description(self) -> String {
+        i18n::t(match self {
+            PaymentMethod::Card => "fee-card",
+            PaymentMethod::Crypto => "fee-crypto",
+        })
+    }
+}
+
 #[derive(Parser, Debug)]
 enum Commands {
     /// List models with pricing (default)
@@ -52,6 +724,121 @@ enum Commands {
         /// Show verbose output with all model information
         #[arg(short, long)]
         verbose: bool,
+        /// Explain which filters include or exclude this model instead of listing
+        #[arg(long, value_name = "MODEL")]
+        why: Option<String>,
+    },
+    /// List models added to OpenRouter in the last few days with their prices, newest first
+    New {
+        /// How many days back to look
+        #[arg(long, default_value = "14")]
+        days: u32,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// List the N cheapest models by input, output or blended price
+    Cheapest {
+        /// How many models to list
+        #[arg(long = "n", default_value = "10", value_name = "N")]
+        count: usize,
+        /// Price to rank by
+        #[arg(long, value_enum, default_value_t)]
+        by: cheapest::Metric,
+        /// Ratio of input to output tokens for the blended price
+        #[arg(long, default_value = "3:1", value_name = "INPUT:OUTPUT")]
+        mix: cheapest::Mix,
+        /// Rank models that are free to use too
+        #[arg(long)]
+        include_free: bool,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Rank models by benchmark score per dollar, to find the best value rather than just the
+    /// cheapest
+    Rank {
+        /// Benchmark to score models on
+        #[arg(long, value_enum, default_value_t)]
+        benchmark: benchmarks::Benchmark,
+        /// Ratio of input to output tokens for the blended price
+        #[arg(long, default_value = "3:1", value_name = "INPUT:OUTPUT")]
+        mix: cheapest::Mix,
+        /// Leave out models scoring below this
+        #[arg(long, value_name = "SCORE")]
+        min_score: Option<f64>,
+        /// How many models to list [default: all with a score]
+        #[arg(long = "n", value_name = "N")]
+        count: Option<usize>,
+        /// JSON file of benchmark scores overriding the bundled data
+        #[arg(long, value_name = "PATH")]
+        benchmarks_file: Option<PathBuf>,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Compare providers as a whole: model count, price statistics, cheapest model and
+    /// context range of each
+    Stats {
+        /// Usage CSV, as for invoice, to also average each provider's prices weighted by the
+        /// tokens its models used
+        #[arg(long, value_name = "PATH")]
+        usage: Option<PathBuf>,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Show everything known about one model: every price, its architecture, limits and
+    /// supported parameters
+    Show {
+        /// Model ID, or a part of one that matches a single model (e.g., 'sonnet-4')
+        model: String,
+    },
+    /// Compare models side by side, one column per model, with each price and limit's change
+    /// relative to the first model
+    Compare {
+        /// Model IDs, or parts of ones that each match a single model (e.g., 'sonnet-4')
+        // Shares the id of the global filters so they aren't added after it
+        #[arg(id = "filters", value_name = "MODELS", required = true, num_args = 2..)]
+        models: Vec<String>,
+    },
+    /// Show each upstream provider serving a model with its own prices, context length and
+    /// quantization, since providers behind one model often charge differently
+    Endpoints {
+        /// Model ID, or a part of one that matches a single model (e.g., 'sonnet-4')
+        model: String,
+    },
+    /// Show a model's price changes over time, from the history recorded on each fetch
+    History {
+        /// Model ID, or a part of one that matches a single model (e.g., 'sonnet-4'). Models
+        /// no longer listed can be given by their full ID.
+        model: String,
+    },
+    /// Save the current model list and prices to a timestamped JSON file, for diffing later
+    Snapshot {
+        /// File to write [default: a file named after the fetch time in the data directory]
+        #[arg(short = 'o', long = "file", value_name = "PATH")]
+        file: Option<PathBuf>,
+        /// Indent the JSON for reading instead of writing it on one line
+        #[arg(long)]
+        pretty: bool,
+    },
+    /// Keep running, re-fetching prices on an interval and printing the models added,
+    /// removed and re-priced since the previous poll
+    Watch {
+        /// Time between polls (e.g., '30m', '1h')
+        #[arg(long, default_value = "1h", value_name = "DURATION")]
+        interval: String,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Show models added, removed and re-priced between two saved model lists, or since a
+    /// date in the local price history
+    Diff {
+        /// Older model list, as saved with '--output json' (or an API response or the cache file)
+        #[arg(required_unless_present = "since")]
+        old: Option<PathBuf>,
+        /// Newer model list [default: current prices]
+        new: Option<PathBuf>,
+        /// Compare current prices with the local price history as of this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", conflicts_with_all = ["old", "new"])]
+        since: Option<NaiveDate>,
     },
     /// Calculate cost for a specific request
     Calc {
@@ -61,12 +848,574 @@ enum Commands {
         output: u64,
         /// Filter models by name (e.g., 'anthropic/', 'sonnet')
         filters: Vec<String>,
-        /// Number of cached input tokens read from cache
+        /// Number of cached input tokens read from cache, or a percentage of the input tokens (e.g., '80%')
+        #[arg(short, long)]
+        cached: Option<CachedTokens>,
+        /// Cache TTL in minutes (affects pricing for some models, default: 5)
+        #[arg(short, long, default_value = "5", value_parser = parse_ttl)]
+        ttl: u64,
+        /// Show the per-token rates applied to each cost component
+        #[arg(long)]
+        show_rates: bool,
+        /// Also show what each cost takes to top up in OpenRouter credits, including purchase fees
+        #[arg(long)]
+        credits: bool,
+        /// Payment method used to buy credits, which determines the top-up fee
+        #[arg(long, value_enum, default_value = "card", requires = "credits")]
+        payment: PaymentMethod,
+        /// Processing tier to price requests at; only models offering it are shown
+        #[arg(long, value_enum, default_value = "standard")]
+        tier: ServiceTier,
+        /// Compare scenarios by sweeping a parameter over several values
+        /// (e.g., 'ttl=5,60' or 'cached=none,0,50%,90%'); may be repeated
+        #[arg(long, value_name = "PARAM=VALUES")]
+        sweep: Vec<String>,
+        /// Save the computed result under this name, to compare later with 'results diff'
+        #[arg(long, value_name = "NAME", conflicts_with = "sweep")]
+        save_as: Option<String>,
+    },
+    /// Save a complete invocation under a name to re-run later with 'run'
+    SaveQuery {
+        /// Name to save the query under
+        name: String,
+        /// Arguments to save, given after '--' (e.g., -- list anthropic/ --sort input)
+        // Shares the id of the global filters so they aren't added after it
+        #[arg(id = "filters", value_name = "ARGS", last = true, required = true)]
+        args: Vec<String>,
+    },
+    /// Run a saved query, or list saved queries when no name is given
+    Run {
+        /// Name of the saved query
+        name: Option<String>,
+    },
+    /// Diagnose paths, config, API key and connectivity problems
+    Doctor,
+    /// Fetch the model list and Hugging Face metadata into the cache ahead of time, e.g. from
+    /// a login script or container entrypoint
+    Warm,
+    /// Check which models can sustain a request rate under provider rate limits, and at what cost
+    Throughput {
+        /// Requests per second the workload needs to sustain
+        #[arg(long, value_name = "RPS")]
+        target_rps: f64,
+        /// Tokens in a typical request as INPUT/OUTPUT (e.g., '1500/300')
+        #[arg(long, value_name = "INPUT/OUTPUT")]
+        tokens_per_req: throughput::TokensPerRequest,
+        /// JSON file of rate limits overriding the bundled data
+        #[arg(long, value_name = "PATH")]
+        rate_limits_file: Option<PathBuf>,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Split a monthly load across candidate models at the lowest cost that stays within each
+    /// model's rate limits
+    Plan {
+        /// Requests a month
+        #[arg(long)]
+        requests: u64,
+        /// Input tokens a month
+        #[arg(long, value_name = "TOKENS")]
+        input: u64,
+        /// Output tokens a month
+        #[arg(long = "output-tokens", default_value = "0", value_name = "TOKENS")]
+        output: u64,
+        /// API keys or accounts available per model
+        #[arg(long, default_value = "1")]
+        keys: u64,
+        /// Busiest rate as a multiple of the average rate over the month
+        #[arg(long, default_value = "1.0", value_name = "FACTOR")]
+        peak_factor: f64,
+        /// JSON file of rate limits overriding the bundled data
+        #[arg(long, value_name = "PATH")]
+        rate_limits_file: Option<PathBuf>,
+        /// Candidate models, by name filter (e.g., 'anthropic/claude-sonnet-4', 'gpt-4.1')
+        #[arg(required = true)]
+        filters: Vec<String>,
+    },
+    /// Estimate per-model savings from prompt compression, net of any extra output it causes
+    CompressSavings {
+        /// Input tokens per request before compression
+        #[arg(long)]
+        input: u64,
+        /// Output tokens per request before compression
+        #[arg(long = "output-tokens", default_value = "0", value_name = "TOKENS")]
+        output: u64,
+        /// Fraction of input tokens kept after compression (e.g., '0.6' keeps 60%)
+        #[arg(long)]
+        ratio: f64,
+        /// Expected increase in output tokens from working with a compressed prompt (e.g., '10%')
+        #[arg(long, default_value = "0%", value_name = "PERCENT")]
+        output_risk: String,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Flag suspicious catalog entries, such as missing or negative prices, and exit with an
+    /// error if any are found
+    LintData {
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Forecast monthly cost per model as P50/P90/P99 from uncertain token counts
+    Forecast {
+        /// Requests per month
+        #[arg(long)]
+        requests: u64,
+        /// Average input tokens per request, optionally with a spread (e.g., '2000±500')
+        #[arg(long, value_name = "TOKENS[±SPREAD]")]
+        input: forecast::TokenEstimate,
+        /// Average output tokens per request, optionally with a spread (e.g., '300±150')
+        #[arg(long = "output-tokens", value_name = "TOKENS[±SPREAD]")]
+        output: forecast::TokenEstimate,
+        /// Add estimated monthly emissions in kg CO2e, from Hugging Face parameter counts or
+        /// the [carbon] config section
+        #[arg(long)]
+        carbon: bool,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// For open-weights models with more than one host, show the cheapest and most expensive
+    /// provider for a monthly token volume and what switching saves
+    Arbitrage {
+        /// Input tokens a month
+        #[arg(long, value_name = "TOKENS")]
+        input: u64,
+        /// Output tokens a month
+        #[arg(long = "output-tokens", default_value = "0", value_name = "TOKENS")]
+        output: u64,
+        /// Filter models by name (e.g., 'meta-llama/', 'qwen')
+        filters: Vec<String>,
+    },
+    /// Estimate the blended cost of a cheap draft model that escalates some requests to an
+    /// expensive verifier model, against either model alone
+    Routing {
+        /// Cheap model that answers every request (model ID or a unique part of one)
+        #[arg(long, value_name = "MODEL")]
+        draft: String,
+        /// Expensive model that escalated requests are sent to (model ID or a unique part of one)
+        #[arg(long, value_name = "MODEL")]
+        verifier: String,
+        /// Input tokens per request
+        #[arg(long)]
+        input: u64,
+        /// Output tokens per request
+        #[arg(long = "output-tokens", default_value = "0", value_name = "TOKENS")]
+        output: u64,
+        /// Share of requests escalated to the verifier (e.g., '20%')
+        #[arg(long, default_value = "20%", value_name = "PERCENT")]
+        escalation_rate: String,
+    },
+    /// Replay historical requests through alternative routing strategies and compare what
+    /// each would have cost with actual spend
+    Backtest {
+        /// JSONL file with one request per line: model, input_tokens and output_tokens, and
+        /// optionally cached_tokens, cost, tier and modalities
+        #[arg(long, value_name = "PATH")]
+        usage: PathBuf,
+        /// TOML, YAML or JSON file of [[strategy]] entries, each with a name and a kind:
+        /// cheapest-capable, quality-tiered or cache-aware
+        #[arg(long, value_name = "PATH")]
+        strategy: PathBuf,
+    },
+    /// Write a printable report of model prices to a file
+    Report {
+        /// Produce a rate card: prices, context and notes for the selected models
+        #[arg(long, required = true)]
+        rate_card: bool,
+        /// File to write; the extension selects the format (.pdf or .html)
+        #[arg(short = 'o', long = "file", value_name = "PATH")]
+        file: PathBuf,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Check changed prompt templates against per-request cost budgets, as a pre-commit or
+    /// CI hook, and exit with an error if any template is over budget
+    Hook {
+        /// Directory of prompt templates, relative to the current directory
+        #[arg(long, value_name = "DIR")]
+        prompts: PathBuf,
+        /// Budget per request in USD for templates whose frontmatter doesn't give max_cost
+        #[arg(long, value_name = "USD")]
+        max_prompt_cost: Option<f64>,
+        /// Model to price templates at (model ID or a unique part of one); defaults to
+        /// hook_model in the config file
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+        /// Output tokens for templates whose frontmatter doesn't give output_tokens
+        #[arg(long = "output-tokens", value_name = "TOKENS")]
+        output_tokens: Option<u64>,
+        /// Check templates changed since this revision instead of staged ones (e.g.,
+        /// 'origin/main' in CI)
+        #[arg(long, value_name = "REV")]
+        base: Option<String>,
+        /// Check every template, not just changed ones
+        #[arg(long, conflicts_with = "base")]
+        all: bool,
+    },
+    /// Produce a dated cost quote for one model to share, as Markdown or a PNG image
+    Quote {
+        /// Model to quote (model ID or a unique part of one)
+        model: String,
+        /// Number of input tokens per request
+        input: u64,
+        /// Number of output tokens per request
+        output: u64,
+        /// Number of cached input tokens read from cache, or a percentage of the input tokens (e.g., '80%')
         #[arg(short, long)]
-        cached: Option<u64>,
+        cached: Option<CachedTokens>,
         /// Cache TTL in minutes (affects pricing for some models, default: 5)
-        #[arg(short, long, default_value = "5")]
+        #[arg(short, long, default_value = "5", value_parser = parse_ttl)]
         ttl: u64,
+        /// Number of requests to quote for
+        #[arg(long, default_value_t = 1)]
+        requests: u64,
+        /// File to write; the extension selects the format (.md or .png). Prints Markdown when
+        /// omitted
+        #[arg(short = 'o', long = "file", value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Write a shields.io-style badge with a model's current prices, as an SVG or as the JSON
+    /// a shields.io endpoint badge reads
+    Badge {
+        /// Model ID, or a part of one that matches a single model (e.g., 'sonnet-4')
+        model: String,
+        /// Which prices to show
+        #[arg(long, value_enum, default_value_t)]
+        price: badge::BadgePrice,
+        /// Left-hand text [default: the model ID]
+        #[arg(long)]
+        label: Option<String>,
+        /// File to write; the extension selects the format (.svg or .json). Prints the SVG
+        /// when omitted
+        #[arg(short = 'o', long = "file", value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Price a usage CSV and write it as an itemized invoice for billing LLM costs on to a
+    /// client, with an optional markup
+    Invoice {
+        /// CSV with a header row naming date, model, input_tokens and output_tokens columns,
+        /// and optionally cached_tokens and requests
+        #[arg(long, value_name = "PATH")]
+        usage: PathBuf,
+        /// Only bill usage dated in this month (YYYY-MM)
+        #[arg(long, value_name = "YYYY-MM")]
+        period: Option<String>,
+        /// Added on top of cost (e.g., '15%')
+        #[arg(long, default_value = "0%", value_name = "PERCENT")]
+        markup: String,
+        /// Who the invoice is addressed to
+        #[arg(long)]
+        client: Option<String>,
+        /// Invoice number [default: the period, or today's date]
+        #[arg(long)]
+        number: Option<String>,
+        /// File to write; the extension selects the format (.html or .md). Prints Markdown
+        /// when omitted
+        #[arg(short = 'o', long = "file", value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Export model prices to a Parquet, Arrow or SQLite file for DuckDB, Pandas and other
+    /// analytics tools, to an Excel workbook, or to a Google spreadsheet
+    Export {
+        /// File format to write
+        #[arg(long = "format", value_enum, default_value = "parquet")]
+        export_format: export::ExportFormat,
+        /// File to write
+        #[arg(
+            short = 'o',
+            long = "file",
+            value_name = "PATH",
+            required_unless_present = "google_sheet"
+        )]
+        file: Option<PathBuf>,
+        /// Replace a tab of this Google spreadsheet with current prices instead of writing a
+        /// file, using the service account key set as google_service_account in the config
+        /// file (or $GOOGLE_APPLICATION_CREDENTIALS)
+        #[arg(long, value_name = "ID", conflicts_with_all = ["file", "export_format"])]
+        google_sheet: Option<String>,
+        /// Spreadsheet tab to replace with current prices
+        #[arg(long, value_name = "NAME", default_value = "Pricing", requires = "google_sheet")]
+        sheet_tab: String,
+        /// Also replace a 'Spend' tab with the credit usage of each OpenRouter key (as shown
+        /// by 'account')
+        #[arg(long, requires = "google_sheet")]
+        spend: bool,
+        /// Requests per month to project each model's monthly budget from (tfvars and
+        /// json-budget formats)
+        #[arg(long, requires_all = ["input", "output"])]
+        requests: Option<u64>,
+        /// Average input tokens per request for the budget projection
+        #[arg(long, requires = "requests")]
+        input: Option<u64>,
+        /// Average output tokens per request for the budget projection
+        #[arg(long = "output-tokens", value_name = "TOKENS", requires = "requests")]
+        output: Option<u64>,
+        /// Headroom added on top of each projected monthly cost for its budget (e.g., '20%')
+        #[arg(long, default_value = "0%", value_name = "PERCENT")]
+        headroom: String,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Run a SQL query against current prices (table 'models') and saved calc results
+    /// (table 'results'), for comparisons the other commands don't cover
+    Sql {
+        /// Query to run (e.g., "SELECT provider, min(input_price) FROM models GROUP BY 1")
+        query: String,
+    },
+    /// Draw a bar chart of input or output prices in the terminal, cheapest first unless
+    /// --sort says otherwise
+    Chart {
+        /// Price to chart
+        #[arg(long, value_enum, default_value = "input")]
+        metric: chart::Metric,
+        /// Draw the chart as an image at this path instead of in the terminal; the extension
+        /// selects the format (.svg or .png)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        /// What to draw
+        #[arg(long, value_enum, default_value = "bar")]
+        kind: chart::ChartKind,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Publish the model table to a Notion database or a Confluence page, e.g. from a cron
+    /// job to keep internal docs in sync with live prices
+    Publish {
+        #[command(subcommand)]
+        target: PublishTarget,
+    },
+    /// Set a model's approval status, or list approvals when no model is given
+    Approve {
+        /// Model ID (e.g., 'openai/gpt-4o')
+        model: Option<String>,
+        /// Approval status to record
+        #[arg(long, value_enum, default_value = "approved", requires = "model")]
+        status: Approval,
+        /// Remove the model's approval status instead
+        #[arg(long, requires = "model", conflicts_with = "status")]
+        clear: bool,
+    },
+    /// Choose default settings interactively and write them to the config file
+    Init,
+    /// Save price alert rules and check them against current prices
+    Alert {
+        #[command(subcommand)]
+        action: AlertAction,
+    },
+    /// Compare calc results saved with --save-as against current pricing
+    Results {
+        #[command(subcommand)]
+        action: ResultsAction,
+    },
+    /// Estimate what prompt templates cost per request on each model
+    Prompts {
+        #[command(subcommand)]
+        action: PromptsAction,
+    },
+    /// Show usage and limits of each OpenRouter API key, with totals across them
+    Account,
+    /// Manage API keys stored in the OS keychain
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Install shell completions and man pages, and optionally a prompt or tmux status-line
+    /// snippet showing a model's prices
+    InstallIntegrations {
+        /// Shells to install completions and prompt snippets for; defaults to the shell in
+        /// $SHELL
+        #[arg(long, value_enum)]
+        shell: Vec<integrations::Shell>,
+        /// Also show a model's input/output price in the shell prompt
+        #[arg(long, conflicts_with = "prefix")]
+        prompt: bool,
+        /// Also install a tmux snippet showing a model's input/output price in the status line
+        #[arg(long, conflicts_with = "prefix")]
+        status_line: bool,
+        /// Model the prompt and status line show; $LLM_PRICING_MODEL overrides it later
+        #[arg(long, value_name = "MODEL", default_value = integrations::DEFAULT_PROMPT_MODEL)]
+        model: String,
+        /// Install completions and man pages under this prefix in the standard share/ layout
+        /// instead of your home directory, when building a package (e.g., Homebrew or .deb)
+        #[arg(long, value_name = "DIR")]
+        prefix: Option<PathBuf>,
+    },
+    /// Update this binary to the latest GitHub release, checking its download against the
+    /// release's SHA-256 checksum
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release is available
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+impl Commands {
+    /// Whether the command resolves a model reference, which may ask which model was meant
+    fn picks_model(&self) -> bool {
+        matches!(
+            self,
+            Commands::List { why: Some(_), .. }
+                | Commands::Show { .. }
+                | Commands::Compare { .. }
+                | Commands::Endpoints { .. }
+                | Commands::History { .. }
+                | Commands::Routing { .. }
+                | Commands::Backtest { .. }
+                | Commands::Hook { .. }
+                | Commands::Quote { .. }
+                | Commands::Badge { .. }
+        )
+    }
+}
+
+#[derive(Parser, Debug)]
+enum PublishTarget {
+    /// Add or update a row per model in a Notion database, matched by its title property.
+    /// Uses the integration token stored with 'llm-pricing auth set notion' or $NOTION_API_KEY.
+    Notion {
+        /// Database ID, from the database's URL
+        #[arg(long, value_name = "ID")]
+        database: String,
+        /// Archive rows for models that are no longer listed
+        #[arg(long)]
+        prune: bool,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Replace the body of a Confluence page with the model table. Uses the API token stored
+    /// with 'llm-pricing auth set confluence' or $CONFLUENCE_API_KEY.
+    Confluence {
+        /// Confluence base URL (e.g., 'https://acme.atlassian.net/wiki')
+        #[arg(long, value_name = "URL")]
+        site: String,
+        /// Page ID, from the page's URL
+        #[arg(long, value_name = "ID")]
+        page: String,
+        /// Atlassian account email for a Confluence Cloud API token; leave out to use a
+        /// Server or Data Center personal access token
+        #[arg(long, value_name = "EMAIL")]
+        user: Option<String>,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+}
+
+#[derive(Parser, Debug)]
+enum AuthAction {
+    /// Store an API key, read from a hidden prompt or from stdin
+    Set {
+        /// Provider the key is for
+        #[arg(default_value = auth::DEFAULT_PROVIDER)]
+        provider: String,
+        /// Store an additional key under this name (e.g., a team), alongside the default key
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Show whether a key is stored or set in the environment, and which is used
+    Status {
+        /// Provider the key is for
+        #[arg(default_value = auth::DEFAULT_PROVIDER)]
+        provider: String,
+    },
+    /// Remove a stored API key
+    Remove {
+        /// Provider the key is for
+        #[arg(default_value = auth::DEFAULT_PROVIDER)]
+        provider: String,
+        /// Remove the additional key stored under this name instead of the default key
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Parser, Debug)]
+enum AlertAction {
+    /// Save a rule, replacing any saved under the same name. Prices are in USD per million
+    /// tokens.
+    Add {
+        /// Name to save the rule under
+        name: String,
+        /// Models the rule covers, as a name filter (e.g., 'anthropic/claude-sonnet-4')
+        /// [default: every model]
+        #[arg(long)]
+        model: Option<String>,
+        /// Trigger when the input price is below this
+        #[arg(long, value_name = "PRICE")]
+        input_below: Option<f64>,
+        /// Trigger when the input price is above this
+        #[arg(long, value_name = "PRICE")]
+        input_above: Option<f64>,
+        /// Trigger when the output price is below this
+        #[arg(long, value_name = "PRICE")]
+        output_below: Option<f64>,
+        /// Trigger when the output price is above this
+        #[arg(long, value_name = "PRICE")]
+        output_above: Option<f64>,
+        /// Trigger when the input or output price moves by more than this, either way (e.g., '20%')
+        #[arg(long, value_name = "PERCENT")]
+        change: Option<String>,
+        /// How far back --change looks in the price history (e.g., '7d') [default: 1d]
+        #[arg(long, value_name = "DURATION", requires = "change")]
+        within: Option<String>,
+    },
+    /// List saved rules
+    List,
+    /// Remove a saved rule
+    Remove {
+        /// Name the rule was saved under
+        name: String,
+    },
+    /// Check the saved rules against current prices, exiting with status 1 if any trigger
+    Check,
+}
+
+#[derive(Parser, Debug)]
+enum ResultsAction {
+    /// List saved results
+    List,
+    /// Re-run a saved result's request and show how each model's cost has changed
+    Diff {
+        /// Name the result was saved under
+        name: String,
+        /// Compare against current pricing
+        #[arg(long, required = true)]
+        live: bool,
+    },
+}
+
+#[derive(Parser, Debug)]
+enum PromptsAction {
+    /// Cost every template in a directory on each model. Templates give their expected output
+    /// length as 'output_tokens: N' in '---' frontmatter.
+    Cost {
+        /// Directory of prompt templates
+        dir: PathBuf,
+        /// Output tokens for templates whose frontmatter doesn't give output_tokens
+        #[arg(long = "output-tokens", value_name = "TOKENS")]
+        output_tokens: Option<u64>,
+        /// Filter models by name (e.g., 'anthropic/', 'sonnet')
+        filters: Vec<String>,
+    },
+    /// Compare template costs between two git revisions, e.g. for a pull request comment with
+    /// '--output markdown'
+    Diff {
+        /// Directory of prompt templates, relative to the current directory
+        dir: PathBuf,
+        /// Revision to compare against
+        #[arg(long, default_value = "main", value_name = "REV")]
+        base: String,
+        /// Revision with the changes
+        #[arg(long, default_value = "HEAD", value_name = "REV")]
+        head: String,
+        /// Output tokens for templates whose frontmatter doesn't give output_tokens
+        #[arg(long = "output-tokens", value_name = "TOKENS")]
+        output_tokens: Option<u64>,
+        /// Exit with status 1 if a template's cost on any model rises by more than this percentage
+        #[arg(long, value_name = "PERCENT")]
+        max_increase: Option<f64>,
+        /// Models to compare on (e.g., 'anthropic/claude-sonnet-4', 'gpt-4o')
+        #[arg(required = true)]
+        filters: Vec<String>,
     },
 }
 
@@ -140,58 +1489,334 @@ struct ApiResponse {
     data: Vec<Model>,
 }
 
-async fn fetch_models() -> anyhow::Result<Vec<Model>> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://openrouter.ai/api/v1/models")
-        .send()
-        .await?;
+const MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
 
-    let api_response: ApiResponse = response.json().await?;
-    
-    // Filter out openrouter/auto which has negative pricing
-    let filtered_models = api_response.data
-        .into_iter()
-        .filter(|model| model.id != "openrouter/auto")
+/// Parse a percentage such as '10%' into a fraction, for the flag named `flag`
+fn parse_percent(flag: &str, value: &str) -> anyhow::Result<f64> {
+    match value.trim().strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().ok().filter(|p| *p >= 0.0),
+        None => None,
+    }
+    .map(|percent| percent / 100.0)
+    .ok_or_else(|| {
+        anyhow::anyhow!("Invalid {}: '{}'. Expected a percentage (e.g., '10%')", flag, value)
+    })
+}
+
+/// Model pages on the OpenRouter website, followed by the model ID
+const MODEL_PAGE_URL: &str = "https://openrouter.ai/models/";
+
+/// Find a single model by exact ID, or by a part of the ID that only one model matches
+fn find_model<'a>(models: &'a [Model], query: &str) -> anyhow::Result<&'a Model> {
+    if let Some(model) = models.iter().find(|model| model.id == query) {
+        return Ok(model);
+    }
+    let query_lower = query.to_lowercase();
+    let matches: Vec<&Model> = models
+        .iter()
+        .filter(|model| model.id.to_lowercase().contains(&query_lower))
         .collect();
-    
-    Ok(filtered_models)
+    match matches.as_slice() {
+        [model] => Ok(model),
+        [] => Err(anyhow::anyhow!("No model matches '{}'", query)),
+        _ if terminal::is_interactive() => choose_model(query, matches),
+        _ => {
+            let mut ids: Vec<&str> = matches.iter().map(|model| model.id.as_str()).collect();
+            ids.sort_unstable();
+            Err(anyhow::anyhow!(
+                "'{}' matches several models: {}",
+                query,
+                ids.join(", ")
+            ))
+        }
+    }
 }
 
-fn group_models_by_provider(models: Vec<Model>) -> HashMap<String, Vec<Model>> {
-    let mut grouped = HashMap::new();
+/// Ask which of several matching models was meant, on stderr so the answer doesn't end up
+/// in the command's output
+fn choose_model<'a>(query: &str, mut matches: Vec<&'a Model>) -> anyhow::Result<&'a Model> {
+    matches.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+    eprintln!("'{}' matches several models:", query);
+    for (i, model) in matches.iter().enumerate() {
+        eprintln!("  {:>3}) {}", i + 1, model.id);
+    }
+    loop {
+        eprint!("Which one? [1-{}] ", matches.len());
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        let read = io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .context("Failed to read answer")?;
+        if read == 0 {
+            return Err(anyhow::anyhow!("No model chosen for '{}'", query));
+        }
+        match answer.trim().parse::<usize>() {
+            Ok(choice) if (1..=matches.len()).contains(&choice) => return Ok(matches[choice - 1]),
+            _ => eprintln!("Enter a number from 1 to {}", matches.len()),
+        }
+    }
+}
 
-    for model in models {
-        let provider = model.id.split('/').next().unwrap_or("unknown").to_string();
-        grouped.entry(provider).or_insert_with(Vec::new).push(model);
+/// Model ID cell linking to the model's OpenRouter page
+fn model_link(id: &str) -> Cell {
+    Cell::Link {
+        text: id.to_string(),
+        url: format!("{MODEL_PAGE_URL}{id}"),
     }
+}
 
-    grouped
+/// Pricing data is considered stale after this long unless --max-age says otherwise
+const DEFAULT_STALE_AFTER: TimeDelta = TimeDelta::hours(24);
+
+/// Where a set of prices came from and when it was fetched
+#[derive(Debug, Clone)]
+struct SourceInfo {
+    name: &'static str,
+    url: &'static str,
+    fetched_at: DateTime<Utc>,
 }
 
-fn filter_models(
-    grouped: HashMap<String, Vec<Model>>,
-    filters: Vec<String>,
-) -> HashMap<String, Vec<Model>> {
-    if filters.is_empty() {
-        return grouped;
+impl SourceInfo {
+    fn age(&self) -> TimeDelta {
+        Utc::now() - self.fetched_at
     }
 
-    let filter_lowers: Vec<String> = filters.iter().map(|f| f.to_lowercase()).collect();
+    fn staleness(&self, stale_after: TimeDelta) -> &'static str {
+        if self.age() > stale_after {
+            "stale"
+        } else {
+            "fresh"
+        }
+    }
 
-    let mut filtered = HashMap::new();
+    fn describe(&self, stale_after: TimeDelta) -> String {
+        format!(
+            "{} ({}), fetched {} ({} ago, {})",
+            self.name,
+            self.url,
+            self.fetched_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            format_duration(self.age()),
+            self.staleness(stale_after)
+        )
+    }
+}
+
+struct Catalog {
+    models: Vec<Model>,
+    source: SourceInfo,
+}
+
+fn parse_duration(duration_str: &str) -> anyhow::Result<TimeDelta> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid duration: '{}'. Use a number followed by s, m, h or d (e.g., '24h')",
+            duration_str
+        )
+    };
+
+    let trimmed = duration_str.trim();
+    let unit = trimmed.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = trimmed[..trimmed.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+
+    if amount <= 0 {
+        return Err(anyhow::anyhow!(
+            "Invalid duration: '{}'. The duration must be longer than zero",
+            duration_str
+        ));
+    }
+
+    match unit {
+        's' => TimeDelta::try_seconds(amount),
+        'm' => TimeDelta::try_minutes(amount),
+        'h' => TimeDelta::try_hours(amount),
+        'd' => TimeDelta::try_days(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+fn format_duration(duration: TimeDelta) -> String {
+    let seconds = duration.num_seconds().max(0);
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 86400 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{}d", seconds / 86400)
+    }
+}
+
+fn check_max_age(source: &SourceInfo, max_age: Option<TimeDelta>) -> anyhow::Result<()> {
+    if let Some(max_age) = max_age {
+        if source.age() > max_age {
+            return Err(anyhow::anyhow!(
+                "Pricing data from {} is {} old, which exceeds --max-age {}",
+                source.name,
+                format_duration(source.age()),
+                format_duration(max_age)
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_catalog() -> anyhow::Result<Catalog> {
+    let client = reqwest::Client::new();
+    let response = client.get(MODELS_URL).send().await?;
+    let fetched_at = Utc::now();
+
+    let api_response: ApiResponse = response.json().await?;
+    
+    // Filter out openrouter/auto which has negative pricing
+    let filtered_models = api_response.data
+        .into_iter()
+        .filter(|model| model.id != "openrouter/auto")
+        .collect();
+    
+    Ok(Catalog {
+        models: filtered_models,
+        source: SourceInfo {
+            name: "OpenRouter API",
+            url: MODELS_URL,
+            fetched_at,
+        },
+    })
+}
+
+/// Fetch the model list, or reuse a recent cached copy when caching is enabled in the config
+async fn load_catalog(use_cache: bool, max_age: Option<TimeDelta>) -> anyhow::Result<Catalog> {
+    if !use_cache {
+        let catalog = fetch_catalog().await?;
+        record_history(&catalog);
+        return Ok(catalog);
+    }
+
+    let cache_ttl = max_age.map_or(cache::CATALOG_TTL, |max_age| max_age.min(cache::CATALOG_TTL));
+    if let Some((models, fetched_at)) = cache::load_catalog(cache_ttl) {
+        return Ok(Catalog {
+            models,
+            source: SourceInfo {
+                name: "OpenRouter API (cached)",
+                url: MODELS_URL,
+                fetched_at,
+            },
+        });
+    }
+
+    let catalog = fetch_catalog().await?;
+    if let Err(e) = cache::store_catalog(&catalog.models, catalog.source.fetched_at) {
+        eprintln!("Warning: {e:#}");
+    }
+    record_history(&catalog);
+    Ok(catalog)
+}
+
+/// Fetch the model list into the cache, then the Hugging Face metadata of every model that has
+/// any, so the next commands don't wait on the network
+async fn warm_cache(use_cache: bool) -> anyhow::Result<()> {
+    let catalog = fetch_catalog().await?;
+    cache::store_catalog(&catalog.models, catalog.source.fetched_at)?;
+    record_history(&catalog);
+    let hugging_face = huggingface::load(&catalog.models).await;
+    if !terminal::is_quiet() {
+        println!(
+            "Cached {} models (reused for {}) and Hugging Face metadata for {} models",
+            catalog.models.len(),
+            format_duration(cache::CATALOG_TTL),
+            hugging_face.len()
+        );
+    }
+    if !use_cache {
+        eprintln!(
+            "Note: caching is off, so other commands still fetch the model list every run. Turn \
+             it on with 'cache = true' in the config file or 'llm-pricing init'."
+        );
+    }
+    Ok(())
+}
+
+/// Add freshly fetched prices to the local price history behind the trend column, warning
+/// about models that are gone since the last fetch
+fn record_history(catalog: &Catalog) {
+    diff::warn_removed(&catalog.models);
+    if let Err(e) = history::record(&catalog.models, catalog.source.fetched_at) {
+        eprintln!("Warning: {e:#}");
+    }
+}
+
+fn group_models_by_provider(models: Vec<Model>) -> BTreeMap<String, Vec<Model>> {
+    group_models(models, GroupBy::Provider)
+}
+
+/// Group models, keeping their order within each group. Not grouping puts every model in
+/// one group under an empty key.
+fn group_models(models: Vec<Model>, group_by: GroupBy) -> BTreeMap<String, Vec<Model>> {
+    let mut grouped = BTreeMap::new();
+
+    for model in models {
+        grouped
+            .entry(group_by.key(&model))
+            .or_insert_with(Vec::new)
+            .push(model);
+    }
+
+    grouped
+}
+
+/// Whether the model's ID or name contains any of the (lowercased) filters
+fn matches_name_filters(model: &Model, filter_lowers: &[String]) -> bool {
+    filter_lowers.iter().any(|filter_lower| {
+        model.id.to_lowercase().contains(filter_lower)
+            || model
+                .name
+                .as_ref()
+                .is_some_and(|name| name.to_lowercase().contains(filter_lower))
+    })
+}
+
+/// Keep models whose ID or name contains any of the filters, preserving their order
+fn filter_by_name(models: Vec<Model>, filters: &[String]) -> Vec<Model> {
+    if filters.is_empty() {
+        return models;
+    }
+    let filter_lowers: Vec<String> = filters.iter().map(|f| f.to_lowercase()).collect();
+    models
+        .into_iter()
+        .filter(|model| matches_name_filters(model, &filter_lowers))
+        .collect()
+}
+
+/// Keep only the first `top` or last `tail` rows, for --top and --tail
+fn limit_rows<T>(mut rows: Vec<T>, top: Option<usize>, tail: Option<usize>) -> Vec<T> {
+    if let Some(top) = top {
+        rows.truncate(top);
+    }
+    if let Some(tail) = tail {
+        rows.drain(..rows.len().saturating_sub(tail));
+    }
+    rows
+}
+
+fn filter_models(
+    grouped: BTreeMap<String, Vec<Model>>,
+    filters: Vec<String>,
+) -> BTreeMap<String, Vec<Model>> {
+    if filters.is_empty() {
+        return grouped;
+    }
+
+    let filter_lowers: Vec<String> = filters.iter().map(|f| f.to_lowercase()).collect();
+
+    let mut filtered = BTreeMap::new();
     for (provider, models) in grouped {
         let filtered_models: Vec<Model> = models
             .into_iter()
-            .filter(|model| {
-                filter_lowers.iter().any(|filter_lower| {
-                    model.id.to_lowercase().contains(filter_lower)
-                        || model
-                            .name
-                            .as_ref()
-                            .is_some_and(|name| name.to_lowercase().contains(filter_lower))
-                })
-            })
+            .filter(|model| matches_name_filters(model, &filter_lowers))
             .collect();
 
         if !filtered_models.is_empty() {
@@ -226,15 +1851,29 @@ fn parse_sort_option(sort_str: Option<String>) -> anyhow::Result<Option<(SortBy,
     }
 }
 
-fn format_price_per_million(price_str: &str) -> String {
+/// Header for a per-token price column, naming the unit when --per changes it from the
+/// usual per 1M tokens
+fn price_header(label: &str) -> String {
+    match table::price_unit() {
+        PriceUnit::Million => label.to_string(),
+        unit => format!("{label}/{}", unit.short()),
+    }
+}
+
+fn format_price_per_unit(price_str: &str) -> String {
     if let Ok(price) = price_str.parse::<f64>() {
-        format!("{:.2}", price * 1_000_000.0)
+        table::format_price(price)
     } else {
         "N/A".to_string()
     }
 }
 
+/// Sort by provider, then model ID, and then by the chosen sort if any. The sort is stable,
+/// so models that tie on the chosen sort keep the provider and ID order.
 fn sort_models(mut models: Vec<Model>, sort_option: Option<(SortBy, bool)>) -> Vec<Model> {
+    let provider = |model: &Model| model.id.split('/').next().unwrap_or("unknown").to_string();
+    models.sort_by(|a, b| provider(a).cmp(&provider(b)).then_with(|| a.id.cmp(&b.id)));
+
     if let Some((sort_by, reverse)) = sort_option {
         models.sort_by(|a, b| {
             let ordering = match sort_by {
@@ -276,122 +1915,627 @@ fn parse_price(price_str: &str) -> anyhow::Result<f64> {
         .map_err(|e| anyhow::anyhow!("Invalid price format: {}", e))
 }
 
-struct TableRow {
+/// One billed component of a request: how many tokens and the effective per-token rate applied
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct CostComponent {
+    tokens: u64,
+    rate: f64,
+    cost: f64,
+}
+
+impl CostComponent {
+    fn new(tokens: u64, rate: f64) -> Self {
+        Self {
+            tokens,
+            rate,
+            cost: (tokens as f64) * rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CalcRow {
     model: String,
-    input: String,
-    output: String,
-    cache_read: String,
-    cache_write: String,
+    input: CostComponent,
+    output: CostComponent,
+    cache_read: CostComponent,
+    cache_write: CostComponent,
+    /// Multiplier applied to the input price for cache writes, when cache writes were billed
+    cache_write_multiplier: Option<f64>,
+    total_cost: f64,
 }
 
-fn print_default_format(grouped: &HashMap<String, Vec<Model>>) {
-    let mut rows = Vec::new();
+fn cache_write_multiplier(ttl: u64) -> anyhow::Result<f64> {
+    match ttl {
+        5 => Ok(1.25), // 5-minute TTL is 1.25x base price
+        60 => Ok(2.0), // 1-hour TTL is 2x base price
+        _ => Err(anyhow::anyhow!(
+            "Invalid TTL: {} minutes. TTL must be exactly 5 or 60 minutes",
+            ttl
+        )),
+    }
+}
 
-    for models in grouped.values() {
-        for model in models {
-            let input_price = format_price_per_million(&model.pricing.prompt);
-            let output_price = format_price_per_million(&model.pricing.completion);
+fn calculate_cost(
+    model: &Model,
+    input: u64,
+    output: u64,
+    cached: Option<u64>,
+    ttl: u64,
+) -> anyhow::Result<CalcRow> {
+    let use_caching = cached.is_some();
+    let cached_tokens = cached.unwrap_or(0);
 
-            let cache_read = model
-                .pricing
-                .input_cache_read
-                .as_ref()
-                .map(|p| format_price_per_million(p))
-                .unwrap_or_else(|| "N/A".to_string());
-            let cache_write = model
-                .pricing
-                .input_cache_write
-                .as_ref()
-                .map(|p| format_price_per_million(p))
-                .unwrap_or_else(|| "N/A".to_string());
+    let input_price = parse_price(&model.pricing.prompt)?;
+    let output_price = parse_price(&model.pricing.completion)?;
+
+    // cached_tokens = tokens read from cache
+    // new_tokens = tokens not in cache that need to be written to cache
+    let new_tokens = input.saturating_sub(cached_tokens);
+
+    let output_component = CostComponent::new(output, output_price);
+
+    let mut cache_read = CostComponent::default();
+    let mut cache_write = CostComponent::default();
+    let mut input_component = CostComponent::default();
+    let mut write_multiplier = None;
 
-            rows.push(TableRow {
-                model: model.id.clone(),
-                input: input_price,
-                output: output_price,
-                cache_read,
-                cache_write,
-            });
+    if cached_tokens > 0 {
+        // Cost for reading cached tokens
+        let cache_read_price = match &model.pricing.input_cache_read {
+            Some(cache_read_price_str) => parse_price(cache_read_price_str)?,
+            None => input_price,
+        };
+        cache_read = CostComponent::new(cached_tokens, cache_read_price);
+    }
+
+    if new_tokens > 0 {
+        if use_caching && model.pricing.input_cache_write.is_some() {
+            // Cost for writing new tokens to cache (replaces regular input cost for these tokens)
+            let multiplier = cache_write_multiplier(ttl)?;
+            cache_write = CostComponent::new(new_tokens, input_price * multiplier);
+            write_multiplier = Some(multiplier);
+        } else {
+            // Regular input cost for tokens (no caching or can't be cached)
+            input_component = CostComponent::new(new_tokens, input_price);
         }
     }
 
-    if rows.is_empty() {
-        return;
+    let total_cost =
+        input_component.cost + output_component.cost + cache_read.cost + cache_write.cost;
+
+    Ok(CalcRow {
+        model: model.id.clone(),
+        input: input_component,
+        output: output_component,
+        cache_read,
+        cache_write,
+        cache_write_multiplier: write_multiplier,
+        total_cost,
+    })
+}
+
+fn ttl_label(ttl: u64) -> String {
+    match ttl {
+        60 => "1h".to_string(),
+        _ => format!("{ttl}m"),
     }
+}
 
-    // Calculate column widths
-    let max_model_width = rows.iter().map(|r| r.model.len()).max().unwrap_or(0).max(5);
-    let max_input_width = rows.iter().map(|r| r.input.len()).max().unwrap_or(0).max(5);
-    let max_output_width = rows
-        .iter()
-        .map(|r| r.output.len())
-        .max()
-        .unwrap_or(0)
-        .max(6);
-    let max_cache_read_width = rows
-        .iter()
-        .map(|r| r.cache_read.len())
-        .max()
-        .unwrap_or(0)
-        .max(10);
-    let max_cache_write_width = rows
+/// The rate and cost of each billed component behind each model's total, for --show-rates.
+/// Notes only appear when some model's cache writes cost more than its input.
+fn rates_table(calc_rows: &[CalcRow], ttl: u64) -> Table {
+    let noted = calc_rows
         .iter()
-        .map(|r| r.cache_write.len())
-        .max()
-        .unwrap_or(0)
-        .max(11);
-
-    // Print header
-    println!(
-        "{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_read$} | {:<width_write$}",
-        "Model",
-        "Input",
-        "Output",
-        "Cache Read",
-        "Cache Write",
-        width_model = max_model_width,
-        width_input = max_input_width,
-        width_output = max_output_width,
-        width_read = max_cache_read_width,
-        width_write = max_cache_write_width,
-    );
+        .any(|row| row.cache_write.tokens > 0 && row.cache_write_multiplier.is_some());
+    let mut headers = vec![
+        i18n::t("col-model"),
+        i18n::t("col-item"),
+        i18n::t("col-tokens"),
+        price_header(&i18n::t("col-rate")),
+        i18n::t("col-cost"),
+    ];
+    if noted {
+        headers.push(i18n::t("col-notes"));
+    }
+    let mut table = Table::new(headers);
+    for row in calc_rows {
+        let cache_write_note = row.cache_write_multiplier.map(|multiplier| {
+            i18n::t_args(
+                "rate-cache-write-note",
+                &[("multiplier", multiplier.to_string().into()), ("ttl", ttl_label(ttl).into())],
+            )
+        });
+        let components = [
+            ("col-input", &row.input, None),
+            ("col-output", &row.output, None),
+            ("col-cache-read", &row.cache_read, None),
+            ("col-cache-write", &row.cache_write, cache_write_note),
+        ];
+        for (label, component, note) in components {
+            if component.tokens == 0 {
+                continue;
+            }
+            let mut cells = vec![
+                model_link(&row.model),
+                Cell::text(i18n::t(label)),
+                Cell::Number(component.tokens as f64, 0),
+                Cell::Price(component.rate),
+                Cell::Cost(component.cost),
+            ];
+            if noted {
+                cells.push(Cell::text(note.unwrap_or_default()));
+            }
+            table.push(cells);
+        }
+    }
+    table
+}
+
+/// A number of cached input tokens, either absolute or as a percentage of the input tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CachedTokens {
+    Count(u64),
+    Percent(f64),
+}
+
+impl CachedTokens {
+    fn resolve(self, input: u64) -> u64 {
+        match self {
+            CachedTokens::Count(count) => count,
+            CachedTokens::Percent(percent) => ((input as f64) * percent / 100.0).round() as u64,
+        }
+    }
+}
+
+impl FromStr for CachedTokens {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        if let Some(percent_str) = s.trim().strip_suffix('%') {
+            let percent = percent_str
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| anyhow::anyhow!("Invalid cached percentage: '{}'", s))?;
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(anyhow::anyhow!(
+                    "Cached percentage must be between 0% and 100%, got '{}'",
+                    s
+                ));
+            }
+            Ok(CachedTokens::Percent(percent))
+        } else {
+            s.trim()
+                .parse::<u64>()
+                .map(CachedTokens::Count)
+                .map_err(|_| anyhow::anyhow!("Invalid cached token count: '{}'", s))
+        }
+    }
+}
+
+impl fmt::Display for CachedTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CachedTokens::Count(count) => write!(f, "{count}"),
+            CachedTokens::Percent(percent) => write!(f, "{percent}%"),
+        }
+    }
+}
+
+/// One combination of calc parameters produced by --sweep
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scenario {
+    ttl: u64,
+    cached: Option<CachedTokens>,
+}
+
+impl Scenario {
+    fn ttl_desc(&self) -> String {
+        // TTL only matters when caching rules are in effect
+        match self.cached {
+            Some(_) => ttl_label(self.ttl),
+            None => "-".to_string(),
+        }
+    }
+
+    fn cached_desc(&self, input: u64) -> String {
+        match self.cached {
+            None => i18n::t("cached-none"),
+            Some(cached @ CachedTokens::Count(_)) => cached.to_string(),
+            Some(cached @ CachedTokens::Percent(_)) => {
+                format!("{} ({})", cached, cached.resolve(input))
+            }
+        }
+    }
+}
+
+fn parse_ttl(ttl_str: &str) -> anyhow::Result<u64> {
+    match ttl_str.trim().parse::<u64>() {
+        Ok(ttl @ (5 | 60)) => Ok(ttl),
+        _ => Err(anyhow::anyhow!(
+            "Invalid TTL: '{}'. TTL must be exactly 5 or 60 minutes",
+            ttl_str
+        )),
+    }
+}
+
+fn expand_sweeps(sweeps: &[String], base: Scenario) -> anyhow::Result<Vec<Scenario>> {
+    let mut scenarios = vec![base];
+
+    for sweep in sweeps {
+        let (param, values) = sweep.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Invalid sweep: '{}'. Expected PARAM=VALUES (e.g., 'ttl=5,60')", sweep)
+        })?;
+        let values: Vec<&str> = values.split(',').collect();
+
+        scenarios = match param.trim() {
+            "ttl" => {
+                let ttls = values
+                    .iter()
+                    .map(|v| parse_ttl(v))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                scenarios
+                    .iter()
+                    .flat_map(|s| ttls.iter().map(move |&ttl| Scenario { ttl, ..*s }))
+                    .collect()
+            }
+            "cached" => {
+                let cached_values = values
+                    .iter()
+                    .map(|v| match v.trim() {
+                        "none" => Ok(None),
+                        v => v.parse::<CachedTokens>().map(Some),
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                scenarios
+                    .iter()
+                    .flat_map(|s| cached_values.iter().map(move |&cached| Scenario { cached, ..*s }))
+                    .collect()
+            }
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Invalid sweep parameter: '{}'. Valid parameters are: ttl, cached",
+                    other
+                ))
+            }
+        };
+    }
 
-    // Print separator
-    println!(
-        "{:-<width_model$}-+-{:-<width_input$}-+-{:-<width_output$}-+-{:-<width_read$}-+-{:-<width_write$}",
-        "",
-        "",
-        "",
-        "",
-        "",
-        width_model = max_model_width,
-        width_input = max_input_width,
-        width_output = max_output_width,
-        width_read = max_cache_read_width,
-        width_write = max_cache_write_width,
+    // Scenarios without caching ignore the TTL, so collapse the duplicates
+    let mut seen = HashSet::new();
+    scenarios.retain(|s| seen.insert((s.ttl_desc(), s.cached.map(|c| c.to_string()))));
+
+    Ok(scenarios)
+}
+
+fn sweep_table(input: u64, scenarios: &[Scenario], sweep_rows: &[Vec<CalcRow>]) -> Table {
+    let mut table = Table::new(
+        [
+            "col-model",
+            "col-ttl",
+            "col-cached",
+            "col-input",
+            "col-output",
+            "col-cache-read",
+            "col-cache-write",
+            "col-total",
+            "col-best",
+        ]
+        .map(i18n::t),
     );
 
-    // Print rows
-    for row in rows {
-        println!(
-            "{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_read$} | {:<width_write$}",
-            row.model,
-            row.input,
-            row.output,
-            row.cache_read,
-            row.cache_write,
-            width_model = max_model_width,
-            width_input = max_input_width,
-            width_output = max_output_width,
-            width_read = max_cache_read_width,
-            width_write = max_cache_write_width,
+    for model_rows in sweep_rows {
+        let cheapest = model_rows
+            .iter()
+            .map(|r| r.total_cost)
+            .fold(f64::INFINITY, f64::min);
+
+        for (scenario, row) in scenarios.iter().zip(model_rows) {
+            table.push(vec![
+                model_link(&row.model),
+                Cell::text(scenario.ttl_desc()),
+                Cell::text(scenario.cached_desc(input)),
+                Cell::Cost(row.input.cost),
+                Cell::Cost(row.output.cost),
+                Cell::Cost(row.cache_read.cost),
+                Cell::Cost(row.cache_write.cost),
+                Cell::Cost(row.total_cost),
+                Cell::text(if row.total_cost == cheapest { "*" } else { "" }),
+            ]);
+        }
+    }
+
+    table
+}
+
+/// The default columns, plus the optional ones that apply to these models
+fn default_columns(models: &[&Model], metadata: &Metadata, options: &TableOptions) -> Vec<Column> {
+    let mut columns = vec![Column::Model];
+    columns.extend(options.group_column);
+    columns.extend([
+        Column::Input,
+        Column::Output,
+        Column::CacheRead,
+        Column::CacheWrite,
+    ]);
+    if options.show_trend {
+        columns.insert(columns.len() - 3, Column::Trend);
+    }
+    if options.wide {
+        columns.extend([
+            Column::Context,
+            Column::MaxCompletion,
+            Column::Created,
+            Column::Modality,
+        ]);
+    }
+    if options.show_license {
+        columns.push(Column::License);
+    }
+    if options.show_hugging_face {
+        columns.extend([Column::Params, Column::Family, Column::Released]);
+    }
+    if options.show_region {
+        columns.push(Column::Region);
+    }
+    if options.gpu.is_some() {
+        columns.extend([Column::TokensPerDollar, Column::GpuHours, Column::SelfHost]);
+    }
+    // Only show the status column when something visible has lifecycle data
+    if models.iter().any(|model| metadata.lifecycle.contains_key(&model.id)) {
+        columns.push(Column::Status);
+    }
+    if models.iter().any(|model| metadata.approval(model).is_some()) {
+        columns.push(Column::Approval);
+    }
+    if models.iter().any(|model| metadata.note(model).is_some()) {
+        columns.push(Column::Notes);
+    }
+    columns
+}
+
+/// Longest provider prefix kept by --compact
+const COMPACT_PROVIDER_LENGTH: usize = 4;
+
+/// Shorten a model ID's provider prefix, e.g. 'anthropic/claude-sonnet-4' to
+/// 'anth/claude-sonnet-4'
+fn abbreviate_provider(id: &str) -> String {
+    match id.split_once('/') {
+        Some((provider, model)) => {
+            let provider: String = provider.chars().take(COMPACT_PROVIDER_LENGTH).collect();
+            format!("{provider}/{model}")
+        }
+        None => id.to_string(),
+    }
+}
+
+fn model_table(models: &[&Model], metadata: &Metadata, options: &TableOptions) -> Table {
+    let columns = match &options.columns {
+        Some(columns) => columns.clone(),
+        None => default_columns(models, metadata, options),
+    };
+
+    let mut table = Table::new(columns.iter().map(|column| column.header(options.gpu.as_ref())));
+    for model in models {
+        table.push(
+            columns
+                .iter()
+                .map(|column| match column {
+                    Column::Model if options.compact => Cell::Link {
+                        text: abbreviate_provider(&model.id),
+                        url: format!("{MODEL_PAGE_URL}{}", model.id),
+                    },
+                    _ => column.cell(model, metadata, options.gpu.as_ref()),
+                })
+                .collect(),
         );
     }
+    if options.summary {
+        table.add_summary();
+    }
+
+    table
 }
 
-fn print_verbose_format(grouped: &HashMap<String, Vec<Model>>) {
-    for (provider, models) in grouped {
-        println!("\n=== {} ===", provider.to_uppercase());
+/// Print a table as a Markdown section, with the note and pricing source below it. For
+/// github-summary output the section goes to the job summary, and the row count to the `rows`
+/// step output.
+fn print_markdown(
+    heading: &str,
+    table: &Table,
+    note: Option<&str>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut section = format!("### {heading}\n\n{}", table.to_markdown());
+    if let Some(note) = note {
+        section.push_str(&format!("\n{note}\n"));
+    }
+    section.push_str(&format!("\n_{}_\n", prices_from(source, stale_after)));
+
+    if format == OutputFormat::GithubSummary {
+        github::summary(&section)?;
+        github::set_output("rows", table.rows.len())?;
+    } else {
+        print!("{section}");
+    }
+    Ok(())
+}
+
+/// Where prices came from, for the line above or below a table
+fn prices_from(source: &SourceInfo, stale_after: TimeDelta) -> String {
+    i18n::t_args("prices-from", &[("source", source.describe(stale_after).into())])
+}
+
+/// Print a heading above a table, unless --quiet asks for rows only
+fn print_heading(heading: &str) {
+    if !terminal::is_quiet() {
+        println!("{heading}");
+        println!();
+    }
+}
+
+/// Print a note below a table, unless --quiet asks for rows only
+fn print_note(note: &str) {
+    if !terminal::is_quiet() {
+        println!();
+        println!("{note}");
+    }
+}
+
+/// Print a table under a heading, with an optional note, for commands that only have
+/// tabular output
+fn print_titled_table(
+    heading: &str,
+    table: &Table,
+    note: Option<&str>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            return Err(anyhow::anyhow!(
+                "This command supports table, csv, html and markdown output"
+            ))
+        }
+        OutputFormat::Csv => table.print_csv(),
+        OutputFormat::Xlsx => xlsx::write(&[(heading, table)])?,
+        OutputFormat::Markdown | OutputFormat::GithubSummary => {
+            print_markdown(heading, table, note, format, source, stale_after)?
+        }
+        OutputFormat::Html => {
+            let intro = [prices_from(source, stale_after)];
+            print!("{}", html::page(heading, &intro, table, note));
+        }
+        OutputFormat::Table => {
+            print_heading(heading);
+            table.print();
+            if let Some(note) = note {
+                print_note(note);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_list(
+    grouped: &BTreeMap<String, Vec<Model>>,
+    verbose: bool,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+    metadata: &Metadata,
+    table_options: &TableOptions,
+) -> anyhow::Result<()> {
+    let models: Vec<&Model> = grouped.values().flatten().collect();
+
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            output::print_list_json(&models, format, source, stale_after, metadata)?
+        }
+        OutputFormat::Csv => model_table(&models, metadata, table_options).print_csv(),
+        OutputFormat::Xlsx => {
+            let tables: Vec<(&str, Table)> = grouped
+                .iter()
+                .map(|(group, models)| {
+                    let name = if group.is_empty() { "Models" } else { group.as_str() };
+                    let models: Vec<&Model> = models.iter().collect();
+                    (name, model_table(&models, metadata, table_options))
+                })
+                .collect();
+            let sheets: Vec<(&str, &Table)> =
+                tables.iter().map(|(name, table)| (*name, table)).collect();
+            xlsx::write(&sheets)?;
+        }
+        OutputFormat::Html => print!(
+            "{}",
+            html::page(
+                &i18n::t("page-title"),
+                &[i18n::t_args(
+                    "prices-per-source",
+                    &[
+                        ("unit", i18n::price_unit().into()),
+                        ("source", source.describe(stale_after).into()),
+                    ],
+                )],
+                &model_table(&models, metadata, table_options),
+                None,
+            )
+        ),
+        OutputFormat::Markdown | OutputFormat::GithubSummary => print_markdown(
+            &i18n::t("page-title"),
+            &model_table(&models, metadata, table_options),
+            Some(&i18n::t_args("prices-per", &[("unit", i18n::price_unit().into())])),
+            format,
+            source,
+            stale_after,
+        )?,
+        OutputFormat::Table if verbose => print_verbose_format(grouped, source, stale_after, metadata),
+        OutputFormat::Table => {
+            if !models.is_empty() {
+                model_table(&models, metadata, table_options).print();
+            }
+        }
+    }
+
+    if let Some(group_by) = table_options.group_stats {
+        if !models.is_empty() {
+            println!();
+            group_stats_table(grouped, group_by).print();
+        }
+    }
+    Ok(())
+}
+
+/// One row per group with its model count and the min/avg/max input and output prices
+fn group_stats_table(grouped: &BTreeMap<String, Vec<Model>>, group_by: GroupBy) -> Table {
+    let mut headers = vec![group_by.label(), i18n::t("col-models")];
+    for price in ["input", "output"] {
+        for stat in ["min", "avg", "max"] {
+            headers.push(price_header(&i18n::t(&format!("col-{stat}-{price}"))));
+        }
+    }
+
+    let mut table = Table::new(headers);
+    for (group, models) in grouped {
+        let mut cells = vec![Cell::text(group), Cell::Number(models.len() as f64, 0)];
+        let prices: [fn(&Model) -> &str; 2] =
+            [|model| &model.pricing.prompt, |model| &model.pricing.completion];
+        for price in prices {
+            let prices: Vec<f64> = models
+                .iter()
+                .filter_map(|model| price(model).parse::<f64>().ok())
+                // Routers list -1 when the price depends on the model they pick
+                .filter(|price| *price >= 0.0)
+                .collect();
+            if prices.is_empty() {
+                cells.extend([Cell::Missing, Cell::Missing, Cell::Missing]);
+                continue;
+            }
+            let min = prices.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let avg = prices.iter().sum::<f64>() / prices.len() as f64;
+            cells.extend([Cell::Price(min), Cell::Price(avg), Cell::Price(max)]);
+        }
+        table.push(cells);
+    }
+    table
+}
+
+fn print_verbose_format(
+    grouped: &BTreeMap<String, Vec<Model>>,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+    metadata: &Metadata,
+) {
+    let source_desc = source.describe(stale_after);
+
+    for (group, models) in grouped {
+        if !group.is_empty() {
+            println!("\n=== {} ===", group.to_uppercase());
+        }
 
         for model in models {
             println!("\nModel: {}", model.id);
@@ -404,26 +2548,46 @@ fn print_verbose_format(grouped: &HashMap<String, Vec<Model>>) {
                 println!("  Description: {description}");
             }
 
+            println!("  License: {}", metadata.license(model));
+
+            if let Some(entry) = metadata.lifecycle.get(&model.id) {
+                println!("  Lifecycle: {}", entry.describe(metadata.today));
+            }
+
+            if let Some(status) = metadata.approval(model) {
+                println!("  Approval: {}", status.as_str());
+            }
+
+            if let Some(note) = metadata.note(model) {
+                println!("  Notes: {note}");
+            }
+
+            let unit = table::price_unit();
             println!("  Pricing:");
+            println!("    Source: {source_desc}");
             println!(
-                "    Input: ${} per 1M tokens",
-                format_price_per_million(&model.pricing.prompt)
+                "    Input: ${} per {}",
+                format_price_per_unit(&model.pricing.prompt),
+                unit.describe()
             );
             println!(
-                "    Output: ${} per 1M tokens",
-                format_price_per_million(&model.pricing.completion)
+                "    Output: ${} per {}",
+                format_price_per_unit(&model.pricing.completion),
+                unit.describe()
             );
 
             if let Some(cache_read) = &model.pricing.input_cache_read {
                 println!(
-                    "    Cache Read: ${} per 1M tokens",
-                    format_price_per_million(cache_read)
+                    "    Cache Read: ${} per {}",
+                    format_price_per_unit(cache_read),
+                    unit.describe()
                 );
             }
             if let Some(cache_write) = &model.pricing.input_cache_write {
                 println!(
-                    "    Cache Write: ${} per 1M tokens",
-                    format_price_per_million(cache_write)
+                    "    Cache Write: ${} per {}",
+                    format_price_per_unit(cache_write),
+                    unit.describe()
                 );
             }
 
@@ -436,7 +2600,7 @@ fn print_verbose_format(grouped: &HashMap<String, Vec<Model>>) {
             }
 
             if let Some(context_length) = model.context_length {
-                println!("  Context Length: {context_length} tokens");
+                println!("  Context Length: {} tokens", table::format_number(context_length as f64, 0));
             }
 
             if let Some(arch) = &model.architecture {
@@ -451,9 +2615,24 @@ fn print_verbose_format(grouped: &HashMap<String, Vec<Model>>) {
                 }
             }
 
+            if let Some(info) = metadata.hugging_face(model) {
+                if let Some(params) = info.params {
+                    println!("  Parameters: {}", huggingface::format_params(params));
+                }
+                if let Some(family) = &info.family {
+                    println!("  Family: {family}");
+                }
+                if let Some(released) = info.released {
+                    println!("  Released: {released}");
+                }
+            }
+
             if let Some(top_provider) = &model.top_provider {
                 if let Some(max_completion) = top_provider.max_completion_tokens {
-                    println!("  Max Completion Tokens: {max_completion}");
+                    println!(
+                        "  Max Completion Tokens: {}",
+                        table::format_number(max_completion as f64, 0)
+                    );
                 }
                 if let Some(is_moderated) = top_provider.is_moderated {
                     println!("  Moderated: {is_moderated}");
@@ -463,12 +2642,478 @@ fn print_verbose_format(grouped: &HashMap<String, Vec<Model>>) {
     }
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-    
-    // Parse sort option and handle reverse flag
-    let sort_option = parse_sort_option(args.sort)?;
+/// Every field of one model as a two-column table, including the prices and limits the model
+/// table has no column for
+fn model_detail_table(model: &Model, metadata: &Metadata) -> Table {
+    let unit = table::price_unit().describe();
+    let per_token = |price: &str| format!("${} per {unit}", format_price_per_unit(price));
+    let tokens = |tokens: u64| format!("{} tokens", table::format_number(tokens as f64, 0));
+    let list = |values: &[String]| if values.is_empty() { "none".to_string() } else { values.join(", ") };
+
+    let mut rows: Vec<(&str, Cell)> = vec![("Model", model_link(&model.id))];
+    let mut push = |field: &'static str, value: String| rows.push((field, Cell::text(value)));
+    if let Some(name) = &model.name {
+        push("Name", name.clone());
+    }
+    if let Some(slug) = model.canonical_slug.as_ref().filter(|slug| **slug != model.id) {
+        push("Canonical Slug", slug.clone());
+    }
+    push("Provider", model.id.split('/').next().unwrap_or("unknown").to_string());
+    if let Some(description) = &model.description {
+        push("Description", description.clone());
+    }
+    if let Some(created) = model.created.and_then(|created| DateTime::from_timestamp(created as i64, 0)) {
+        push("Created", created.format("%Y-%m-%d").to_string());
+    }
+    push("License", metadata.license(model).to_string());
+    if let Some(id) = &model.hugging_face_id {
+        push("Hugging Face", id.clone());
+    }
+    if let Some(entry) = metadata.lifecycle.get(&model.id) {
+        push("Lifecycle", entry.describe(metadata.today));
+    }
+    if let Some(status) = metadata.approval(model) {
+        push("Approval", status.as_str().to_string());
+    }
+    if let Some(note) = metadata.note(model) {
+        push("Notes", note.to_string());
+    }
+    let tags = metadata.tags.of(model);
+    if !tags.is_empty() {
+        push("Tags", tags.join(", "));
+    }
+
+    let pricing = &model.pricing;
+    push("Input", per_token(&pricing.prompt));
+    push("Output", per_token(&pricing.completion));
+    if let Some(price) = &pricing.input_cache_read {
+        push("Cache Read", per_token(price));
+    }
+    if let Some(price) = &pricing.input_cache_write {
+        push("Cache Write", per_token(price));
+    }
+    if let Some(price) = &pricing.internal_reasoning {
+        push("Internal Reasoning", per_token(price));
+    }
+    if let Some(price) = &pricing.request {
+        push("Per Request", format!("${price}"));
+    }
+    if let Some(price) = &pricing.image {
+        push("Image", format!("${price} per image"));
+    }
+    if let Some(price) = &pricing.web_search {
+        push("Web Search", format!("${price} per search"));
+    }
+
+    if let Some(context_length) = model.context_length {
+        push("Context Length", tokens(context_length));
+    }
+    if let Some(top_provider) = &model.top_provider {
+        if let Some(context_length) = top_provider.context_length.filter(|length| Some(*length) != model.context_length) {
+            push("Top Provider Context", tokens(context_length));
+        }
+        if let Some(max_completion) = top_provider.max_completion_tokens {
+            push("Max Completion Tokens", tokens(max_completion));
+        }
+        if let Some(is_moderated) = top_provider.is_moderated {
+            push("Moderated", is_moderated.to_string());
+        }
+    }
+    if let Some(limits) = &model.per_request_limits {
+        if let Some(prompt_tokens) = &limits.prompt_tokens {
+            push("Request Limit (Input)", format!("{prompt_tokens} tokens"));
+        }
+        if let Some(completion_tokens) = &limits.completion_tokens {
+            push("Request Limit (Output)", format!("{completion_tokens} tokens"));
+        }
+    }
+
+    if let Some(arch) = &model.architecture {
+        if let Some(modality) = &arch.modality {
+            push("Modality", modality.clone());
+        }
+        if let Some(modalities) = &arch.input_modalities {
+            push("Input Modalities", list(modalities));
+        }
+        if let Some(modalities) = &arch.output_modalities {
+            push("Output Modalities", list(modalities));
+        }
+        if let Some(tokenizer) = &arch.tokenizer {
+            push("Tokenizer", tokenizer.clone());
+        }
+        if let Some(instruct_type) = &arch.instruct_type {
+            push("Instruct Type", instruct_type.clone());
+        }
+    }
+    if let Some(info) = metadata.hugging_face(model) {
+        if let Some(params) = info.params {
+            push("Parameters", huggingface::format_params(params));
+        }
+        if let Some(family) = &info.family {
+            push("Family", family.clone());
+        }
+        if let Some(released) = info.released {
+            push("Released", released.to_string());
+        }
+    }
+    if let Some(parameters) = &model.supported_parameters {
+        push("Supported Parameters", list(parameters));
+    }
+
+    let mut table = Table::new(["Field", "Value"]);
+    for (field, value) in rows {
+        table.push(vec![Cell::text(field), value]);
+    }
+    table
+}
+
+/// Rows of the compare table
+const COMPARE_FIELDS: [&str; 15] = [
+    "Input",
+    "Output",
+    "Cache Read",
+    "Cache Write",
+    "Internal Reasoning",
+    "Per Request",
+    "Image",
+    "Web Search",
+    "Context",
+    "Max Completion",
+    "Modality",
+    "Input Modalities",
+    "Output Modalities",
+    "Tokenizer",
+    "License",
+];
+
+/// One model's column of the compare table, in the order of COMPARE_FIELDS
+fn compare_cells(model: &Model, metadata: &Metadata) -> [Cell; COMPARE_FIELDS.len()] {
+    let price = |price: Option<&String>| price.map_or(Cell::Missing, |price| Cell::price_str(price));
+    let cost = |price: Option<&String>| {
+        price
+            .and_then(|price| price.parse::<f64>().ok())
+            .map_or(Cell::Missing, Cell::Cost)
+    };
+    let tokens = |tokens: Option<u64>| tokens.map_or(Cell::Missing, |tokens| Cell::Number(tokens as f64, 0));
+    let arch = model.architecture.as_ref();
+    let text = |value: Option<&String>| value.map_or(Cell::Missing, Cell::text);
+    let list = |values: Option<&Vec<String>>| values.map_or(Cell::Missing, |values| Cell::text(values.join(", ")));
+    let pricing = &model.pricing;
+    [
+        price(Some(&pricing.prompt)),
+        price(Some(&pricing.completion)),
+        price(pricing.input_cache_read.as_ref()),
+        price(pricing.input_cache_write.as_ref()),
+        price(pricing.internal_reasoning.as_ref()),
+        cost(pricing.request.as_ref()),
+        cost(pricing.image.as_ref()),
+        cost(pricing.web_search.as_ref()),
+        tokens(model.context_length),
+        tokens(model.top_provider.as_ref().and_then(|top| top.max_completion_tokens)),
+        text(arch.and_then(|arch| arch.modality.as_ref())),
+        list(arch.and_then(|arch| arch.input_modalities.as_ref())),
+        list(arch.and_then(|arch| arch.output_modalities.as_ref())),
+        text(arch.and_then(|arch| arch.tokenizer.as_ref())),
+        Cell::text(metadata.license(model).to_string()),
+    ]
+}
+
+/// Models as columns and their prices, limits and architecture as rows. Each model after the
+/// first gets a column with the percentage change of its numeric values from the first model's.
+fn compare_table(models: &[&Model], metadata: &Metadata) -> Table {
+    let columns: Vec<_> = models.iter().map(|model| compare_cells(model, metadata)).collect();
+    let mut headers = vec!["Field".to_string()];
+    for (i, model) in models.iter().enumerate() {
+        headers.push(model.id.clone());
+        if i > 0 {
+            headers.push("Change %".to_string());
+        }
+    }
+    let mut table = Table::new(headers);
+    for (row_index, field) in COMPARE_FIELDS.iter().enumerate() {
+        let cells: Vec<&Cell> = columns.iter().map(|column| &column[row_index]).collect();
+        // Leave out prices none of the models charge, such as web search on most models
+        if cells.iter().all(|cell| matches!(cell, Cell::Missing)) {
+            continue;
+        }
+        let base = match cells[0] {
+            Cell::Price(value) | Cell::Cost(value) | Cell::Number(value, _) if *value > 0.0 => Some(*value),
+            _ => None,
+        };
+        // Text rows such as the modality have nothing to take a percentage of
+        let numeric = cells
+            .iter()
+            .any(|cell| matches!(cell, Cell::Price(_) | Cell::Cost(_) | Cell::Number(..)));
+        let mut row = vec![Cell::text(*field)];
+        for (i, cell) in cells.into_iter().enumerate() {
+            let change = match (cell, base) {
+                (Cell::Price(value) | Cell::Cost(value) | Cell::Number(value, _), Some(base)) => {
+                    Cell::Number((value - base) / base * 100.0, 1)
+                }
+                _ if numeric => Cell::Missing,
+                _ => Cell::text(""),
+            };
+            row.push(cell.clone());
+            if i > 0 {
+                row.push(change);
+            }
+        }
+        table.push(row);
+    }
+    table
+}
+
+fn parse_query_args(query_args: &[String]) -> anyhow::Result<Args> {
+    let args = Args::try_parse_from(std::iter::once("llm-pricing".to_string()).chain(query_args.iter().cloned()))?;
+    if matches!(
+        args.command,
+        Some(Commands::SaveQuery { .. } | Commands::Run { .. } | Commands::Doctor | Commands::Init | Commands::Auth { .. })
+    ) {
+        return Err(anyhow::anyhow!(
+            "Saved queries cannot run 'save-query', 'run', 'doctor', 'init' or 'auth'"
+        ));
+    }
+    #[cfg(feature = "self-update")]
+    if matches!(args.command, Some(Commands::SelfUpdate { .. })) {
+        return Err(anyhow::anyhow!("Saved queries cannot run 'self-update'"));
+    }
+    Ok(args)
+}
+
+fn save_query(name: &str, query_args: &[String]) -> anyhow::Result<()> {
+    // Validate up front so a typo fails now rather than when the query is run
+    parse_query_args(query_args)?;
+
+    let mut config = Config::load()?;
+    config.queries.insert(name.to_string(), query_args.to_vec());
+    let path = config.save()?;
+    println!("Saved query '{}' to {}", name, path.display());
+    Ok(())
+}
+
+fn list_queries() -> anyhow::Result<()> {
+    let config = Config::load()?;
+    if config.queries.is_empty() {
+        println!("No saved queries. Create one with 'llm-pricing save-query <NAME> -- <ARGS>...'");
+        return Ok(());
+    }
+
+    let mut table = Table::new(["Name", "Arguments"]);
+    for (name, query_args) in &config.queries {
+        table.push(vec![Cell::text(name), Cell::text(query_args.join(" "))]);
+    }
+    table.print();
+    Ok(())
+}
+
+fn set_approval(model: &str, status: Option<Approval>) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    match status {
+        Some(status) => {
+            config.approvals.insert(model.to_string(), status);
+            let path = config.save()?;
+            println!("Marked '{}' as {} in {}", model, status.as_str(), path.display());
+        }
+        None => {
+            if config.approvals.remove(model).is_none() {
+                println!("'{model}' has no approval status");
+                return Ok(());
+            }
+            let path = config.save()?;
+            println!("Cleared approval status of '{}' in {}", model, path.display());
+        }
+    }
+    Ok(())
+}
+
+fn list_approvals() -> anyhow::Result<()> {
+    let config = Config::load()?;
+    if config.approvals.is_empty() {
+        println!("No approvals recorded. Set one with 'llm-pricing approve <MODEL> [--status <STATUS>]'");
+        return Ok(());
+    }
+
+    let mut table = Table::new(["Model", "Status"]);
+    for (model, status) in &config.approvals {
+        table.push(vec![Cell::text(model), Cell::text(status.as_str())]);
+    }
+    table.print();
+    Ok(())
+}
+
+/// Flags typed alongside 'run <NAME>', which apply on top of the saved query
+fn run_overrides(name: &str) -> Vec<String> {
+    let typed: Vec<String> = std::env::args().skip(1).collect();
+    match typed.windows(2).position(|pair| pair[0] == "run" && pair[1] == name) {
+        Some(i) => typed[..i].iter().chain(&typed[i + 2..]).cloned().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The saved query's arguments followed by any flags typed with 'run', which take precedence
+fn query_argv(config: &Config, name: &str) -> anyhow::Result<Vec<String>> {
+    let query_args = config.queries.get(name).ok_or_else(|| {
+        anyhow::anyhow!("No saved query named '{}'. Use 'llm-pricing run' to list saved queries", name)
+    })?;
+    Ok(query_args.iter().cloned().chain(run_overrides(name)).collect())
+}
+
+fn resolve_query(name: &str) -> anyhow::Result<Args> {
+    parse_query_args(&query_argv(&Config::load()?, name)?)
+}
+
+/// Exit status when no models match the filters
+const EXIT_NO_MATCH: i32 = 2;
+/// Exit status when pricing data or another API couldn't be reached
+const EXIT_NETWORK: i32 = 3;
+
+/// Exit with a distinct status when nothing matched, so scripts can tell it apart from errors
+fn exit_no_match(hint: bool) -> ! {
+    eprintln!("No models found matching the filter");
+    if hint {
+        eprintln!("Use 'llm-pricing list' to see available models");
+    }
+    capture::exit(EXIT_NO_MATCH);
+}
+
+#[tokio::main]
+async fn main() {
+    let result = run().await;
+    // Held-back output belongs before any error, as if it had been printed all along
+    capture::finish();
+    if let Err(e) = result {
+        eprintln!("Error: {e:?}");
+        let network = e
+            .chain()
+            .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some());
+        std::process::exit(if network { EXIT_NETWORK } else { 1 });
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let mut args = Args::parse();
+    // Set before anything prints, including the setup questions
+    if let Some(lang) = args.lang {
+        i18n::set_lang(lang);
+    }
+
+    if args.dry_run && !matches!(args.command, Some(Commands::InstallIntegrations { .. })) {
+        return dry_run::print(&args);
+    }
+    // Resolved first so that a saved query runs any command the same way as typing it would
+    if let Some(Commands::Run { name: Some(name) }) = &args.command {
+        args = resolve_query(name)?;
+        if let Some(lang) = args.lang {
+            i18n::set_lang(lang);
+        }
+    }
+
+    match &args.command {
+        Some(Commands::SaveQuery { name, args: query_args }) => {
+            return save_query(name, query_args);
+        }
+        Some(Commands::Run { name: None }) => return list_queries(),
+        Some(Commands::Approve { model: None, .. }) => return list_approvals(),
+        Some(Commands::Approve {
+            model: Some(model),
+            status,
+            clear,
+        }) => return set_approval(model, (!clear).then_some(*status)),
+        Some(Commands::Doctor) => {
+            if !doctor::run().await? {
+                capture::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Init) => {
+            onboarding::run()?;
+            return Ok(());
+        }
+        Some(Commands::Auth { action }) => return auth::run(action),
+        Some(Commands::Account) => return account::run().await,
+        Some(Commands::Results { action: ResultsAction::List }) => return results::list(),
+        Some(Commands::Alert { action: AlertAction::List }) => return alerts::list(),
+        Some(Commands::Alert { action: AlertAction::Remove { name } }) => return alerts::remove(name),
+        Some(Commands::Alert {
+            action:
+                AlertAction::Add {
+                    name,
+                    model,
+                    input_below,
+                    input_above,
+                    output_below,
+                    output_above,
+                    change,
+                    within,
+                },
+        }) => {
+            return alerts::add(
+                name,
+                AlertRule {
+                    model: model.clone(),
+                    input_below: *input_below,
+                    input_above: *input_above,
+                    output_below: *output_below,
+                    output_above: *output_above,
+                    change_percent: change
+                        .as_deref()
+                        .map(|change| parse_percent("--change", change).map(|change| change * 100.0))
+                        .transpose()?,
+                    within: within.clone(),
+                },
+            )
+        }
+        Some(Commands::InstallIntegrations {
+            shell,
+            prompt,
+            status_line,
+            model,
+            prefix,
+        }) => {
+            return integrations::run(&integrations::Options {
+                shells: shell,
+                prompt: *prompt,
+                status_line: *status_line,
+                model,
+                prefix: prefix.as_deref(),
+                dry_run: args.dry_run,
+            })
+        }
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { check }) => return self_update::run(*check).await,
+        _ => {}
+    }
+
+    terminal::set_plain(args.plain);
+    terminal::set_quiet(args.quiet);
+    table::set_no_header(args.no_header);
+    terminal::set_no_links(args.no_links);
+    terminal::set_interactive(!args.no_interactive);
+    table::set_precision(args.precision);
+    table::set_price_unit(args.per);
+    table::set_style(args.style);
+
+    // Login scripts run warm on a terminal, where the setup questions would hold up the shell
+    let config = if onboarding::needed()? && !matches!(args.command, Some(Commands::Warm)) {
+        onboarding::run()?
+    } else {
+        Config::load()?
+    };
+    i18n::set_lang(args.lang.or(config.lang).unwrap_or_default());
+    let format = args.format.or(config.output).unwrap_or(OutputFormat::Table);
+    match (&args.out_file, format) {
+        (Some(path), OutputFormat::Xlsx) => xlsx::set_out_file(path.clone()),
+        (None, OutputFormat::Xlsx) => {
+            return Err(anyhow::anyhow!(
+                "xlsx output needs a file to write; pass --out-file <PATH>"
+            ))
+        }
+        (Some(_), _) => return Err(anyhow::anyhow!("--out-file is only used with --output xlsx")),
+        (None, _) => {}
+    }
+    
+    // Parse sort option and handle reverse flag
+    let sort_option = parse_sort_option(args.sort.clone())?;
     let final_sort_option = match sort_option {
         Some((sort_by, suffix_reverse)) => {
             // Combine suffix reverse with explicit reverse flag
@@ -477,6 +3122,9 @@ async fn main() -> anyhow::Result<()> {
         }
         None => None,
     };
+
+    let max_age = args.max_age.as_deref().map(parse_duration).transpose()?;
+    let stale_after = max_age.unwrap_or(DEFAULT_STALE_AFTER);
     
     // Validate sort option for non-calc commands
     if let Some((SortBy::Total, _)) = &final_sort_option {
@@ -485,119 +3133,853 @@ async fn main() -> anyhow::Result<()> {
         }
     }
     
-    let models = fetch_models().await?;
+    if (args.top.is_some() || args.tail.is_some())
+        && !matches!(
+            args.command,
+            None | Some(Commands::List { .. } | Commands::New { .. } | Commands::Calc { .. } | Commands::Chart { .. })
+        )
+    {
+        return Err(anyhow::anyhow!("--top and --tail can only be used with list, new, calc and chart"));
+    }
+
+    if args.group_by.is_some() && !matches!(args.command, None | Some(Commands::List { .. })) {
+        return Err(anyhow::anyhow!("--group-by can only be used with list"));
+    }
+    let group_by = args.group_by.unwrap_or(GroupBy::Provider);
+    if args.group_stats {
+        if !matches!(args.command, None | Some(Commands::List { .. })) {
+            return Err(anyhow::anyhow!("--group-stats can only be used with list"));
+        }
+        if group_by == GroupBy::None {
+            return Err(anyhow::anyhow!(
+                "--group-stats needs grouping; it can't be combined with --group-by none"
+            ));
+        }
+        if format != OutputFormat::Table {
+            return Err(anyhow::anyhow!("--group-stats is only supported with table output"));
+        }
+    }
+
+    if !args.columns.is_empty() {
+        if !matches!(
+            args.command,
+            None | Some(Commands::List { .. } | Commands::New { .. } | Commands::Publish { .. })
+        ) {
+            return Err(anyhow::anyhow!("--columns can only be used with list, new and publish"));
+        }
+        if args.gpu.is_none() && args.columns.iter().any(|column| column.needs_gpu()) {
+            return Err(anyhow::anyhow!(
+                "The tokens_per_dollar, gpu_hours and self_host columns require --gpu"
+            ));
+        }
+    }
+
+    let mut metadata = Metadata {
+        lifecycle: lifecycle::load(args.lifecycle_file.as_deref())?,
+        licenses: Licenses::bundled()?,
+        notes: config.notes.clone(),
+        approvals: config.approvals.clone(),
+        tags: Tags::bundled()?,
+        hugging_face: BTreeMap::new(),
+        input_trends: BTreeMap::new(),
+        emissions: config.carbon.clone().unwrap_or_default(),
+        regions: region::Regions::bundled(&config.regions)?,
+        residency: BTreeMap::new(),
+        today: Utc::now().date_naive(),
+    };
+    metadata.tags.check_known(&args.tag)?;
+    metadata.regions.check_known(&args.region)?;
+
+    if let Some(Commands::Warm) = args.command {
+        return warm_cache(config.cache).await;
+    }
+    let Catalog { models, source } = load_catalog(config.cache, max_age).await?;
+    if let Err(e) = check_max_age(&source, max_age) {
+        if format == OutputFormat::GithubSummary {
+            github::error("Stale pricing data", &e.to_string());
+        }
+        return Err(e);
+    }
+    if format == OutputFormat::GithubSummary && source.staleness(stale_after) == "stale" {
+        github::warning(
+            "Stale pricing data",
+            &format!("Using prices from {}", source.describe(stale_after)),
+        );
+    }
+
+    let use_hugging_face = args.hugging_face
+        || args.min_params.is_some()
+        || args.columns.iter().any(|column| column.needs_hugging_face())
+        || matches!(args.command, Some(Commands::Forecast { carbon: true, .. }));
+    if use_hugging_face {
+        metadata.hugging_face = huggingface::load(&models).await;
+    }
+    if !args.region.is_empty() || args.columns.contains(&Column::Region) {
+        // Endpoints are fetched per model, so only look up the ones that could be listed
+        let candidates: Vec<&Model> = match &args.command {
+            Some(Commands::List { why: Some(query), .. }) => vec![find_model(&models, query)?],
+            Some(Commands::List { filters, .. }) => {
+                let filter_lowers: Vec<String> = filters.iter().map(|f| f.to_lowercase()).collect();
+                models
+                    .iter()
+                    .filter(|model| {
+                        filters.is_empty() || matches_name_filters(model, &filter_lowers)
+                    })
+                    .filter(|model| why::is_included(model, &args, &metadata))
+                    .collect()
+            }
+            _ => models
+                .iter()
+                .filter(|model| why::is_included(model, &args, &metadata))
+                .collect(),
+        };
+        metadata.residency = metadata.regions.residency(&candidates).await;
+    }
+    if args.trend || args.columns.contains(&Column::Trend) {
+        metadata.input_trends = history::input_trends(args.trend_snapshots);
+        if metadata.input_trends.values().all(|prices| prices.len() < 2) {
+            eprintln!(
+                "Note: no price changes recorded yet. Each fetch adds to the history when prices \
+                 change, so trends fill in over time."
+            );
+        }
+    }
+
+    // A pager would read the same keyboard as the question of which model was meant
+    let may_ask = terminal::is_interactive() && args.command.as_ref().is_some_and(Commands::picks_model);
+    // Both hold output back until the command is done, which watch never is
+    let watching = matches!(args.command, Some(Commands::Watch { .. }));
+    if args.copy {
+        if watching {
+            return Err(anyhow::anyhow!("--copy can't be used with watch, which runs until stopped"));
+        }
+        clipboard::start()?;
+    } else if !args.no_pager && !may_ask && !watching {
+        pager::start();
+    }
+
+    if let Some(Commands::List { why: Some(query), filters, .. }) = &args.command {
+        return why::explain(&models, query, filters, &args, &metadata);
+    }
+    if let Some(Commands::Watch { interval, filters }) = &args.command {
+        return watch::run(models, source, interval, filters, &args, &metadata, format, stale_after).await;
+    }
+
+    let models: Vec<Model> = models
+        .into_iter()
+        .filter(|model| why::is_included(model, &args, &metadata))
+        .collect();
+    let table_options = TableOptions {
+        show_license: args.show_license || !args.license.is_empty(),
+        wide: args.wide,
+        compact: args.compact,
+        show_hugging_face: args.hugging_face || args.min_params.is_some(),
+        show_trend: args.trend,
+        show_region: !args.region.is_empty(),
+        gpu: args.gpu.map(|gpu| GpuAssumptions {
+            gpu,
+            hourly_rate: args.gpu_hourly_rate.unwrap_or(gpu.default_hourly_rate()),
+            throughput: args.gpu_throughput.unwrap_or(gpu.default_throughput()),
+        }),
+        columns: (!args.columns.is_empty()).then(|| args.columns.clone()),
+        summary: args.summary,
+        group_column: group_by.column(),
+        group_stats: args.group_stats.then_some(group_by),
+    };
 
     match args.command {
-        Some(Commands::List { filters, verbose }) => {
+        Some(Commands::List { filters, verbose, .. }) => {
+            let sorted_models = sort_models(models.clone(), final_sort_option);
+            let listed = limit_rows(filter_by_name(sorted_models, &filters), args.top, args.tail);
+            if listed.is_empty() {
+                exit_no_match(!filters.is_empty());
+            }
+            let grouped = group_models(listed, group_by);
+
+            print_list(&grouped, verbose, format, &source, stale_after, &metadata, &table_options)?;
+        }
+        None => {
+            // Default to list command for backward compatibility
+            let sorted_models = sort_models(models.clone(), final_sort_option);
+            let listed = limit_rows(filter_by_name(sorted_models, &args.filters), args.top, args.tail);
+            if listed.is_empty() {
+                exit_no_match(!args.filters.is_empty());
+            }
+            let grouped = group_models(listed, group_by);
+
+            print_list(&grouped, args.verbose, format, &source, stale_after, &metadata, &table_options)?;
+        }
+        Some(Commands::New { days, filters }) => {
+            let since = Utc::now() - TimeDelta::days(days.into());
+            let mut recent: Vec<Model> = filter_by_name(models, &filters)
+                .into_iter()
+                .filter(|model| {
+                    model
+                        .created
+                        .and_then(|created| DateTime::from_timestamp(created as i64, 0))
+                        .is_some_and(|created| created >= since)
+                })
+                .collect();
+            recent.sort_by(|a, b| b.created.cmp(&a.created).then_with(|| a.id.cmp(&b.id)));
+            if final_sort_option.is_some() {
+                recent = sort_models(recent, final_sort_option);
+            }
+            let listed = limit_rows(recent, args.top, args.tail);
+            if listed.is_empty() {
+                eprintln!("No models added in the last {days} days");
+                capture::exit(EXIT_NO_MATCH);
+            }
+            // One group keeps the newest-first order, and the wide columns show the creation date
+            let grouped = group_models(listed, GroupBy::None);
+            let table_options = TableOptions {
+                wide: true,
+                ..table_options
+            };
+            print_list(&grouped, false, format, &source, stale_after, &metadata, &table_options)?;
+        }
+        Some(Commands::Cheapest {
+            count,
+            by,
+            mix,
+            include_free,
+            filters,
+        }) => {
+            if count == 0 {
+                return Err(anyhow::anyhow!("--n must be at least 1"));
+            }
+            let filtered = filter_by_name(models, &filters);
+            if filtered.is_empty() {
+                exit_no_match(!filters.is_empty());
+            }
+            let models: Vec<&Model> = filtered.iter().collect();
+            let ranking = cheapest::Ranking {
+                count,
+                metric: by,
+                mix,
+                include_free,
+            };
+            cheapest::print(&models, &ranking, format, &source, stale_after)?;
+        }
+        Some(Commands::Rank {
+            benchmark,
+            mix,
+            min_score,
+            count,
+            benchmarks_file,
+            filters,
+        }) => {
+            if count == Some(0) {
+                return Err(anyhow::anyhow!("--n must be at least 1"));
+            }
+            let benchmarks = Benchmarks::load(benchmarks_file.as_deref())?;
+            let filtered = filter_by_name(models, &filters);
+            if filtered.is_empty() {
+                exit_no_match(!filters.is_empty());
+            }
+            let models: Vec<&Model> = filtered.iter().collect();
+            let ranking = rank::Ranking {
+                benchmark,
+                mix,
+                min_score,
+                count,
+            };
+            rank::print(&models, &benchmarks, &ranking, format, &source, stale_after)?;
+        }
+        Some(Commands::Stats { usage, filters }) => {
+            let usage = usage.as_deref().map(|path| invoice::read_usage(path, None)).transpose()?;
+            let grouped = filter_models(group_models_by_provider(models), filters);
+            if grouped.is_empty() {
+                exit_no_match(true);
+            }
+            stats::print(&grouped, usage.as_ref(), format, &source, stale_after)?;
+        }
+        Some(Commands::Plan {
+            requests,
+            input,
+            output,
+            keys,
+            peak_factor,
+            rate_limits_file,
+            filters,
+        }) => {
+            if requests == 0 {
+                return Err(anyhow::anyhow!("--requests must be greater than zero"));
+            }
+            if keys == 0 {
+                return Err(anyhow::anyhow!("--keys must be at least 1"));
+            }
+            if peak_factor.is_nan() || peak_factor < 1.0 {
+                return Err(anyhow::anyhow!("--peak-factor must be at least 1"));
+            }
+            let rate_limits = RateLimits::load(rate_limits_file.as_deref())?;
+
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(true);
+            }
+
+            planner::print(
+                &models,
+                &rate_limits,
+                &planner::Load {
+                    requests,
+                    input,
+                    output,
+                    keys,
+                    peak_factor,
+                },
+                format,
+                &source,
+                stale_after,
+            )?;
+        }
+        Some(Commands::Backtest { usage, strategy }) => {
+            backtest::print(&models, &usage, &strategy, format, &source, stale_after)?;
+        }
+        Some(Commands::Throughput {
+            target_rps,
+            tokens_per_req,
+            rate_limits_file,
+            filters,
+        }) => {
+            if target_rps.is_nan() || target_rps <= 0.0 {
+                return Err(anyhow::anyhow!("--target-rps must be greater than zero"));
+            }
+            let rate_limits = RateLimits::load(rate_limits_file.as_deref())?;
+
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            throughput::print(
+                &models,
+                &rate_limits,
+                target_rps,
+                tokens_per_req,
+                format,
+                &source,
+                stale_after,
+            )?;
+        }
+        Some(Commands::CompressSavings {
+            input,
+            output,
+            ratio,
+            output_risk,
+            filters,
+        }) => {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(anyhow::anyhow!("--ratio must be between 0 and 1, got {}", ratio));
+            }
+            let output_risk = parse_percent("--output-risk", &output_risk)?;
+
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            let compression = compress::Compression {
+                input,
+                output,
+                ratio,
+                output_risk,
+            };
+            compress::print(&models, &compression, format, &source, stale_after)?;
+        }
+        Some(Commands::LintData { filters }) => {
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let mut models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+            models.sort_by(|a, b| a.id.cmp(&b.id));
+
+            if lint::print(&models, format, &source, stale_after)? {
+                capture::exit(1);
+            }
+        }
+        Some(Commands::Forecast {
+            requests,
+            input,
+            output,
+            carbon,
+            filters,
+        }) => {
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            let forecast = forecast::Forecast { requests, input, output };
+            let emissions = carbon.then_some(&metadata);
+            forecast::print(&models, forecast, emissions, format, &source, stale_after)?;
+        }
+        Some(Commands::Routing {
+            draft,
+            verifier,
+            input,
+            output,
+            escalation_rate,
+        }) => {
+            let escalation_rate = parse_percent("--escalation-rate", &escalation_rate)?;
+            if escalation_rate > 1.0 {
+                return Err(anyhow::anyhow!("--escalation-rate can't be more than 100%"));
+            }
+            let routing = routing::Routing {
+                draft: find_model(&models, &draft)?,
+                verifier: find_model(&models, &verifier)?,
+                input,
+                output,
+                escalation_rate,
+            };
+            routing::print(&routing, format, &source, stale_after)?;
+        }
+        Some(Commands::Report { file, filters, .. }) => {
             let sorted_models = sort_models(models.clone(), final_sort_option);
             let grouped = group_models_by_provider(sorted_models);
             let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            report::write_rate_card(&models, &metadata, &source, &file)?;
+            println!("Wrote rate card for {} models to {}", models.len(), file.display());
+        }
+        Some(Commands::Hook {
+            prompts: dir,
+            max_prompt_cost,
+            model,
+            output_tokens,
+            base,
+            all,
+        }) => {
+            let query = model.or_else(|| config.hook_model.clone()).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "hook needs a model to price templates at. Pass --model, or set hook_model in \
+                     the config file"
+                )
+            })?;
+            let model = find_model(&models, &query)?;
+            let templates = if all {
+                prompts::load_dir(&dir, output_tokens)?
+            } else {
+                let files = prompts::changed_files(&dir, base.as_deref())?;
+                prompts::load_files(&dir, &files, output_tokens)?
+            };
+            if templates.is_empty() {
+                println!("No changed prompt templates in {}", dir.display());
+                return Ok(());
+            }
 
-            if verbose {
-                print_verbose_format(&filtered);
+            let over_budget = prompts::check_budgets(
+                &templates,
+                model,
+                max_prompt_cost,
+                format,
+                &source,
+                stale_after,
+            )?;
+            if over_budget {
+                capture::exit(1);
+            }
+        }
+        Some(Commands::Show { model }) => {
+            let model = find_model(&models, &model)?;
+            match format {
+                OutputFormat::Json | OutputFormat::Ndjson => {
+                    output::print_model_json(model, format, &source, stale_after, &metadata)?
+                }
+                _ => print_titled_table(
+                    &model.id,
+                    &model_detail_table(model, &metadata),
+                    Some(&format!("Prices in USD, from {}.", source.describe(stale_after))),
+                    format,
+                    &source,
+                    stale_after,
+                )?,
+            }
+        }
+        Some(Commands::Compare { models: queries }) => {
+            let compared = queries
+                .iter()
+                .map(|query| find_model(&models, query))
+                .collect::<anyhow::Result<Vec<&Model>>>()?;
+            match format {
+                OutputFormat::Json | OutputFormat::Ndjson => {
+                    output::print_list_json(&compared, format, &source, stale_after, &metadata)?
+                }
+                _ => print_titled_table(
+                    &format!(
+                        "Comparison: {}",
+                        compared.iter().map(|model| model.id.as_str()).collect::<Vec<_>>().join(" vs ")
+                    ),
+                    &compare_table(&compared, &metadata),
+                    Some(&format!(
+                        "Prices in USD. Change % is relative to {}.",
+                        compared[0].id
+                    )),
+                    format,
+                    &source,
+                    stale_after,
+                )?,
+            }
+        }
+        Some(Commands::Arbitrage { input, output, filters }) => {
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(true);
+            }
+            arbitrage::print(&models, input, output, format, stale_after).await?;
+        }
+        Some(Commands::History { model }) => {
+            // Models that have since been removed only exist in the history, so a reference
+            // that matches nothing listed is taken as an ID. Ambiguous ones still fail.
+            let query = model.to_lowercase();
+            let id = if models.iter().any(|listed| listed.id.to_lowercase().contains(&query)) {
+                find_model(&models, &model)?.id.clone()
             } else {
-                print_default_format(&filtered);
+                model
+            };
+            history::print(&id, format, &source, stale_after)?;
+        }
+        Some(Commands::Snapshot { file, pretty }) => {
+            if let Err(e) = history::record(&models, source.fetched_at) {
+                eprintln!("Warning: couldn't record price history: {e:#}");
             }
+            let path = snapshot::save(&models, source.fetched_at, file.as_deref(), pretty)?;
+            println!(
+                "Saved {} models fetched {} to {}",
+                models.len(),
+                source.fetched_at.format("%Y-%m-%d %H:%M UTC"),
+                path.display()
+            );
         }
-        None => {
-            // Default to list command for backward compatibility
+        Some(Commands::Diff { old, new, since }) => {
+            let old = match (old, since) {
+                (_, Some(since)) => diff::Snapshot::from_history(since)?,
+                (Some(path), None) => diff::Snapshot::from_file(&path)?,
+                (None, None) => unreachable!("clap requires a file or --since"),
+            };
+            let new = match new {
+                Some(path) => diff::Snapshot::from_file(&path)?,
+                None => diff::Snapshot::from_models(
+                    &models,
+                    format!("current prices (fetched {})", source.fetched_at.format("%Y-%m-%d %H:%M UTC")),
+                ),
+            };
+            diff::print(&old, &new, format, &source, stale_after)?;
+        }
+        Some(Commands::Endpoints { model }) => {
+            let model = find_model(&models, &model)?;
+            endpoints::print(model, format, stale_after).await?;
+        }
+        Some(Commands::Badge {
+            model,
+            price,
+            label,
+            file,
+        }) => {
+            badge::write(find_model(&models, &model)?, price, label.as_deref(), file.as_deref())?;
+        }
+        Some(Commands::Invoice {
+            usage,
+            period,
+            markup,
+            client,
+            number,
+            file,
+        }) => {
+            let branding = config.invoice.unwrap_or_default();
+            invoice::write(
+                &models,
+                &source,
+                &invoice::Options {
+                    usage: &usage,
+                    period: period.as_deref(),
+                    markup: parse_percent("--markup", &markup)?,
+                    client: client.as_deref(),
+                    number: number.as_deref(),
+                    branding: &branding,
+                    file: file.as_deref(),
+                },
+            )?;
+        }
+        Some(Commands::Quote {
+            model,
+            input,
+            output,
+            cached,
+            ttl,
+            requests,
+            file,
+        }) => {
+            let model = find_model(&models, &model)?;
+            let cost = calculate_cost(model, input, output, cached.map(|c| c.resolve(input)), ttl)?;
+            let quote = quote::Quote {
+                model,
+                cost,
+                requests,
+                prepared_at: Utc::now(),
+            };
+            quote::write(&quote, &metadata, &source, file.as_deref())?;
+        }
+        Some(Commands::Sql { query }) => {
+            let models: Vec<&Model> = models.iter().collect();
+            sql::run(&query, &models, &metadata, format, &source, stale_after)?;
+        }
+        Some(Commands::Export {
+            export_format,
+            file,
+            google_sheet,
+            sheet_tab,
+            spend,
+            requests,
+            input,
+            output,
+            headroom,
+            filters,
+        }) => {
+            if requests.is_some() && !export_format.has_budgets() {
+                return Err(anyhow::anyhow!(
+                    "--requests is only used with --format tfvars or json-budget"
+                ));
+            }
+            let usage = match (requests, input, output) {
+                (Some(requests), Some(input), Some(output)) => Some(budget::Usage {
+                    requests,
+                    input,
+                    output,
+                    headroom: parse_percent("--headroom", &headroom)?,
+                }),
+                _ => None,
+            };
             let sorted_models = sort_models(models.clone(), final_sort_option);
             let grouped = group_models_by_provider(sorted_models);
-            let filtered = filter_models(grouped, args.filters);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
 
-            if args.verbose {
-                print_verbose_format(&filtered);
-            } else {
-                print_default_format(&filtered);
+            if let Some(spreadsheet) = google_sheet {
+                let credentials = config
+                    .google_service_account
+                    .clone()
+                    .or_else(|| std::env::var_os("GOOGLE_APPLICATION_CREDENTIALS").map(PathBuf::from))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "--google-sheet needs a service account key. Set \
+                             google_service_account in the config file to the path of its JSON \
+                             key file"
+                        )
+                    })?;
+                let prices = export::model_sheet(&models, &metadata);
+                let mut tabs = vec![(sheet_tab.as_str(), &prices)];
+                let usage = if spend {
+                    Some(account::usage_table().await?.ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "--spend needs an OpenRouter API key. Add one with 'llm-pricing auth set'"
+                        )
+                    })?)
+                } else {
+                    None
+                };
+                if let Some(usage) = &usage {
+                    tabs.push(("Spend", usage));
+                }
+                google_sheets::push(&spreadsheet, &credentials, &tabs).await?;
+                println!(
+                    "Updated {} models in Google spreadsheet {}",
+                    models.len(),
+                    spreadsheet
+                );
+            } else if let Some(file) = file {
+                export::write(&models, &metadata, &source, export_format, usage.as_ref(), &file)?;
+                println!("Exported {} models to {}", models.len(), file.display());
             }
         }
+        Some(Commands::Publish { target }) => {
+            let filters = match &target {
+                PublishTarget::Notion { filters, .. } | PublishTarget::Confluence { filters, .. } => {
+                    filters
+                }
+            };
+            let sorted_models = sort_models(models.clone(), final_sort_option);
+            let published = filter_by_name(sorted_models, filters);
+            if published.is_empty() {
+                exit_no_match(!filters.is_empty());
+            }
+            let published: Vec<&Model> = published.iter().collect();
+            let table = model_table(&published, &metadata, &table_options);
+
+            match &target {
+                PublishTarget::Notion { database, prune, .. } => {
+                    publish::notion(&table, database, *prune).await?
+                }
+                PublishTarget::Confluence {
+                    site, page, user, ..
+                } => {
+                    publish::confluence(&table, site, page, user.as_deref(), &source, stale_after)
+                        .await?
+                }
+            }
+        }
+        Some(Commands::Chart {
+            metric,
+            out,
+            kind,
+            filters,
+        }) => {
+            let sort_option = final_sort_option.or(Some((metric.sort_by(), args.reverse)));
+            let sorted_models = sort_models(models.clone(), sort_option);
+            let charted = limit_rows(filter_by_name(sorted_models, &filters), args.top, args.tail);
+            if charted.is_empty() {
+                exit_no_match(!filters.is_empty());
+            }
+            let charted: Vec<&Model> = charted.iter().collect();
+
+            chart::print(&charted, metric, kind, out.as_deref(), format, &source, stale_after)?;
+        }
         Some(Commands::Calc {
             filters,
             input,
             output,
             cached,
             ttl,
+            show_rates,
+            credits,
+            payment,
+            tier,
+            sweep,
+            save_as,
         }) => {
+            if show_rates {
+                if !sweep.is_empty() {
+                    return Err(anyhow::anyhow!("--show-rates can't be combined with --sweep"));
+                }
+                // JSON output already includes the rate of each cost component
+                if matches!(format, OutputFormat::Csv | OutputFormat::Xlsx | OutputFormat::Html) {
+                    return Err(anyhow::anyhow!(
+                        "--show-rates is only supported with table and markdown output; json and ndjson output include the rates"
+                    ));
+                }
+            }
+            let models = ServiceTiers::bundled()?.apply(models, tier);
+            if tier != ServiceTier::Standard {
+                eprintln!("{}", i18n::t_args("calc-tier-note", &[("tier", tier.as_str().into())]));
+            }
+
             // For calc command, we handle total sorting after calculating costs
-            let calc_models = if matches!(final_sort_option, Some((SortBy::Total, _))) { 
-                models 
-            } else { 
-                sort_models(models.clone(), final_sort_option.clone())
-            };
+            let calc_models = sort_models(models, final_sort_option.clone());
             let grouped = group_models_by_provider(calc_models);
             let filtered = filter_models(grouped, filters);
 
-            struct CalcRow {
-                model: String,
-                input_cost: f64,
-                output_cost: f64,
-                cache_read_cost: f64,
-                cache_write_cost: f64,
-                total_cost: f64,
-            }
+            if !sweep.is_empty() {
+                let base = Scenario { ttl, cached };
+                let scenarios = expand_sweeps(&sweep, base)?;
 
-            let use_caching = cached.is_some();
-            let cached_tokens = cached.unwrap_or(0);
-            let mut calc_rows = Vec::new();
+                let mut sweep_rows = Vec::new();
+                for (_, models_in_provider) in filtered {
+                    for model in models_in_provider {
+                        let rows = scenarios
+                            .iter()
+                            .map(|scenario| {
+                                let cached_tokens = scenario.cached.map(|c| c.resolve(input));
+                                calculate_cost(&model, input, output, cached_tokens, scenario.ttl)
+                            })
+                            .collect::<anyhow::Result<Vec<_>>>()?;
+                        sweep_rows.push(rows);
+                    }
+                }
 
-            for (_, models_in_provider) in filtered {
-                for model in models_in_provider {
-                    let input_price = parse_price(&model.pricing.prompt)?;
-                    let output_price = parse_price(&model.pricing.completion)?;
-                    
-                    // cached_tokens = tokens read from cache 
-                    // new_tokens = tokens not in cache that need to be written to cache
-                    let new_tokens = input.saturating_sub(cached_tokens);
-
-                    let output_cost = (output as f64) * output_price;
-
-                    let mut cache_read_cost = 0.0;
-                    let mut cache_write_cost = 0.0;
-                    let mut input_cost = 0.0;
-
-                    if cached_tokens > 0 {
-                        // Cost for reading cached tokens
-                        if let Some(cache_read_price_str) = &model.pricing.input_cache_read {
-                            let cache_read_price = parse_price(cache_read_price_str)?;
-                            cache_read_cost = (cached_tokens as f64) * cache_read_price;
+                if sweep_rows.is_empty() {
+                    exit_no_match(true);
+                }
+
+                // Sort models by their cheapest scenario if requested
+                if let Some((SortBy::Total, reverse)) = final_sort_option {
+                    let cheapest = |rows: &[CalcRow]| {
+                        rows.iter().map(|r| r.total_cost).fold(f64::INFINITY, f64::min)
+                    };
+                    sweep_rows.sort_by(|a, b| {
+                        let ordering = cheapest(a).partial_cmp(&cheapest(b)).unwrap_or(Ordering::Equal);
+                        if reverse {
+                            ordering.reverse()
                         } else {
-                            cache_read_cost = (cached_tokens as f64) * input_price;
+                            ordering
                         }
-                    }
+                    });
+                }
+                let sweep_rows = limit_rows(sweep_rows, args.top, args.tail);
 
-                    if new_tokens > 0 {
-                        if use_caching && model.pricing.input_cache_write.is_some() {
-                            // Cost for writing new tokens to cache (replaces regular input cost for these tokens)
-                            let actual_write_price = match ttl {
-                                5 => input_price * 1.25, // 5-minute TTL is 1.25x base price
-                                60 => input_price * 2.0,  // 1-hour TTL is 2x base price
-                                _ => unimplemented!("TTL must be exactly 5 or 60 minutes"),
-                            };
-                            cache_write_cost = (new_tokens as f64) * actual_write_price;
-                            // Cache write cost replaces regular input cost for these tokens
+                match format {
+                    OutputFormat::Json | OutputFormat::Ndjson => output::print_sweep_json(
+                        input,
+                        output,
+                        &scenarios,
+                        &sweep_rows,
+                        format,
+                        &source,
+                        stale_after,
+                    )?,
+                    OutputFormat::Csv => sweep_table(input, &scenarios, &sweep_rows).print_csv(),
+                    OutputFormat::Xlsx => {
+                        let sheet = i18n::t("sweep-sheet");
+                        xlsx::write(&[(sheet.as_str(), &sweep_table(input, &scenarios, &sweep_rows))])?
+                    }
+                    OutputFormat::Html
+                    | OutputFormat::Markdown
+                    | OutputFormat::GithubSummary
+                    | OutputFormat::Table => {
+                        let heading = i18n::t_args(
+                            "sweep-heading",
+                            &[
+                                ("input", input.to_string().into()),
+                                ("output", output.to_string().into()),
+                                ("scenarios", scenarios.len().into()),
+                            ],
+                        );
+                        let table = sweep_table(input, &scenarios, &sweep_rows);
+                        if matches!(format, OutputFormat::Markdown | OutputFormat::GithubSummary) {
+                            print_markdown(&heading, &table, None, format, &source, stale_after)?;
+                        } else if format == OutputFormat::Html {
+                            let intro = [prices_from(&source, stale_after)];
+                            print!("{}", html::page(&heading, &intro, &table, None));
                         } else {
-                            // Regular input cost for tokens (no caching or can't be cached)
-                            input_cost = (new_tokens as f64) * input_price;
+                            print_heading(&heading);
+                            table.print();
                         }
                     }
+                }
+                return Ok(());
+            }
 
-                    let total_cost = input_cost + output_cost + cache_read_cost + cache_write_cost;
+            let cached = cached.map(|c| c.resolve(input));
+            let use_caching = cached.is_some();
+            let cached_tokens = cached.unwrap_or(0);
+            let mut calc_rows = Vec::new();
 
-                    calc_rows.push(CalcRow {
-                        model: model.id.clone(),
-                        input_cost,
-                        output_cost,
-                        cache_read_cost,
-                        cache_write_cost,
-                        total_cost,
-                    });
+            for (_, models_in_provider) in filtered {
+                for model in models_in_provider {
+                    calc_rows.push(calculate_cost(&model, input, output, cached, ttl)?);
                 }
             }
 
             if calc_rows.is_empty() {
-                eprintln!("No models found matching the filter");
-                eprintln!("Use 'llm-pricing list' to see available models");
-                std::process::exit(1);
+                exit_no_match(true);
             }
 
             // Sort by total cost if requested
@@ -611,131 +3993,179 @@ async fn main() -> anyhow::Result<()> {
                     }
                 });
             }
+            let calc_rows = limit_rows(calc_rows, args.top, args.tail);
 
-            // Calculate column widths
-            let max_model_width = calc_rows
-                .iter()
-                .map(|r| r.model.len())
-                .max()
-                .unwrap_or(0)
-                .max(5);
-
-            // Calculate the actual width needed for cost columns
-            let format_cost = |cost: f64| format!("${:.6}", cost);
-            let max_input_width = calc_rows
-                .iter()
-                .map(|r| format_cost(r.input_cost).len())
-                .max()
-                .unwrap_or(0)
-                .max(5);
-            let max_output_width = calc_rows
-                .iter()
-                .map(|r| format_cost(r.output_cost).len())
-                .max()
-                .unwrap_or(0)
-                .max(6);
-            let max_cache_read_width = calc_rows
-                .iter()
-                .map(|r| format_cost(r.cache_read_cost).len())
-                .max()
-                .unwrap_or(0)
-                .max(10);
-            let max_cache_write_width = calc_rows
-                .iter()
-                .map(|r| format_cost(r.cache_write_cost).len())
-                .max()
-                .unwrap_or(0)
-                .max(11);
-            let max_total_width = calc_rows
-                .iter()
-                .map(|r| format_cost(r.total_cost).len())
-                .max()
-                .unwrap_or(0)
-                .max(5);
-
-            // Print header with request details
-            let cache_desc = if use_caching && cached_tokens > 0 {
-                let ttl_desc = match ttl {
-                    5 => "5m",
-                    60 => "1h",
-                    _ => unimplemented!("TTL must be exactly 5 or 60 minutes"),
-                };
-                format!(" ({} cached, {} TTL)", cached_tokens, ttl_desc)
-            } else {
-                String::new()
+            for row in &calc_rows {
+                if let Some(entry) = metadata.lifecycle.get(&row.model) {
+                    eprintln!("Warning: {} is {}", row.model, entry.describe(metadata.today));
+                }
+            }
+
+            let request = output::CalcRequestJson {
+                input,
+                output,
+                cached,
+                ttl,
+                tier,
             };
+            if let Some(name) = &save_as {
+                let path = results::save(name, request.clone(), &calc_rows, &source)?;
+                eprintln!("Saved result '{}' to {}", name, path.display());
+            }
 
-            println!(
-                "Cost calculation: {} input + {} output{}",
-                input, output, cache_desc
-            );
-            println!();
+            if matches!(format, OutputFormat::Json | OutputFormat::Ndjson) {
+                let payment = credits.then_some(payment);
+                output::print_calc_json(request, &calc_rows, payment, format, &source, stale_after)?;
+                return Ok(());
+            }
 
+            let mut headers = vec!["col-model", "col-input", "col-output"];
             if use_caching {
-                println!("{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_read$} | {:<width_write$} | {:<width_total$}",
-                    "Model", "Input", "Output", "Cache Read", "Cache Write", "Total",
-                    width_model = max_model_width,
-                    width_input = max_input_width,
-                    width_output = max_output_width,
-                    width_read = max_cache_read_width,
-                    width_write = max_cache_write_width,
-                    width_total = max_total_width);
-                println!("{:-<width_model$}-+-{:-<width_input$}-+-{:-<width_output$}-+-{:-<width_read$}-+-{:-<width_write$}-+-{:-<width_total$}",
-                    "", "", "", "", "", "",
-                    width_model = max_model_width,
-                    width_input = max_input_width,
-                    width_output = max_output_width,
-                    width_read = max_cache_read_width,
-                    width_write = max_cache_write_width,
-                    width_total = max_total_width);
-
-                for row in calc_rows {
-                    println!("{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_read$} | {:<width_write$} | {:<width_total$}",
-                        row.model,
-                        format_cost(row.input_cost),
-                        format_cost(row.output_cost),
-                        format_cost(row.cache_read_cost),
-                        format_cost(row.cache_write_cost),
-                        format_cost(row.total_cost),
-                        width_model = max_model_width,
-                        width_input = max_input_width,
-                        width_output = max_output_width,
-                        width_read = max_cache_read_width,
-                        width_write = max_cache_write_width,
-                        width_total = max_total_width);
+                headers.extend(["col-cache-read", "col-cache-write"]);
+            }
+            headers.push("col-total");
+            if credits {
+                headers.push("col-top-up-cost");
+            }
+
+            let mut table = Table::new(headers.into_iter().map(i18n::t));
+            for row in &calc_rows {
+                let mut cells = vec![
+                    model_link(&row.model),
+                    Cell::Cost(row.input.cost),
+                    Cell::Cost(row.output.cost),
+                ];
+                if use_caching {
+                    cells.push(Cell::Cost(row.cache_read.cost));
+                    cells.push(Cell::Cost(row.cache_write.cost));
                 }
-            } else {
-                println!(
-                    "{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_total$}",
-                    "Model",
-                    "Input",
-                    "Output",
-                    "Total",
-                    width_model = max_model_width,
-                    width_input = max_input_width,
-                    width_output = max_output_width,
-                    width_total = max_total_width
-                );
-                println!("{:-<width_model$}-+-{:-<width_input$}-+-{:-<width_output$}-+-{:-<width_total$}",
-                    "", "", "", "",
-                    width_model = max_model_width,
-                    width_input = max_input_width,
-                    width_output = max_output_width,
-                    width_total = max_total_width);
-
-                for row in calc_rows {
-                    println!("{:<width_model$} | {:<width_input$} | {:<width_output$} | {:<width_total$}",
-                        row.model,
-                        format_cost(row.input_cost),
-                        format_cost(row.output_cost),
-                        format_cost(row.total_cost),
-                        width_model = max_model_width,
-                        width_input = max_input_width,
-                        width_output = max_output_width,
-                        width_total = max_total_width);
+                cells.push(Cell::Cost(row.total_cost));
+                if credits {
+                    cells.push(Cell::Cost(payment.top_up_cost(row.total_cost)));
                 }
+                table.push(cells);
             }
+            if args.summary {
+                table.add_totals();
+            }
+
+            if format == OutputFormat::Csv {
+                table.print_csv();
+                return Ok(());
+            }
+            if format == OutputFormat::Xlsx {
+                return xlsx::write(&[("Cost calculation", &table)]);
+            }
+
+            // Header with request details
+            let mut heading = i18n::t_args(
+                "calc-heading",
+                &[("input", input.to_string().into()), ("output", output.to_string().into())],
+            );
+            if use_caching && cached_tokens > 0 {
+                heading.push(' ');
+                heading.push_str(&i18n::t_args(
+                    "calc-cached",
+                    &[("cached", cached_tokens.to_string().into()), ("ttl", ttl_label(ttl).into())],
+                ));
+            }
+            if tier != ServiceTier::Standard {
+                heading.push(' ');
+                heading.push_str(&i18n::t_args("calc-tier", &[("tier", tier.as_str().into())]));
+            }
+            let credits_note = i18n::t_args("credits-note", &[("fee", payment.fee_description().into())]);
+
+            if format == OutputFormat::Html {
+                let intro = [prices_from(&source, stale_after)];
+                let footer = credits.then_some(credits_note.as_str());
+                print!("{}", html::page(&heading, &intro, &table, footer));
+                return Ok(());
+            }
+            if matches!(format, OutputFormat::Markdown | OutputFormat::GithubSummary) {
+                let note = credits.then_some(credits_note.as_str());
+                print_markdown(&heading, &table, note, format, &source, stale_after)?;
+                if show_rates {
+                    if format == OutputFormat::Markdown {
+                        println!();
+                    }
+                    let rates = rates_table(&calc_rows, ttl);
+                    print_markdown(&i18n::t("rates-heading"), &rates, None, format, &source, stale_after)?;
+                }
+                return Ok(());
+            }
+
+            print_heading(&heading);
+            table.print();
+
+            if credits {
+                print_note(&credits_note);
+            }
+
+            if show_rates {
+                if !terminal::is_quiet() {
+                    println!();
+                }
+                print_heading(&i18n::t("rates-heading"));
+                rates_table(&calc_rows, ttl).print();
+            }
+        }
+        Some(Commands::Prompts {
+            action: PromptsAction::Cost { dir, output_tokens, filters },
+        }) => {
+            let templates = prompts::load_dir(&dir, output_tokens)?;
+            if templates.is_empty() {
+                return Err(anyhow::anyhow!("No prompt templates found in {}", dir.display()));
+            }
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            prompts::print_cost(&templates, &models, format, &source, stale_after)?;
+        }
+        Some(Commands::Prompts {
+            action: PromptsAction::Diff { dir, base, head, output_tokens, max_increase, filters },
+        }) => {
+            let base_templates = prompts::load_revision(&base, &dir, output_tokens)?;
+            let head_templates = prompts::load_revision(&head, &dir, output_tokens)?;
+            let grouped = group_models_by_provider(models);
+            let filtered = filter_models(grouped, filters);
+            let models: Vec<&Model> = filtered.values().flatten().collect();
+            if models.is_empty() {
+                exit_no_match(false);
+            }
+
+            let exceeded = prompts::print_diff(
+                (&base, &base_templates),
+                (&head, &head_templates),
+                &models,
+                max_increase,
+                format,
+                &source,
+                stale_after,
+            )?;
+            if exceeded {
+                capture::exit(1);
+            }
+        }
+        Some(Commands::Alert { action: AlertAction::Check }) => {
+            if alerts::check(&models, format, &source, stale_after)? {
+                capture::exit(1);
+            }
+        }
+        Some(Commands::Results {
+            action: ResultsAction::Diff { name, .. },
+        }) => {
+            results::diff_live(&name, models, format, &source, stale_after)?;
+        }
+        Some(Commands::SaveQuery { .. } | Commands::Run { .. } | Commands::Doctor | Commands::Init | Commands::Auth { .. } | Commands::Account | Commands::Approve { .. } | Commands::Warm | Commands::Results { action: ResultsAction::List } | Commands::Alert { action: AlertAction::Add { .. } | AlertAction::List | AlertAction::Remove { .. } } | Commands::InstallIntegrations { .. }) => {
+            unreachable!("handled before fetching models")
         }
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { .. }) => unreachable!("handled before fetching models"),
+        Some(Commands::Watch { .. }) => unreachable!("handled before filtering models"),
     }
 
     Ok(())
@@ -0,0 +1,114 @@
+use crate::{auth, cache, config::Config, format_duration, i18n, paths, OutputFormat};
+use anyhow::Context;
+use clap::ValueEnum;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Whether to offer setup: there is no config file yet and someone is at the terminal to answer
+pub fn needed() -> anyhow::Result<bool> {
+    Ok(!paths::config_file()?.exists() && io::stdin().is_terminal() && io::stdout().is_terminal())
+}
+
+/// Ask a few questions and write the answers to the config file
+pub fn run() -> anyhow::Result<Config> {
+    let mut config = Config::load()?;
+
+    println!("{}", i18n::t("wizard-welcome"));
+    println!("{}", i18n::t("wizard-defaults"));
+    println!();
+
+    // xlsx writes to a file named on each run and github-summary only makes sense inside a
+    // workflow, so neither can be a default
+    let can_default = |format: OutputFormat| {
+        !matches!(format, OutputFormat::Xlsx | OutputFormat::GithubSummary)
+    };
+    let formats = OutputFormat::value_variants()
+        .iter()
+        .filter(|format| can_default(**format))
+        .filter_map(|format| format.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    config.output = loop {
+        let question = i18n::t_args("wizard-output", &[("formats", formats.as_str().into())]);
+        let answer = prompt(&format!("{question} "))?;
+        if answer.is_empty() {
+            break None;
+        }
+        match OutputFormat::from_str(&answer, true) {
+            Ok(format) if can_default(format) => break Some(format),
+            _ => println!(
+                "{}",
+                i18n::t_args("wizard-choose", &[("formats", formats.as_str().into())])
+            ),
+        }
+    };
+
+    config.cache = confirm(
+        &i18n::t_args(
+            "wizard-cache",
+            &[("duration", format_duration(cache::CATALOG_TTL).into())],
+        ),
+        true,
+    )?;
+
+    if confirm(&i18n::t("wizard-store-key"), false)? {
+        // Setup still completes when the keychain is unavailable, e.g. over SSH
+        match auth::read_key(auth::DEFAULT_PROVIDER)
+            .and_then(|key| auth::store_key(auth::DEFAULT_PROVIDER, None, &key))
+        {
+            Ok(()) => println!("{}", i18n::t("wizard-stored")),
+            Err(e) => println!(
+                "{}",
+                i18n::t_args(
+                    "wizard-store-failed",
+                    &[
+                        ("error", format!("{e:#}").into()),
+                        ("variable", auth::env_var(auth::DEFAULT_PROVIDER).into()),
+                    ],
+                )
+            ),
+        }
+    }
+
+    let path = config.save()?;
+    println!();
+    println!(
+        "{}",
+        i18n::t_args("wizard-saved", &[("path", path.display().to_string().into())])
+    );
+    println!("{}", i18n::t("wizard-change-later"));
+    println!();
+    Ok(config)
+}
+
+fn prompt(question: &str) -> anyhow::Result<String> {
+    print!("{question}");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    Ok(answer.trim().to_string())
+}
+
+/// Ask a yes/no question. English answers are accepted whatever the language.
+fn confirm(question: &str, default: bool) -> anyhow::Result<bool> {
+    let (yes, no) = (i18n::t("wizard-yes"), i18n::t("wizard-no"));
+    let choices = if default {
+        format!("[{}/{no}]", yes.to_uppercase())
+    } else {
+        format!("[{yes}/{}]", no.to_uppercase())
+    };
+    loop {
+        let answer = prompt(&format!("{question} {choices}: "))?.to_lowercase();
+        match answer.as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ if answer.starts_with(&yes) => return Ok(true),
+            _ if answer.starts_with(&no) => return Ok(false),
+            _ => println!("{}", i18n::t("wizard-yes-no")),
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{
+    config::Approval, endpoints::Endpoint, huggingface, lifecycle::Lifecycle, service_tier::ServiceTier, CalcRow, CostComponent,
+    Metadata, Model, OutputFormat, PaymentMethod, Scenario, SourceInfo,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+/// Print one compact JSON object per line for `--output ndjson`, each with the `source` object
+/// that JSON documents carry once, so every line says how fresh its prices are. Rows that
+/// already have a `source` field keep their own.
+fn print_lines<T: Serialize>(
+    rows: &[T],
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let source = serde_json::to_value(SourceJson::new(source, stale_after))?;
+    let mut stdout = io::stdout().lock();
+    for row in rows {
+        let mut row = serde_json::to_value(row)?;
+        if let Some(fields) = row.as_object_mut() {
+            fields.entry("source").or_insert_with(|| source.clone());
+        }
+        let written = serde_json::to_writer(&mut stdout, &row)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(stdout));
+        match written {
+            // The reader stopped early (e.g. `| head`), so stop quietly like other line tools
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SourceJson<'a> {
+    name: &'a str,
+    url: &'a str,
+    fetched_at: DateTime<Utc>,
+    age_seconds: i64,
+    staleness: &'a str,
+}
+
+impl<'a> SourceJson<'a> {
+    fn new(source: &'a SourceInfo, stale_after: TimeDelta) -> Self {
+        Self {
+            name: source.name,
+            url: source.url,
+            fetched_at: source.fetched_at,
+            age_seconds: source.age().num_seconds(),
+            staleness: source.staleness(stale_after),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ModelJson<'a> {
+    #[serde(flatten)]
+    model: &'a Model,
+    provider: &'a str,
+    license: &'a str,
+    tags: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifecycle: Option<&'a Lifecycle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    approval: Option<Approval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hugging_face: Option<&'a huggingface::ModelInfo>,
+}
+
+impl<'a> ModelJson<'a> {
+    fn new(model: &'a Model, metadata: &'a Metadata) -> Self {
+        Self {
+            model,
+            provider: model.id.split('/').next().unwrap_or("unknown"),
+            license: metadata.license(model),
+            tags: metadata.tags.of(model),
+            lifecycle: metadata.lifecycle.get(&model.id),
+            approval: metadata.approval(model),
+            notes: metadata.note(model),
+            hugging_face: metadata.hugging_face(model),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ListJson<'a> {
+    source: SourceJson<'a>,
+    models: Vec<ModelJson<'a>>,
+}
+
+/// Print models as a JSON document, or one model per line for NDJSON
+pub fn print_list_json(
+    models: &[&Model],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+    metadata: &Metadata,
+) -> anyhow::Result<()> {
+    let models: Vec<ModelJson> = models
+        .iter()
+        .map(|model| ModelJson::new(model, metadata))
+        .collect();
+    if format == OutputFormat::Ndjson {
+        return print_lines(&models, source, stale_after);
+    }
+
+    let list = ListJson {
+        source: SourceJson::new(source, stale_after),
+        models,
+    };
+    println!("{}", serde_json::to_string_pretty(&list)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ShowJson<'a> {
+    source: SourceJson<'a>,
+    model: ModelJson<'a>,
+}
+
+/// One model with everything known about it, for `show`
+pub fn print_model_json(
+    model: &Model,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+    metadata: &Metadata,
+) -> anyhow::Result<()> {
+    let model = ModelJson::new(model, metadata);
+    if format == OutputFormat::Ndjson {
+        return print_lines(&[model], source, stale_after);
+    }
+
+    let show = ShowJson {
+        source: SourceJson::new(source, stale_after),
+        model,
+    };
+    println!("{}", serde_json::to_string_pretty(&show)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct EndpointsJson<'a> {
+    source: SourceJson<'a>,
+    model: &'a str,
+    endpoints: &'a [Endpoint],
+}
+
+/// Each provider serving a model with its own prices, for `endpoints`
+pub fn print_endpoints_json(
+    model: &str,
+    endpoints: &[Endpoint],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    if format == OutputFormat::Ndjson {
+        return print_lines(endpoints, source, stale_after);
+    }
+    let list = EndpointsJson {
+        source: SourceJson::new(source, stale_after),
+        model,
+        endpoints,
+    };
+    println!("{}", serde_json::to_string_pretty(&list)?);
+    Ok(())
+}
+
+/// The request parameters a calc result was computed for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalcRequestJson {
+    pub input: u64,
+    pub output: u64,
+    pub cached: Option<u64>,
+    pub ttl: u64,
+    pub tier: ServiceTier,
+}
+
+#[derive(Serialize)]
+struct CreditsJson {
+    payment: PaymentMethod,
+    top_up_cost: f64,
+}
+
+#[derive(Serialize)]
+struct CalcRowJson<'a> {
+    model: &'a str,
+    input: &'a CostComponent,
+    output: &'a CostComponent,
+    cache_read: &'a CostComponent,
+    cache_write: &'a CostComponent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_write_multiplier: Option<f64>,
+    total_cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credits: Option<CreditsJson>,
+}
+
+impl<'a> CalcRowJson<'a> {
+    fn new(row: &'a CalcRow, payment: Option<PaymentMethod>) -> Self {
+        Self {
+            model: &row.model,
+            input: &row.input,
+            output: &row.output,
+            cache_read: &row.cache_read,
+            cache_write: &row.cache_write,
+            cache_write_multiplier: row.cache_write_multiplier,
+            total_cost: row.total_cost,
+            credits: payment.map(|payment| CreditsJson {
+                payment,
+                top_up_cost: payment.top_up_cost(row.total_cost),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CalcJson<'a> {
+    source: SourceJson<'a>,
+    request: CalcRequestJson,
+    results: Vec<CalcRowJson<'a>>,
+}
+
+pub fn print_calc_json(
+    request: CalcRequestJson,
+    rows: &[CalcRow],
+    payment: Option<PaymentMethod>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let results: Vec<CalcRowJson> = rows.iter().map(|row| CalcRowJson::new(row, payment)).collect();
+    if format == OutputFormat::Ndjson {
+        return print_lines(&results, source, stale_after);
+    }
+
+    let calc = CalcJson {
+        source: SourceJson::new(source, stale_after),
+        request,
+        results,
+    };
+    println!("{}", serde_json::to_string_pretty(&calc)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ScenarioJson {
+    ttl: u64,
+    cached: Option<String>,
+    cached_tokens: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct SweepRowJson<'a> {
+    scenario: ScenarioJson,
+    #[serde(flatten)]
+    result: CalcRowJson<'a>,
+    best: bool,
+}
+
+#[derive(Serialize)]
+struct SweepJson<'a> {
+    source: SourceJson<'a>,
+    input: u64,
+    output: u64,
+    results: Vec<SweepRowJson<'a>>,
+}
+
+pub fn print_sweep_json(
+    input: u64,
+    output: u64,
+    scenarios: &[Scenario],
+    sweep_rows: &[Vec<CalcRow>],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut results = Vec::new();
+    for model_rows in sweep_rows {
+        let cheapest = model_rows
+            .iter()
+            .map(|r| r.total_cost)
+            .fold(f64::INFINITY, f64::min);
+
+        for (scenario, row) in scenarios.iter().zip(model_rows) {
+            results.push(SweepRowJson {
+                scenario: ScenarioJson {
+                    ttl: scenario.ttl,
+                    cached: scenario.cached.map(|c| c.to_string()),
+                    cached_tokens: scenario.cached.map(|c| c.resolve(input)),
+                },
+                result: CalcRowJson::new(row, None),
+                best: row.total_cost == cheapest,
+            });
+        }
+    }
+
+    if format == OutputFormat::Ndjson {
+        return print_lines(&results, source, stale_after);
+    }
+
+    let sweep = SweepJson {
+        source: SourceJson::new(source, stale_after),
+        input,
+        output,
+        results,
+    };
+    println!("{}", serde_json::to_string_pretty(&sweep)?);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SqlJson<'a> {
+    source: SourceJson<'a>,
+    rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+fn sql_value(value: &rusqlite::types::Value) -> serde_json::Value {
+    use rusqlite::types::Value;
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(value) => (*value).into(),
+        Value::Real(value) => (*value).into(),
+        Value::Text(text) => text.clone().into(),
+        Value::Blob(bytes) => bytes.clone().into(),
+    }
+}
+
+/// Print query results as a JSON document with one object per row, or one row per line for
+/// NDJSON
+pub fn print_sql_json(
+    columns: &[String],
+    rows: &[Vec<rusqlite::types::Value>],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let rows: Vec<serde_json::Map<String, serde_json::Value>> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .cloned()
+                .zip(row.iter().map(sql_value))
+                .collect()
+        })
+        .collect();
+    if format == OutputFormat::Ndjson {
+        return print_lines(&rows, source, stale_after);
+    }
+
+    let sql = SqlJson {
+        source: SourceJson::new(source, stale_after),
+        rows,
+    };
+    println!("{}", serde_json::to_string_pretty(&sql)?);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{capture, terminal};
+use std::{
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+/// Send the rest of stdout through $PAGER (default `less`) when it's a terminal, like
+/// `git log`. Output is held back until the command is done, and only paged when it's
+/// taller than the terminal; anything shorter is printed as usual.
+pub fn start() {
+    if !io::stdout().is_terminal() || pager().is_none() {
+        return;
+    }
+    // Without a capture, output is simply printed as it comes
+    if capture::start(show).is_ok() {
+        terminal::set_paged();
+    }
+}
+
+/// The pager to run, unless $PAGER turns paging off with `cat` or an empty value
+fn pager() -> Option<Command> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut words = pager.split_whitespace();
+    let program = words.next().filter(|program| *program != "cat")?;
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    Some(command)
+}
+
+fn show(output: String) {
+    // Leave a row for the prompt that follows
+    let fits = terminal::height().is_none_or(|rows| output.lines().count() < rows);
+    // A missing pager isn't worth failing over, so print directly instead
+    let child = (!fits)
+        .then(pager)
+        .flatten()
+        .and_then(|mut command| command.spawn().ok());
+    let Some(mut child) = child else {
+        print!("{output}");
+        let _ = io::stdout().flush();
+        return;
+    };
+    // Quitting the pager early closes the pipe, which isn't an error worth reporting
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    let _ = child.wait();
+}
@@ -0,0 +1,29 @@
+use anyhow::Context;
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+/// Environment variable that overrides the config file location
+pub const CONFIG_ENV: &str = "LLM_PRICING_CONFIG";
+
+/// Platform directories: XDG on Linux, ~/Library on macOS, %APPDATA%/%LOCALAPPDATA% on Windows
+fn project_dirs() -> anyhow::Result<ProjectDirs> {
+    ProjectDirs::from("", "", "llm-pricing")
+        .context("Could not determine the home directory for this platform")
+}
+
+pub fn config_file() -> anyhow::Result<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(project_dirs()?.config_dir().join("config.toml"))
+}
+
+/// Directory for re-fetchable data such as API responses
+pub fn cache_dir() -> anyhow::Result<PathBuf> {
+    Ok(project_dirs()?.cache_dir().to_path_buf())
+}
+
+/// Directory for data the user would want to keep, such as snapshots and history
+pub fn data_dir() -> anyhow::Result<PathBuf> {
+    Ok(project_dirs()?.data_dir().to_path_buf())
+}
@@ -0,0 +1,144 @@
+use crate::{
+    calculate_cost, model_link, print_titled_table,
+    rate_limits::{RateLimit, RateLimits},
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+
+/// Minutes in the 30-day month volumes are spread over
+const MINUTES_PER_MONTH: f64 = 30.0 * 24.0 * 60.0;
+
+/// A month's load, and how far above its average rate the busiest minute goes
+pub struct Load {
+    pub requests: u64,
+    pub input: u64,
+    pub output: u64,
+    pub keys: u64,
+    pub peak_factor: f64,
+}
+
+/// The largest share of the load a model can take at peak without going over its rate limits
+/// with `load.keys` keys, which may be more than all of it
+fn max_share(limit: &RateLimit, load: &Load) -> f64 {
+    let per_minute = |monthly: u64| monthly as f64 / MINUTES_PER_MONTH * load.peak_factor;
+    let share = |limit: Option<u64>, demand: f64| match limit {
+        Some(limit) if demand > 0.0 => (limit * load.keys) as f64 / demand,
+        _ => f64::INFINITY,
+    };
+    [
+        share(Some(limit.rpm), per_minute(load.requests)),
+        share(limit.tpm, per_minute(load.input + load.output)),
+        share(limit.input_tpm, per_minute(load.input)),
+        share(limit.output_tpm, per_minute(load.output)),
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min)
+}
+
+struct Candidate<'a> {
+    model: &'a Model,
+    limit: Option<&'a RateLimit>,
+    /// What the whole month's load would cost on this model alone
+    cost: f64,
+    max_share: f64,
+    share: f64,
+}
+
+/// Split a monthly load across candidate models at the lowest cost that keeps each within its
+/// rate limits at peak, and print the allocation. Cost is linear in each model's share and
+/// each share is capped by its limits, so filling the cheapest model first, then the next, is
+/// the optimum of the linear program.
+pub fn print(
+    models: &[&Model],
+    rate_limits: &RateLimits,
+    load: &Load,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut candidates = Vec::new();
+    for model in models {
+        // Routers report variable pricing as -1, which would make them look cheaper than free
+        let prices = [&model.pricing.prompt, &model.pricing.completion];
+        if prices.iter().any(|price| price.parse::<f64>().is_ok_and(|price| price < 0.0)) {
+            continue;
+        }
+        let limit = rate_limits.lookup(&model.id);
+        candidates.push(Candidate {
+            model,
+            limit,
+            cost: calculate_cost(model, load.input, load.output, None, 5)?.total_cost,
+            max_share: limit.map_or(f64::INFINITY, |limit| max_share(limit, load)),
+            share: 0.0,
+        });
+    }
+    if candidates.is_empty() {
+        return Err(anyhow::anyhow!("None of the matching models have fixed prices to plan with"));
+    }
+    candidates.sort_by(|a, b| a.cost.total_cmp(&b.cost).then_with(|| a.model.id.cmp(&b.model.id)));
+
+    let mut remaining = 1.0;
+    for candidate in &mut candidates {
+        candidate.share = candidate.max_share.min(remaining);
+        remaining -= candidate.share;
+        if remaining <= 0.0 {
+            break;
+        }
+    }
+    if remaining > 1e-9 {
+        return Err(anyhow::anyhow!(
+            "The candidates' rate limits only cover {:.1}% of the load at peak. Plan with more keys \
+             per model (--keys) or add candidates.",
+            (1.0 - remaining) * 100.0
+        ));
+    }
+
+    let mut table = Table::new([
+        "Model",
+        "Tier",
+        "Share %",
+        "Requests",
+        "Limit Used %",
+        "Cost/Month",
+    ]);
+    let mut total = 0.0;
+    for candidate in &candidates {
+        let cost = candidate.cost * candidate.share;
+        total += cost;
+        table.push(vec![
+            model_link(&candidate.model.id),
+            candidate
+                .limit
+                .map_or(Cell::Missing, |limit| Cell::text(limit.tier.as_deref().unwrap_or(""))),
+            Cell::Number(candidate.share * 100.0, 1),
+            Cell::Number((load.requests as f64 * candidate.share).round(), 0),
+            if candidate.max_share.is_finite() {
+                Cell::Number(candidate.share / candidate.max_share * 100.0, 1)
+            } else {
+                Cell::Missing
+            },
+            Cell::Cost(cost),
+        ]);
+    }
+    table.push_footer(vec![
+        Cell::text("Total"),
+        Cell::text(""),
+        Cell::Number(100.0, 1),
+        Cell::Number(load.requests as f64, 0),
+        Cell::text(""),
+        Cell::Cost(total),
+    ]);
+
+    let heading = format!(
+        "Allocation of {} requests a month ({} input + {} output tokens)",
+        load.requests, load.input, load.output
+    );
+    let note = format!(
+        "Cheapest first, each model taking as much as its rate limits allow with {} key(s) at {}x \
+         the average rate. Limit Used %: of the most the model could take. N/A: no rate limit \
+         data, so no cap.",
+        load.keys, load.peak_factor
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
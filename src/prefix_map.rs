@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+/// Values keyed by ID, where keys ending in `*` match any ID with that prefix. Exact keys take
+/// precedence, then the longest matching prefix.
+pub struct PrefixMap<T> {
+    exact: HashMap<String, T>,
+    prefixes: Vec<(String, T)>,
+}
+
+impl<T> PrefixMap<T> {
+    pub fn new(entries: impl IntoIterator<Item = (String, T)>) -> Self {
+        let mut exact = HashMap::new();
+        let mut prefixes = Vec::new();
+        for (key, value) in entries {
+            match key.strip_suffix('*') {
+                Some(prefix) => prefixes.push((prefix.to_string(), value)),
+                None => {
+                    exact.insert(key, value);
+                }
+            }
+        }
+        // Longest prefix first so more specific families win
+        prefixes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        Self { exact, prefixes }
+    }
+
+    pub fn lookup(&self, id: &str) -> Option<&T> {
+        self.exact.get(id).or_else(|| {
+            self.prefixes
+                .iter()
+                .find(|(prefix, _)| id.starts_with(prefix.as_str()))
+                .map(|(_, value)| value)
+        })
+    }
+}
@@ -0,0 +1,467 @@
+use crate::{
+    calculate_cost, github, model_link, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::TimeDelta;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path, PathBuf},
+    process::Command,
+};
+
+/// Rough characters per token for English text and code. Exact counts depend on each model's
+/// tokenizer, so template sizes are estimates.
+const CHARS_PER_TOKEN: f64 = 4.0;
+
+/// A prompt template and how many tokens a request using it sends and gets back
+pub struct Template {
+    /// Path relative to the prompt directory, with '/' separators
+    pub name: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Most a request using the template may cost in USD, from `max_cost` in the frontmatter
+    pub max_cost: Option<f64>,
+}
+
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as f64 / CHARS_PER_TOKEN).ceil() as u64
+}
+
+/// Split `---` frontmatter from the start of a template, returning it and the body
+fn split_frontmatter(contents: &str) -> (Option<&str>, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return (None, contents);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, contents)
+}
+
+/// Parse a template, taking its expected output length from an `output_tokens: N` line in
+/// the frontmatter, or `default_output` when there isn't one, and its cost budget from a
+/// `max_cost: USD` line
+pub fn parse(name: &str, contents: &str, default_output: Option<u64>) -> anyhow::Result<Template> {
+    let (frontmatter, body) = split_frontmatter(contents);
+    let mut output_tokens = None;
+    let mut max_cost = None;
+    for line in frontmatter.unwrap_or_default().lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "output_tokens" => {
+                output_tokens = Some(value.parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "Invalid output_tokens in {}: '{}'. Expected a number of tokens",
+                        name,
+                        value
+                    )
+                })?);
+            }
+            "max_cost" => {
+                let cost = value.trim_start_matches('$').parse::<f64>().ok();
+                max_cost = Some(cost.filter(|cost| *cost >= 0.0).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid max_cost in {}: '{}'. Expected an amount in USD (e.g., 0.05)",
+                        name,
+                        value
+                    )
+                })?);
+            }
+            _ => {}
+        }
+    }
+    let output_tokens = output_tokens.or(default_output).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} has no output_tokens in its frontmatter. Add one, or pass --output-tokens",
+            name
+        )
+    })?;
+
+    Ok(Template {
+        name: name.to_string(),
+        input_tokens: estimate_tokens(body),
+        output_tokens,
+        max_cost,
+    })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Load every template under `dir`, skipping hidden files and directories
+pub fn load_dir(dir: &Path, default_output: Option<u64>) -> anyhow::Result<Vec<Template>> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    load_files(dir, &files, default_output)
+}
+
+/// Load the given template files from under `dir`, skipping hidden ones
+pub fn load_files(
+    dir: &Path,
+    files: &[PathBuf],
+    default_output: Option<u64>,
+) -> anyhow::Result<Vec<Template>> {
+    let mut files = files.to_vec();
+    files.sort();
+
+    files
+        .iter()
+        .filter_map(|path| template_name(path, dir).map(|name| (path, name)))
+        .map(|(path, name)| {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template {}", path.display()))?;
+            parse(&name, &contents, default_output)
+        })
+        .collect()
+}
+
+/// Template name relative to `dir` with '/' separators, or None for hidden files
+fn template_name(path: &Path, dir: &Path) -> Option<String> {
+    let relative = path.strip_prefix(dir).unwrap_or(path);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if parts.iter().any(|part| part.starts_with('.')) {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+fn git(args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Files under `dir` that are staged for commit, or with `base`, that changed between that
+/// revision and the working tree. Deleted files are left out, having nothing to check.
+pub fn changed_files(dir: &Path, base: Option<&str>) -> anyhow::Result<Vec<PathBuf>> {
+    let dir_arg = dir.to_string_lossy();
+    // --relative gives paths relative to the current directory, like `dir`
+    let mut args = vec!["diff", "--relative", "--name-only", "-z", "--diff-filter=ACMR"];
+    match base {
+        Some(base) => args.push(base),
+        None => args.push("--cached"),
+    }
+    args.extend(["--", &dir_arg]);
+    let listing = git(&args)?;
+    Ok(listing
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .collect())
+}
+
+/// Load every template under `dir` as it was at a git revision. A directory that didn't exist
+/// yet has no templates.
+pub fn load_revision(
+    revision: &str,
+    dir: &Path,
+    default_output: Option<u64>,
+) -> anyhow::Result<Vec<Template>> {
+    // git lists paths relative to the current directory without a leading './'
+    let dir: PathBuf = dir
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect();
+    let dir_arg = if dir.as_os_str().is_empty() {
+        ".".into()
+    } else {
+        dir.to_string_lossy()
+    };
+    let listing = git(&["ls-tree", "-r", "-z", "--name-only", revision, "--", &dir_arg])?;
+    let mut templates = Vec::new();
+    for path in listing.split(|byte| *byte == 0).filter(|path| !path.is_empty()) {
+        let path = String::from_utf8_lossy(path).into_owned();
+        let Some(name) = template_name(Path::new(&path), &dir) else {
+            continue;
+        };
+        let contents = git(&["show", &format!("{revision}:./{path}")])?;
+        let contents = String::from_utf8(contents)
+            .with_context(|| format!("Template {path} at {revision} isn't valid UTF-8"))?;
+        templates.push(parse(&name, &contents, default_output)?);
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Print the cost of one request with each template on each model, cheapest model first
+pub fn print_cost(
+    templates: &[Template],
+    models: &[&Model],
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut table = Table::new(["Template", "Input Tokens", "Output Tokens", "Model", "Cost"]);
+    for template in templates {
+        let mut costs = models
+            .iter()
+            .map(|model| {
+                calculate_cost(model, template.input_tokens, template.output_tokens, None, 5)
+                    .map(|cost| (model, cost.total_cost))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        costs.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        for (model, cost) in costs {
+            table.push(vec![
+                Cell::text(&template.name),
+                Cell::Number(template.input_tokens as f64, 0),
+                Cell::Number(template.output_tokens as f64, 0),
+                model_link(&model.id),
+                Cell::Cost(cost),
+            ]);
+        }
+    }
+
+    let heading = format!(
+        "Prompt costs: {} templates across {} models, per request",
+        templates.len(),
+        models.len()
+    );
+    let note = format!(
+        "Input tokens are estimated at {CHARS_PER_TOKEN} characters per token; exact counts \
+         depend on each model's tokenizer."
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
+
+/// Compare the per-request cost of each template that changed between two revisions, on each
+/// model. Unchanged templates are only counted, to keep the output short enough for a pull
+/// request comment. Returns whether any template's cost rose by more than `max_increase`
+/// percent on some model.
+pub fn print_diff(
+    (base_revision, base): (&str, &[Template]),
+    (head_revision, head): (&str, &[Template]),
+    models: &[&Model],
+    max_increase: Option<f64>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<bool> {
+    let mut pairs: BTreeMap<&str, (Option<&Template>, Option<&Template>)> = BTreeMap::new();
+    for template in base {
+        pairs.entry(&template.name).or_default().0 = Some(template);
+    }
+    for template in head {
+        pairs.entry(&template.name).or_default().1 = Some(template);
+    }
+
+    let cost = |model: &Model, template: Option<&Template>| {
+        template
+            .map(|template| {
+                calculate_cost(model, template.input_tokens, template.output_tokens, None, 5)
+                    .map(|cost| cost.total_cost)
+            })
+            .transpose()
+    };
+    let tokens = |template: Option<&Template>| {
+        template
+            .map(|template| Cell::Number((template.input_tokens + template.output_tokens) as f64, 0))
+            .unwrap_or(Cell::Missing)
+    };
+
+    let mut table = Table::new([
+        "Template",
+        "Status",
+        "Base Tokens",
+        "Head Tokens",
+        "Model",
+        "Base Cost",
+        "Head Cost",
+        "Change",
+        "Change %",
+    ]);
+    let mut unchanged = 0;
+    let mut total_change = 0.0;
+    let mut violations = Vec::new();
+    for (name, (old, new)) in &pairs {
+        let status = match (old, new) {
+            (Some(old), Some(new))
+                if old.input_tokens == new.input_tokens && old.output_tokens == new.output_tokens =>
+            {
+                unchanged += 1;
+                continue;
+            }
+            (Some(_), Some(_)) => "changed",
+            (None, _) => "added",
+            (_, None) => "removed",
+        };
+        for model in models {
+            let old_cost = cost(model, *old)?;
+            let new_cost = cost(model, *new)?;
+            let change = new_cost.unwrap_or(0.0) - old_cost.unwrap_or(0.0);
+            total_change += change;
+            let percent = match (old_cost, new_cost) {
+                (Some(old_cost), Some(_)) if old_cost > 0.0 => Some(change / old_cost * 100.0),
+                _ => None,
+            };
+            if let (Some(percent), Some(max_increase)) = (percent, max_increase) {
+                if percent > max_increase {
+                    violations.push(format!(
+                        "{name} costs {percent:.1}% more per request on {}, over --max-increase {max_increase}%",
+                        model.id
+                    ));
+                }
+            }
+            table.push(vec![
+                Cell::text(*name),
+                Cell::text(status),
+                tokens(*old),
+                tokens(*new),
+                model_link(&model.id),
+                old_cost.map_or(Cell::Missing, Cell::Cost),
+                new_cost.map_or(Cell::Missing, Cell::Cost),
+                Cell::Cost(change),
+                percent.map_or(Cell::Missing, |percent| Cell::Number(percent, 1)),
+            ]);
+        }
+    }
+
+    let changed = pairs.len() - unchanged;
+    if format == OutputFormat::GithubSummary {
+        github::set_output("changed", changed)?;
+        github::set_output("cost-change", format!("{total_change:.6}"))?;
+        github::set_output("violations", violations.len())?;
+    }
+    let heading = format!("Prompt cost impact: {base_revision}..{head_revision}");
+    if changed == 0 {
+        let message = format!("No prompt templates changed ({unchanged} unchanged)");
+        if format == OutputFormat::GithubSummary {
+            github::summary(&format!("### {heading}\n\n{message}\n"))?;
+        } else if format == OutputFormat::Markdown {
+            println!("### {heading}\n\n{message}");
+        } else {
+            println!("{message}");
+        }
+        return Ok(false);
+    }
+
+    let direction = if total_change > 0.0 { "more" } else { "less" };
+    let note = format!(
+        "{changed} templates changed and {unchanged} unchanged. Summed over the models shown, \
+         one request with each changed template costs ${:.6} {direction} than before. Input \
+         tokens are estimated at {CHARS_PER_TOKEN} characters per token.",
+        total_change.abs()
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)?;
+
+    for violation in &violations {
+        if format == OutputFormat::GithubSummary {
+            github::error("Prompt cost increase", violation);
+        } else {
+            eprintln!("{violation}");
+        }
+    }
+    Ok(!violations.is_empty())
+}
+
+/// Check each template's per-request cost on `model` against its `max_cost`, or
+/// `default_budget` for templates that don't declare one. Returns whether any is over budget.
+pub fn check_budgets(
+    templates: &[Template],
+    model: &Model,
+    default_budget: Option<f64>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<bool> {
+    let mut table = Table::new([
+        "Template",
+        "Input Tokens",
+        "Output Tokens",
+        "Cost",
+        "Budget",
+        "Status",
+    ]);
+    let mut violations = Vec::new();
+    for template in templates {
+        let cost =
+            calculate_cost(model, template.input_tokens, template.output_tokens, None, 5)?.total_cost;
+        let budget = template.max_cost.or(default_budget);
+        let status = match budget {
+            Some(budget) if cost > budget => {
+                violations.push(format!(
+                    "{} costs ${cost:.6} per request on {}, over its ${budget} budget",
+                    template.name, model.id
+                ));
+                "over budget"
+            }
+            Some(_) => "ok",
+            None => "no budget",
+        };
+        table.push(vec![
+            Cell::text(&template.name),
+            Cell::Number(template.input_tokens as f64, 0),
+            Cell::Number(template.output_tokens as f64, 0),
+            Cell::Cost(cost),
+            budget.map_or(Cell::Missing, Cell::Cost),
+            Cell::text(status),
+        ]);
+    }
+
+    if format == OutputFormat::GithubSummary {
+        github::set_output("violations", violations.len())?;
+    }
+    let heading = format!(
+        "Prompt cost budgets on {}: {} of {} templates over budget",
+        model.id,
+        violations.len(),
+        templates.len()
+    );
+    let note = format!(
+        "Cost of one request with each template. Input tokens are estimated at \
+         {CHARS_PER_TOKEN} characters per token."
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)?;
+
+    for violation in &violations {
+        if format == OutputFormat::GithubSummary {
+            github::error("Prompt over budget", violation);
+        } else {
+            eprintln!("{violation}");
+        }
+    }
+    Ok(!violations.is_empty())
+}
@@ -0,0 +1,323 @@
+use crate::{
+    auth, html,
+    table::{self, Table},
+    SourceInfo,
+};
+use anyhow::Context;
+use chrono::TimeDelta;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1/";
+
+/// API version the requests below are written against
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Notion's limit on pages per query response
+const NOTION_PAGE_SIZE: usize = 100;
+
+#[derive(Deserialize)]
+struct NotionDatabase {
+    properties: BTreeMap<String, NotionProperty>,
+}
+
+#[derive(Deserialize)]
+struct NotionProperty {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct NotionQuery {
+    results: Vec<NotionPage>,
+    has_more: bool,
+    next_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotionPage {
+    id: String,
+    properties: BTreeMap<String, Value>,
+}
+
+/// The token stored with `llm-pricing auth set <service>`, or from its environment variable
+fn token(service: &str) -> anyhow::Result<String> {
+    auth::api_key(service)?.map(|(key, _)| key).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No {} token. Store one with 'llm-pricing auth set {}', or set {}",
+            service,
+            service,
+            auth::env_var(service)
+        )
+    })
+}
+
+/// Notion property types that table cells can fill
+fn is_supported(kind: &str) -> bool {
+    matches!(kind, "title" | "rich_text" | "number" | "select")
+}
+
+/// A table cell as the value of a Notion property of the given type, or None for types that
+/// can't hold it
+fn notion_value(kind: &str, value: &Value) -> Option<Value> {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let rich_text = || json!([{ "type": "text", "text": { "content": text } }]);
+    match kind {
+        "title" => Some(json!({ "title": rich_text() })),
+        "rich_text" => Some(json!({ "rich_text": rich_text() })),
+        "number" => Some(json!({ "number": value.as_f64() })),
+        "select" if text.is_empty() => Some(json!({ "select": null })),
+        "select" => Some(json!({ "select": { "name": text } })),
+        _ => None,
+    }
+}
+
+fn notion_request(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    path: &str,
+    token: &str,
+) -> reqwest::RequestBuilder {
+    client
+        .request(method, format!("{NOTION_API_URL}{path}"))
+        .bearer_auth(token)
+        .header("Notion-Version", NOTION_VERSION)
+}
+
+/// Send a Notion request, explaining the usual cause when the database can't be reached
+async fn notion_send(request: reqwest::RequestBuilder, database: &str) -> anyhow::Result<Value> {
+    let response = request.send().await?;
+    if matches!(
+        response.status(),
+        reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::FORBIDDEN
+    ) {
+        return Err(anyhow::anyhow!(
+            "Couldn't open Notion database {}. Check the ID, and add the integration to the \
+             database with its '...' menu > Connections",
+            database
+        ));
+    }
+    response
+        .error_for_status()?
+        .json()
+        .await
+        .context("Unexpected response from the Notion API")
+}
+
+/// Add or update a page per table row in a Notion database, matching rows to pages by the
+/// database's title property, which gets the first column. Other columns fill properties of
+/// the same name. With `prune`, pages for rows that are no longer in the table are archived.
+pub async fn notion(table: &Table, database: &str, prune: bool) -> anyhow::Result<()> {
+    let token = token("notion")?;
+    let client = reqwest::Client::new();
+
+    let schema: NotionDatabase = serde_json::from_value(
+        notion_send(
+            notion_request(&client, reqwest::Method::GET, &format!("databases/{database}"), &token),
+            database,
+        )
+        .await?,
+    )
+    .context("Unexpected response from the Notion API")?;
+    let title = schema
+        .properties
+        .iter()
+        .find(|(_, property)| property.kind == "title")
+        .map(|(name, _)| name.clone())
+        .ok_or_else(|| anyhow::anyhow!("Notion database {} has no title property", database))?;
+
+    // Each column's property name and type, with the first column going to the title
+    let mut columns = Vec::new();
+    let mut unmatched = Vec::new();
+    for (i, header) in table.headers.iter().enumerate() {
+        let property = if i == 0 {
+            Some((title.clone(), "title".to_string()))
+        } else {
+            schema
+                .properties
+                .iter()
+                .find(|(name, property)| {
+                    name.eq_ignore_ascii_case(header) && is_supported(&property.kind)
+                })
+                .map(|(name, property)| (name.clone(), property.kind.clone()))
+        };
+        if property.is_none() {
+            unmatched.push(header.as_str());
+        }
+        columns.push(property);
+    }
+    if !unmatched.is_empty() {
+        eprintln!(
+            "Warning: Notion database {} has no text, number or select properties named {}, so \
+             those columns aren't published",
+            database,
+            unmatched.join(", ")
+        );
+    }
+
+    let mut existing: BTreeMap<String, String> = BTreeMap::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut body = json!({ "page_size": NOTION_PAGE_SIZE });
+        if let Some(cursor) = &cursor {
+            body["start_cursor"] = json!(cursor);
+        }
+        let request = notion_request(
+            &client,
+            reqwest::Method::POST,
+            &format!("databases/{database}/query"),
+            &token,
+        )
+        .json(&body);
+        let query: NotionQuery = serde_json::from_value(notion_send(request, database).await?)
+            .context("Unexpected response from the Notion API")?;
+        for page in query.results {
+            let text: String = page
+                .properties
+                .get(&title)
+                .and_then(|property| property["title"].as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|part| part["plain_text"].as_str())
+                .collect();
+            existing.insert(text, page.id);
+        }
+        match query.next_cursor.filter(|_| query.has_more) {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let (mut added, mut updated) = (0, 0);
+    let mut published = Vec::new();
+    // Footer rows such as --summary statistics aren't models
+    for row in table.sheet_values().into_iter().skip(1).take(table.rows.len()) {
+        let mut properties = Map::new();
+        for (value, column) in row.iter().zip(&columns) {
+            if let Some((name, kind)) = column {
+                if let Some(value) = notion_value(kind, value) {
+                    properties.insert(name.clone(), value);
+                }
+            }
+        }
+        let key = match &row[0] {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        let request = match existing.get(&key) {
+            Some(page) => {
+                updated += 1;
+                notion_request(&client, reqwest::Method::PATCH, &format!("pages/{page}"), &token)
+                    .json(&json!({ "properties": properties }))
+            }
+            None => {
+                added += 1;
+                notion_request(&client, reqwest::Method::POST, "pages", &token).json(&json!({
+                    "parent": { "database_id": database },
+                    "properties": properties,
+                }))
+            }
+        };
+        notion_send(request, database).await?;
+        published.push(key);
+    }
+
+    let mut archived = 0;
+    if prune {
+        for (key, page) in &existing {
+            if !published.contains(key) {
+                let request =
+                    notion_request(&client, reqwest::Method::PATCH, &format!("pages/{page}"), &token)
+                        .json(&json!({ "archived": true }));
+                notion_send(request, database).await?;
+                archived += 1;
+            }
+        }
+    }
+
+    println!("Published to Notion database {database}: {added} added, {updated} updated, {archived} archived");
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ConfluencePage {
+    title: String,
+    version: ConfluenceVersion,
+}
+
+#[derive(Deserialize)]
+struct ConfluenceVersion {
+    number: u64,
+}
+
+/// Replace the body of a Confluence page with the table under a line saying where the prices
+/// came from, as a new version of the page. With `user`, the token is an Atlassian Cloud API
+/// token; without, a Confluence Server or Data Center personal access token.
+pub async fn confluence(
+    table: &Table,
+    site: &str,
+    page: &str,
+    user: Option<&str>,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let token = token("confluence")?;
+    let client = reqwest::Client::new();
+    let url = format!("{}/rest/api/content/{page}", site.trim_end_matches('/'));
+    let authorize = |request: reqwest::RequestBuilder| match user {
+        Some(user) => request.basic_auth(user, Some(&token)),
+        None => request.bearer_auth(&token),
+    };
+
+    let response = authorize(client.get(&url).query(&[("expand", "version")]))
+        .send()
+        .await?;
+    if matches!(
+        response.status(),
+        reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::FORBIDDEN
+    ) {
+        return Err(anyhow::anyhow!(
+            "Couldn't open Confluence page {} on {}. Check the page ID, and that the token's \
+             user can edit it",
+            page,
+            site
+        ));
+    }
+    let current: ConfluencePage = response
+        .error_for_status()?
+        .json()
+        .await
+        .context("Unexpected response from the Confluence API")?;
+
+    let unit = table::price_unit().describe();
+    let body = format!(
+        "<p>Prices in USD per {} from {}. This page is updated by llm-pricing; edits will be \
+         replaced.</p>\n{}",
+        unit,
+        html::escape(&source.describe(stale_after)),
+        table.to_html()
+    );
+    let version = current.version.number + 1;
+    authorize(client.put(&url))
+        .json(&json!({
+            "id": page,
+            "type": "page",
+            "title": current.title,
+            "version": { "number": version, "message": "Updated prices" },
+            "body": { "storage": { "value": body, "representation": "storage" } },
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    println!(
+        "Published {} rows to Confluence page '{}' (version {version})",
+        table.rows.len(),
+        current.title
+    );
+    Ok(())
+}
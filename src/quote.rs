@@ -0,0 +1,315 @@
+use crate::{
+    drawing::{self, Drawing},
+    i18n, model_link, price_header, report,
+    table::{self, Cell, Table},
+    CalcRow, Metadata, Model, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use plotters::{coord::Shift, prelude::*};
+use std::{fs, path::Path};
+
+/// Font sizes for the title and the rest of a PNG quote, the room each takes, and the
+/// margin around them, in pixels
+const TITLE_SIZE: f64 = 26.0;
+const BODY_SIZE: f64 = 15.0;
+const TITLE_HEIGHT: u32 = 40;
+const LINE_HEIGHT: u32 = 20;
+const PNG_MARGIN: u32 = 32;
+
+/// Narrowest PNG quote, in body characters, so short quotes still have room for the footer
+const MIN_PNG_CHARS: usize = 60;
+
+const TEXT: RGBColor = RGBColor(33, 37, 41);
+const MUTED: RGBColor = RGBColor(108, 117, 125);
+const ACCENT: RGBColor = RGBColor(13, 71, 161);
+
+/// A quote for some number of requests to one model, priced when it was prepared
+pub struct Quote<'a> {
+    pub model: &'a Model,
+    pub cost: CalcRow,
+    pub requests: u64,
+    pub prepared_at: DateTime<Utc>,
+}
+
+impl Quote<'_> {
+    fn title(&self) -> String {
+        let model = self.model.name.as_deref().unwrap_or(&self.model.id);
+        i18n::t_args("quote-title", &[("model", model.into())])
+    }
+
+    fn prepared(&self) -> String {
+        let date = self.prepared_at.format("%Y-%m-%d %H:%M UTC").to_string();
+        i18n::t_args("quote-prepared", &[("date", date.into())])
+    }
+
+    fn footer(&self, source: &SourceInfo) -> String {
+        i18n::t_args(
+            "quote-footer",
+            &[
+                ("unit", i18n::price_unit().into()),
+                ("source", source.name.into()),
+                ("fetched", source.fetched_at.format("%Y-%m-%d %H:%M UTC").to_string().into()),
+            ],
+        )
+    }
+
+    /// What the model is, from the catalog and any notes or lifecycle data about it
+    fn details_table(&self, metadata: &Metadata) -> Table {
+        let model = self.model;
+        let tokens = |tokens: Option<u64>| {
+            tokens.map_or(i18n::t("unknown"), |tokens| {
+                i18n::t_args("tokens", &[("tokens", table::format_number(tokens as f64, 0).into())])
+            })
+        };
+        let label = |id: &str| Cell::text(i18n::t(id));
+        let mut table = Table::new([i18n::t("col-detail"), i18n::t("col-value")]);
+        table.push(vec![label("col-model"), model_link(&model.id)]);
+        if let Some(name) = &model.name {
+            table.push(vec![label("col-name"), Cell::text(name)]);
+        }
+        table.push(vec![
+            label("col-provider"),
+            Cell::text(model.id.split('/').next().unwrap_or("unknown")),
+        ]);
+        table.push(vec![label("col-context"), Cell::text(tokens(model.context_length))]);
+        table.push(vec![
+            label("detail-max-completion"),
+            Cell::text(tokens(
+                model
+                    .top_provider
+                    .as_ref()
+                    .and_then(|provider| provider.max_completion_tokens),
+            )),
+        ]);
+        if let Some(modality) = model.architecture.as_ref().and_then(|arch| arch.modality.as_ref()) {
+            table.push(vec![label("col-modality"), Cell::text(modality)]);
+        }
+        table.push(vec![label("col-license"), Cell::text(metadata.license(model))]);
+        if let Some(entry) = metadata.lifecycle.get(&model.id) {
+            table.push(vec![label("col-status"), Cell::text(entry.describe(metadata.today))]);
+        }
+        if let Some(note) = metadata.note(model) {
+            table.push(vec![label("detail-note"), Cell::text(note)]);
+        }
+        table
+    }
+
+    /// Line items for one request, then the totals
+    fn cost_table(&self) -> Table {
+        let cost = &self.cost;
+        let mut table = Table::new([
+            i18n::t("col-item"),
+            i18n::t("col-tokens"),
+            price_header(&i18n::t("col-rate")),
+            i18n::t("col-cost"),
+        ]);
+        let cache_write = match cost.cache_write_multiplier {
+            Some(multiplier) => i18n::t_args(
+                "item-cache-write-multiplier",
+                &[("multiplier", multiplier.to_string().into())],
+            ),
+            None => i18n::t("item-cache-write"),
+        };
+        let items = [
+            (i18n::t("col-input"), &cost.input),
+            (i18n::t("item-cache-read"), &cost.cache_read),
+            (cache_write, &cost.cache_write),
+            (i18n::t("col-output"), &cost.output),
+        ];
+        for (label, component) in items {
+            if component.tokens > 0 {
+                table.push(vec![
+                    Cell::text(label),
+                    Cell::Number(component.tokens as f64, 0),
+                    Cell::Price(component.rate),
+                    Cell::Cost(component.cost),
+                ]);
+            }
+        }
+        let blank = || Cell::text("");
+        table.push(vec![
+            Cell::text(i18n::t("quote-total")),
+            blank(),
+            blank(),
+            Cell::Cost(cost.total_cost),
+        ]);
+        if self.requests > 1 {
+            table.push(vec![
+                Cell::text(i18n::t_args(
+                    "quote-total-requests",
+                    &[("requests", table::format_number(self.requests as f64, 0).into())],
+                )),
+                blank(),
+                blank(),
+                Cell::Cost(cost.total_cost * self.requests as f64),
+            ]);
+        }
+        table
+    }
+
+    fn markdown(&self, metadata: &Metadata, source: &SourceInfo) -> String {
+        format!(
+            "## {}\n\n_{}_\n\n{}\n{}\n_{}_\n",
+            self.title(),
+            self.prepared(),
+            self.details_table(metadata).to_markdown(),
+            self.cost_table().to_markdown(),
+            self.footer(source)
+        )
+    }
+
+    /// The quote as an image, with the tables laid out as monospaced text the way the PDF
+    /// rate card does
+    fn image(&self, metadata: &Metadata, source: &SourceInfo) -> QuoteImage {
+        let (details_header, details) = report::text_lines(&self.details_table(metadata));
+        let (cost_header, costs) = report::text_lines(&self.cost_table());
+        let chars = details_header
+            .iter()
+            .chain(&details)
+            .chain(&cost_header)
+            .chain(&costs)
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(MIN_PNG_CHARS);
+        QuoteImage {
+            title: self.title(),
+            prepared: self.prepared(),
+            tables: vec![(details_header, details), (cost_header, costs)],
+            footer: wrap(&self.footer(source), chars),
+        }
+    }
+}
+
+/// The lines of a quote to draw, with each table's header lines apart from its rows
+struct QuoteImage {
+    title: String,
+    prepared: String,
+    tables: Vec<(Vec<String>, Vec<String>)>,
+    footer: Vec<String>,
+}
+
+impl QuoteImage {
+    fn lines(&self) -> impl Iterator<Item = &String> {
+        self.tables
+            .iter()
+            .flat_map(|(header, rows)| header.iter().chain(rows))
+            .chain(&self.footer)
+            .chain([&self.prepared])
+    }
+}
+
+impl Drawing for QuoteImage {
+    fn size(&self) -> (u32, u32) {
+        let body = drawing::font(FontFamily::Monospace, BODY_SIZE);
+        let title = drawing::font(FontFamily::SansSerif, TITLE_SIZE);
+        let width = self
+            .lines()
+            .map(|line| drawing::text_width(&body, line))
+            .chain([
+                drawing::text_width(&body, &"0".repeat(MIN_PNG_CHARS)),
+                drawing::text_width(&title, &self.title),
+            ])
+            .max()
+            .unwrap_or(0);
+        // The prepared line and a gap, the tables with a gap after each, the footer, and a
+        // blank line to finish
+        let body_lines = 2
+            + self
+                .tables
+                .iter()
+                .map(|(header, rows)| header.len() + rows.len() + 1)
+                .sum::<usize>()
+            + self.footer.len()
+            + 1;
+        let height = TITLE_HEIGHT + LINE_HEIGHT * body_lines as u32 + 2 * PNG_MARGIN;
+        (width + 2 * PNG_MARGIN, height)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, area: &DrawingArea<DB, Shift>) -> anyhow::Result<()>
+    where
+        DB::ErrorType: 'static,
+    {
+        let body = drawing::font(FontFamily::Monospace, BODY_SIZE);
+        let title = drawing::font(FontFamily::SansSerif, TITLE_SIZE).style(FontStyle::Bold);
+        let (width, _) = self.size();
+        let left = PNG_MARGIN as i32;
+        let line_height = LINE_HEIGHT as i32;
+
+        area.fill(&WHITE)?;
+        area.draw(&Rectangle::new(
+            [(0, 0), (width as i32, (PNG_MARGIN / 4) as i32)],
+            ACCENT.filled(),
+        ))?;
+        let mut y = PNG_MARGIN as i32;
+        area.draw(&Text::new(self.title.as_str(), (left, y), title.color(&ACCENT)))?;
+        y += TITLE_HEIGHT as i32;
+        // Each line's text and color, with None for the gaps
+        let mut lines = vec![Some((&self.prepared, MUTED)), None];
+        for (header, rows) in &self.tables {
+            lines.extend(header.iter().map(|text| Some((text, MUTED))));
+            lines.extend(rows.iter().map(|text| Some((text, TEXT))));
+            lines.push(None);
+        }
+        lines.extend(self.footer.iter().map(|text| Some((text, MUTED))));
+        for line in lines {
+            if let Some((text, color)) = line {
+                area.draw(&Text::new(text.clone(), (left, y), body.color(&color)))?;
+            }
+            y += line_height;
+        }
+        Ok(())
+    }
+}
+
+/// Break text into lines of at most `width` characters at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// Print the quote as Markdown, or write it to a file as Markdown or PNG depending on the
+/// extension
+pub fn write(
+    quote: &Quote,
+    metadata: &Metadata,
+    source: &SourceInfo,
+    path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let Some(path) = path else {
+        print!("{}", quote.markdown(metadata, source));
+        return Ok(());
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("md" | "markdown") => fs::write(path, quote.markdown(metadata, source))
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        // The bundled font has no CJK glyphs, so Japanese quotes are drawn in English
+        Some("png") => drawing::save(
+            &i18n::latin_only(|| quote.image(metadata, source)),
+            path,
+            drawing::Format::Png,
+        )?,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported quote file '{}'. Use a .md or .png extension",
+                path.display()
+            ))
+        }
+    }
+    println!("Wrote quote for {} to {}", quote.model.id, path.display());
+    Ok(())
+}
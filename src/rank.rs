@@ -0,0 +1,103 @@
+use crate::{
+    benchmarks::{Benchmark, Benchmarks},
+    cheapest::Mix,
+    model_link, price_header, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+
+/// What models are scored on and which make the cut
+pub struct Ranking {
+    pub benchmark: Benchmark,
+    pub mix: Mix,
+    /// Leave out models scoring below this
+    pub min_score: Option<f64>,
+    /// How many models to list; all of them when None
+    pub count: Option<usize>,
+}
+
+/// Print models ranked by benchmark points per dollar of a million blended tokens, best value
+/// first. Models without a score for the benchmark, or that are free, can't be ranked and are
+/// counted in the note.
+pub fn print(
+    models: &[&Model],
+    benchmarks: &Benchmarks,
+    ranking: &Ranking,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let (mut unscored, mut free) = (0, 0);
+    let mut ranked = Vec::new();
+    for model in models {
+        let Some(score) = benchmarks
+            .lookup(&model.id)
+            .and_then(|scores| scores.get(ranking.benchmark))
+        else {
+            unscored += 1;
+            continue;
+        };
+        if ranking.min_score.is_some_and(|min_score| score < min_score) {
+            continue;
+        }
+        let price = |price: &str| price.parse::<f64>().ok().filter(|price| *price >= 0.0);
+        let (Some(input), Some(output)) = (price(&model.pricing.prompt), price(&model.pricing.completion)) else {
+            continue;
+        };
+        let blended = ranking.mix.blend(input, output);
+        if blended == 0.0 {
+            free += 1;
+            continue;
+        }
+        ranked.push((*model, score, blended, score / (blended * 1_000_000.0)));
+    }
+    ranked.sort_by(|a, b| b.3.total_cmp(&a.3).then_with(|| a.0.id.cmp(&b.0.id)));
+    if let Some(count) = ranking.count {
+        ranked.truncate(count);
+    }
+    if ranked.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No matching models have a {} score to rank by",
+            ranking.benchmark.label()
+        ));
+    }
+
+    let label = ranking.benchmark.label();
+    let mut table = Table::new([
+        "#".to_string(),
+        "Model".to_string(),
+        label.to_string(),
+        price_header(&format!("Blended {}", ranking.mix)),
+        "Points/$".to_string(),
+    ]);
+    for (rank, (model, score, blended, value)) in ranked.iter().enumerate() {
+        table.push(vec![
+            Cell::Number((rank + 1) as f64, 0),
+            model_link(&model.id),
+            Cell::Number(*score, 1),
+            Cell::Price(*blended),
+            Cell::Number(*value, 1),
+        ]);
+    }
+
+    let mut note = format!(
+        "Points/$: {label} points per USD of 1M tokens at {} input to output tokens. Scores are \
+         as published by each model's developer; --benchmarks-file adds or corrects them.",
+        ranking.mix
+    );
+    if unscored > 0 {
+        note.push_str(&format!(" {unscored} model(s) without a {label} score left out."));
+    }
+    if free > 0 {
+        note.push_str(&format!(" {free} free model(s) left out."));
+    }
+    print_titled_table(
+        &format!("Models by {label} points per dollar"),
+        &table,
+        Some(&note),
+        format,
+        source,
+        stale_after,
+    )
+}
@@ -0,0 +1,93 @@
+use crate::prefix_map::PrefixMap;
+use anyhow::Context;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
+
+/// Published rate limits at each provider's entry paid tier, keyed by model ID. Keys ending
+/// in `*` match any ID with that prefix; exact keys take precedence, then the longest prefix.
+const BUNDLED_RATE_LIMITS: &str = include_str!("../data/rate_limits.json");
+
+/// Limits for one API key. Providers limit either input and output tokens separately or
+/// all tokens together, so any of the token limits may be absent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimit {
+    /// The account tier these limits apply to
+    #[serde(default)]
+    pub tier: Option<String>,
+    /// Requests per minute
+    pub rpm: u64,
+    /// Input and output tokens per minute combined
+    #[serde(default)]
+    pub tpm: Option<u64>,
+    /// Input tokens per minute
+    #[serde(default)]
+    pub input_tpm: Option<u64>,
+    /// Output tokens per minute
+    #[serde(default)]
+    pub output_tpm: Option<u64>,
+}
+
+impl RateLimit {
+    /// Number of keys (or accounts) needed to sustain a request rate, given per-request tokens
+    pub fn keys_needed(&self, requests_per_second: f64, input: u64, output: u64) -> u64 {
+        let per_minute = requests_per_second * 60.0;
+        let needed = |demand: f64, limit: Option<u64>| match limit {
+            Some(limit) if limit > 0 => (demand / limit as f64).ceil(),
+            Some(_) => f64::INFINITY,
+            None => 0.0,
+        };
+
+        [
+            needed(per_minute, Some(self.rpm)),
+            needed(per_minute * (input + output) as f64, self.tpm),
+            needed(per_minute * input as f64, self.input_tpm),
+            needed(per_minute * output as f64, self.output_tpm),
+        ]
+        .into_iter()
+        .fold(1.0, f64::max)
+        .min(u64::MAX as f64) as u64
+    }
+
+    /// Short description of the token limits, e.g. "30000 in / 8000 out"
+    pub fn describe_tokens(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(tpm) = self.tpm {
+            parts.push(tpm.to_string());
+        }
+        if let Some(input_tpm) = self.input_tpm {
+            parts.push(format!("{input_tpm} in"));
+        }
+        if let Some(output_tpm) = self.output_tpm {
+            parts.push(format!("{output_tpm} out"));
+        }
+        if parts.is_empty() {
+            "unlimited".to_string()
+        } else {
+            parts.join(" / ")
+        }
+    }
+}
+
+pub struct RateLimits(PrefixMap<RateLimit>);
+
+impl RateLimits {
+    /// Load the bundled rate limits, with entries from `override_path` taking precedence
+    pub fn load(override_path: Option<&Path>) -> anyhow::Result<Self> {
+        let mut entries: HashMap<String, RateLimit> =
+            serde_json::from_str(BUNDLED_RATE_LIMITS).context("Invalid bundled rate limit data")?;
+
+        if let Some(path) = override_path {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read rate limits file {}", path.display()))?;
+            let overrides: HashMap<String, RateLimit> = serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid rate limits file {}", path.display()))?;
+            entries.extend(overrides);
+        }
+
+        Ok(Self(PrefixMap::new(entries)))
+    }
+
+    pub fn lookup(&self, model_id: &str) -> Option<&RateLimit> {
+        self.0.lookup(model_id)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{endpoints, Model};
+use anyhow::Context;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Where OpenRouter providers serve requests from, keyed by provider name or, for providers
+/// with regional endpoints, by endpoint tag (e.g. "google-vertex/europe"). Providers missing
+/// here have unknown residency.
+const BUNDLED_REGIONS: &str = include_str!("../data/regions.json");
+
+/// Regions each provider serves from, keyed by lowercased provider name or endpoint tag
+pub struct Regions(BTreeMap<String, Vec<String>>);
+
+impl Regions {
+    /// The bundled map, with `overrides` from the config file replacing its entries
+    pub fn bundled(overrides: &BTreeMap<String, Vec<String>>) -> anyhow::Result<Self> {
+        let entries: BTreeMap<String, Vec<String>> =
+            serde_json::from_str(BUNDLED_REGIONS).context("Invalid bundled region data")?;
+        // Provider names and regions are matched ignoring case
+        let lowercase = |(key, regions): (&String, &Vec<String>)| {
+            let regions = regions.iter().map(|region| region.to_lowercase()).collect();
+            (key.to_lowercase(), regions)
+        };
+        let mut map: BTreeMap<String, Vec<String>> = entries.iter().map(lowercase).collect();
+        map.extend(overrides.iter().map(lowercase));
+        Ok(Self(map))
+    }
+
+    /// Fail on a --region that no provider serves from, listing the ones that exist
+    pub fn check_known(&self, regions: &[String]) -> anyhow::Result<()> {
+        let known: BTreeSet<&str> = self.0.values().flatten().map(String::as_str).collect();
+        for region in regions {
+            if !known.contains(region.to_lowercase().as_str()) {
+                let known: Vec<&str> = known.into_iter().collect();
+                return Err(anyhow::anyhow!(
+                    "Unknown region '{}'. Regions are: {}",
+                    region,
+                    known.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Where the providers serving each of `models` run, from their OpenRouter endpoints.
+    /// Models whose endpoints can't be fetched are left out.
+    pub async fn residency(&self, models: &[&Model]) -> BTreeMap<String, Residency> {
+        let ids = models.iter().map(|model| model.id.clone()).collect();
+        let mut residency = BTreeMap::new();
+        for (id, endpoints) in endpoints::fetch_all(ids).await {
+            let mut entry = Residency::default();
+            for endpoint in &endpoints {
+                let regions = endpoint
+                    .tag()
+                    .and_then(|tag| self.0.get(&tag.to_lowercase()))
+                    .or_else(|| self.0.get(&endpoint.provider_name().to_lowercase()));
+                match regions {
+                    Some(regions) => entry.regions.extend(regions.iter().cloned()),
+                    None => entry.unknown.push(endpoint.provider()),
+                }
+            }
+            residency.insert(id, entry);
+        }
+        residency
+    }
+}
+
+/// Where the providers serving a model run
+#[derive(Debug, Default)]
+pub struct Residency {
+    /// Regions of providers in the region map
+    pub regions: BTreeSet<String>,
+    /// Providers missing from the region map
+    pub unknown: Vec<String>,
+}
+
+impl Residency {
+    /// Whether a provider known to run in one of `regions` serves the model
+    pub fn served_from(&self, regions: &[String]) -> bool {
+        regions
+            .iter()
+            .any(|region| self.regions.contains(&region.to_lowercase()))
+    }
+}
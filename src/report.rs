@@ -0,0 +1,259 @@
+use crate::{
+    html, i18n,
+    table::{self, Cell, Table},
+    Metadata, Model, SourceInfo,
+};
+use anyhow::Context;
+use std::{fs, path::Path};
+
+/// Build the rate card table: prices per unit, context and notes for each model
+fn rate_card_table(models: &[&Model], metadata: &Metadata) -> Table {
+    let unit = table::price_unit().short();
+    let per_unit = |id: &str| format!("{} /{unit}", i18n::t(id));
+    let mut table = Table::new([
+        i18n::t("col-model"),
+        per_unit("col-input"),
+        per_unit("col-output"),
+        per_unit("col-cache-read"),
+        per_unit("col-cache-write"),
+        i18n::t("col-context"),
+        i18n::t("col-notes"),
+    ]);
+
+    for model in models {
+        let optional_price = |price: &Option<String>| {
+            price.as_deref().map(Cell::price_str).unwrap_or(Cell::Missing)
+        };
+        let notes = metadata
+            .lifecycle
+            .get(&model.id)
+            .map(|entry| entry.describe(metadata.today))
+            .into_iter()
+            .chain(metadata.note(model).map(str::to_string))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        table.push(vec![
+            Cell::text(&model.id),
+            Cell::price_str(&model.pricing.prompt),
+            Cell::price_str(&model.pricing.completion),
+            optional_price(&model.pricing.input_cache_read),
+            optional_price(&model.pricing.input_cache_write),
+            model
+                .context_length
+                .map(|tokens| Cell::Number(tokens as f64, 0))
+                .unwrap_or(Cell::Missing),
+            Cell::text(notes),
+        ]);
+    }
+
+    table
+}
+
+/// Write a printable rate card, as HTML or PDF depending on the file extension
+pub fn write_rate_card(
+    models: &[&Model],
+    metadata: &Metadata,
+    source: &SourceInfo,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let text = || {
+        let generated = i18n::t_args(
+            "generated",
+            &[("date", metadata.today.format("%Y-%m-%d").to_string().into())],
+        );
+        let footer = i18n::t_args(
+            "rate-card-footer",
+            &[
+                ("unit", i18n::price_unit().into()),
+                ("source", source.name.into()),
+                ("fetched", source.fetched_at.format("%Y-%m-%d %H:%M UTC").to_string().into()),
+            ],
+        );
+        (rate_card_table(models, metadata), generated, footer)
+    };
+    let contents = match extension.as_deref() {
+        Some("html" | "htm") => {
+            let (table, generated, footer) = text();
+            html::page(&i18n::t("rate-card-title"), &[generated], &table, Some(&footer)).into_bytes()
+        }
+        Some("pdf") => i18n::latin_only(|| {
+            let (table, generated, footer) = text();
+            rate_card_pdf(&table, &generated, &footer)
+        }),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported report file '{}'. Use a .pdf or .html extension",
+                path.display()
+            ))
+        }
+    };
+
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// A4 landscape, in points
+const PAGE_WIDTH: f64 = 842.0;
+const PAGE_HEIGHT: f64 = 595.0;
+const MARGIN: f64 = 40.0;
+const FONT_SIZE: f64 = 8.0;
+const LINE_HEIGHT: f64 = 11.0;
+
+/// Characters per line in the monospaced body font, which is 0.6em wide
+const LINE_CHARS: usize = ((PAGE_WIDTH - 2.0 * MARGIN) / (FONT_SIZE * 0.6)) as usize;
+
+/// Escape text for a PDF string literal, replacing characters the standard fonts can't show.
+/// WinAnsiEncoding matches Latin-1 above 0xA0, so accented letters are written as octal
+/// escapes of their code points.
+fn escape_pdf(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{c}"),
+            ' '..='~' => c.to_string(),
+            '\u{a0}'..='\u{ff}' => format!("\\{:03o}", c as u32),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+/// Lay out the table as aligned monospaced text, the same way it prints in the terminal
+pub fn text_lines(table: &Table) -> (Vec<String>, Vec<String>) {
+    let rows: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(Cell::display).collect())
+        .collect();
+    let widths: Vec<usize> = table
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(header.chars().count())
+        })
+        .collect();
+
+    let format_line = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.trim_end().chars().take(LINE_CHARS).collect::<String>()
+    };
+
+    let header = vec![
+        format_line(&table.headers),
+        "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+            .chars()
+            .take(LINE_CHARS)
+            .collect(),
+    ];
+    (header, rows.iter().map(|row| format_line(row)).collect())
+}
+
+/// Render the rate card as a minimal PDF using the standard Helvetica and Courier fonts
+fn rate_card_pdf(table: &Table, generated: &str, footer: &str) -> Vec<u8> {
+    let (header, lines) = text_lines(table);
+    let top = PAGE_HEIGHT - MARGIN;
+    let table_top = top - 44.0;
+    let lines_per_page =
+        (((table_top - MARGIN - 2.0 * LINE_HEIGHT) / LINE_HEIGHT) as usize - header.len()).max(1);
+
+    let chunks: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(lines_per_page).collect()
+    };
+    let page_count = chunks.len();
+
+    let mut streams = Vec::new();
+    for (page, chunk) in chunks.iter().enumerate() {
+        let mut stream = String::new();
+        let mut text = |font: &str, size: f64, y: f64, line: &str| {
+            stream.push_str(&format!(
+                "BT /{font} {size} Tf {MARGIN} {y:.1} Td ({}) Tj ET\n",
+                escape_pdf(line)
+            ));
+        };
+
+        text("F2", 16.0, top - 16.0, &i18n::t("rate-card-title"));
+        text("F1", 9.0, top - 32.0, generated);
+
+        let mut y = table_top;
+        for line in header.iter().chain(chunk.iter()) {
+            text("F3", FONT_SIZE, y, line);
+            y -= LINE_HEIGHT;
+        }
+
+        if page + 1 == page_count {
+            text("F1", 8.0, y - LINE_HEIGHT, footer);
+        }
+        text(
+            "F1",
+            8.0,
+            MARGIN - 16.0,
+            &i18n::t_args(
+                "page-of",
+                &[("page", (page + 1).to_string().into()), ("pages", page_count.to_string().into())],
+            ),
+        );
+        streams.push(stream);
+    }
+
+    // Objects 1-5 are fixed, then each page is a page object followed by its content stream
+    let first_page = 6;
+    let page_refs: Vec<String> = (0..page_count)
+        .map(|i| format!("{} 0 R", first_page + 2 * i))
+        .collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_refs.join(" "),
+            page_count
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    for (i, stream) in streams.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> /Contents {} 0 R >>",
+            first_page + 2 * i + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            stream.len(),
+            stream
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref_offset = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    for offset in offsets {
+        pdf.push_str(&format!("{offset:010} 00000 n \n"));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ));
+    pdf.into_bytes()
+}
@@ -0,0 +1,220 @@
+use crate::{
+    calculate_cost, model_link, output::CalcRequestJson, paths, print_titled_table,
+    service_tier::{ServiceTier, ServiceTiers},
+    table::{Cell, Table},
+    CalcRow, Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// A calc result saved with --save-as, with the inputs needed to re-run it
+#[derive(Serialize, Deserialize)]
+struct SavedResult {
+    saved_at: DateTime<Utc>,
+    source: SavedSource,
+    request: CalcRequestJson,
+    results: Vec<CalcRow>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedSource {
+    name: String,
+    fetched_at: DateTime<Utc>,
+}
+
+fn results_dir() -> anyhow::Result<PathBuf> {
+    Ok(paths::data_dir()?.join("results"))
+}
+
+fn result_file(name: &str) -> anyhow::Result<PathBuf> {
+    // Names become file names, so keep them to characters that are safe everywhere
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(anyhow::anyhow!(
+            "Invalid result name: '{}'. Use letters, digits, '-', '_' and '.'",
+            name
+        ));
+    }
+    Ok(results_dir()?.join(format!("{name}.json")))
+}
+
+/// Save a calc result under `name`, replacing any earlier result with that name
+pub fn save(
+    name: &str,
+    request: CalcRequestJson,
+    rows: &[CalcRow],
+    source: &SourceInfo,
+) -> anyhow::Result<PathBuf> {
+    let path = result_file(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create results directory {}", parent.display()))?;
+    }
+    let saved = SavedResult {
+        saved_at: Utc::now(),
+        source: SavedSource {
+            name: source.name.to_string(),
+            fetched_at: source.fetched_at,
+        },
+        request,
+        results: rows.to_vec(),
+    };
+    fs::write(&path, serde_json::to_string_pretty(&saved)?)
+        .with_context(|| format!("Failed to write result file {}", path.display()))?;
+    Ok(path)
+}
+
+fn load(name: &str) -> anyhow::Result<SavedResult> {
+    let path = result_file(name)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!(
+            "No saved result named '{}'. See 'llm-pricing results list'",
+            name
+        ));
+    }
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read result file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid result file {}", path.display()))
+}
+
+/// Every saved result with its name, in no particular order
+fn load_all() -> anyhow::Result<Vec<(String, SavedResult)>> {
+    let dir = results_dir()?;
+    let mut saved = Vec::new();
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            saved.push((name.to_string(), load(name)?));
+        }
+    }
+    Ok(saved)
+}
+
+/// One model's cost in a saved result
+pub struct SavedCost {
+    pub name: String,
+    pub saved_at: DateTime<Utc>,
+    pub input: u64,
+    pub output: u64,
+    pub model: String,
+    pub total_cost: f64,
+}
+
+/// Every model's cost in every saved result, for querying alongside current prices
+pub fn saved_costs() -> anyhow::Result<Vec<SavedCost>> {
+    Ok(load_all()?
+        .into_iter()
+        .flat_map(|(name, saved)| {
+            saved.results.into_iter().map(move |row| SavedCost {
+                name: name.clone(),
+                saved_at: saved.saved_at,
+                input: saved.request.input,
+                output: saved.request.output,
+                model: row.model,
+                total_cost: row.total_cost,
+            })
+        })
+        .collect())
+}
+
+/// List saved results, newest first
+pub fn list() -> anyhow::Result<()> {
+    let mut saved = load_all()?;
+    if saved.is_empty() {
+        println!("No saved results. Save one with 'llm-pricing calc <INPUT> <OUTPUT> --save-as <NAME>'");
+        return Ok(());
+    }
+    saved.sort_by_key(|(_, result)| std::cmp::Reverse(result.saved_at));
+
+    let mut table = Table::new(["Name", "Saved", "Input", "Output", "Models"]);
+    for (name, result) in &saved {
+        table.push(vec![
+            Cell::text(name),
+            Cell::text(result.saved_at.format("%Y-%m-%d %H:%M UTC").to_string()),
+            Cell::Number(result.request.input as f64, 0),
+            Cell::Number(result.request.output as f64, 0),
+            Cell::Number(result.results.len() as f64, 0),
+        ]);
+    }
+    table.print();
+    Ok(())
+}
+
+/// Re-run a saved result's request against current prices and show how each model's cost
+/// has moved since it was saved
+pub fn diff_live(
+    name: &str,
+    models: Vec<Model>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let saved = load(name)?;
+    let request = &saved.request;
+    let models = ServiceTiers::bundled()?.apply(models, request.tier);
+
+    let mut table = Table::new(["Model", "Saved", "Live", "Change", "Change %"]);
+    let mut unavailable = Vec::new();
+    for row in &saved.results {
+        let Some(model) = models.iter().find(|model| model.id == row.model) else {
+            unavailable.push(row.model.as_str());
+            table.push(vec![
+                Cell::text(&row.model),
+                Cell::Cost(row.total_cost),
+                Cell::Missing,
+                Cell::Missing,
+                Cell::Missing,
+            ]);
+            continue;
+        };
+        let live =
+            calculate_cost(model, request.input, request.output, request.cached, request.ttl)?;
+        let change = live.total_cost - row.total_cost;
+        table.push(vec![
+            model_link(&row.model),
+            Cell::Cost(row.total_cost),
+            Cell::Cost(live.total_cost),
+            Cell::Cost(change),
+            if row.total_cost > 0.0 {
+                Cell::Number(change / row.total_cost * 100.0, 1)
+            } else {
+                Cell::Missing
+            },
+        ]);
+    }
+
+    let heading = format!(
+        "Drift of '{}' ({} input + {} output), saved {} with prices fetched {}",
+        name,
+        request.input,
+        request.output,
+        saved.saved_at.format("%Y-%m-%d %H:%M UTC"),
+        saved.source.fetched_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    let note = (!unavailable.is_empty()).then(|| {
+        format!(
+            "No longer available{}: {}",
+            if request.tier == ServiceTier::Standard {
+                String::new()
+            } else {
+                format!(" at the {} tier", request.tier.as_str())
+            },
+            unavailable.join(", ")
+        )
+    });
+
+    print_titled_table(&heading, &table, note.as_deref(), format, source, stale_after)
+}
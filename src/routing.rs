@@ -0,0 +1,84 @@
+use crate::{
+    calculate_cost, model_link, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+
+/// A two-tier routing strategy: every request goes to the cheap draft model first, and a
+/// share of them are escalated to the expensive verifier model, which runs the request again
+pub struct Routing<'a> {
+    pub draft: &'a Model,
+    pub verifier: &'a Model,
+    pub input: u64,
+    pub output: u64,
+    /// Fraction of requests escalated to the verifier, e.g. 0.2
+    pub escalation_rate: f64,
+}
+
+/// Print the blended cost per request of routing, against each model on its own
+pub fn print(
+    routing: &Routing,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let draft = calculate_cost(routing.draft, routing.input, routing.output, None, 5)?.total_cost;
+    let verifier =
+        calculate_cost(routing.verifier, routing.input, routing.output, None, 5)?.total_cost;
+    let blended = draft + routing.escalation_rate * verifier;
+
+    let mut table = Table::new([
+        "Strategy",
+        "Model",
+        "Cost per Request",
+        "Per 1K Requests",
+        "Savings vs Verifier %",
+    ]);
+    let savings = |cost: f64| {
+        if verifier > 0.0 {
+            Cell::Number((verifier - cost) / verifier * 100.0, 1)
+        } else {
+            Cell::Missing
+        }
+    };
+    let strategies = [
+        (
+            format!("Routed ({:.0}% escalated)", routing.escalation_rate * 100.0),
+            Cell::text(format!("{} + {}", routing.draft.id, routing.verifier.id)),
+            blended,
+        ),
+        ("Draft only".to_string(), model_link(&routing.draft.id), draft),
+        ("Verifier only".to_string(), model_link(&routing.verifier.id), verifier),
+    ];
+    for (strategy, model, cost) in strategies {
+        table.push(vec![
+            Cell::text(strategy),
+            model,
+            Cell::Cost(cost),
+            Cell::Cost(cost * 1000.0),
+            savings(cost),
+        ]);
+    }
+
+    let heading = format!(
+        "Routing estimate: {} input + {} output, {:.0}% of requests escalated",
+        routing.input,
+        routing.output,
+        routing.escalation_rate * 100.0
+    );
+    // Routing costs draft + rate * verifier, which matches the verifier alone when
+    // rate = 1 - draft / verifier
+    let note = if verifier > draft {
+        format!(
+            "Escalated requests pay for both models. Routing stops saving money above a {:.1}% \
+             escalation rate.",
+            (1.0 - draft / verifier) * 100.0
+        )
+    } else {
+        "The draft model costs at least as much as the verifier, so routing never saves money."
+            .to_string()
+    };
+
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
@@ -0,0 +1,155 @@
+use anyhow::Context;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/tekacs/llm-pricing/releases/latest";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Release asset built for the platform this binary is running on
+fn platform_asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        // The musl build is statically linked, so it runs regardless of the system glibc
+        ("linux", "x86_64") => Some("llm-pricing-linux-x86_64-musl"),
+        ("macos", "x86_64") => Some("llm-pricing-macos-x86_64"),
+        ("macos", "aarch64") => Some("llm-pricing-macos-aarch64"),
+        ("windows", "x86_64") => Some("llm-pricing-windows-x86_64.exe"),
+        _ => None,
+    }
+}
+
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.trim_start_matches('v').splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.split(['-', '+']).next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
+
+/// Check GitHub for a newer release and, unless `check_only`, replace the running binary with it
+pub async fn run(check_only: bool) -> anyhow::Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("llm-pricing/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .send()
+        .await?
+        .error_for_status()
+        .context("Failed to look up the latest release")?
+        .json()
+        .await?;
+
+    let latest = release.tag_name.trim_start_matches('v');
+    let is_newer = match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => return Err(anyhow::anyhow!("Unrecognized release version: '{}'", release.tag_name)),
+    };
+
+    if !is_newer {
+        println!("llm-pricing v{current} is up to date");
+        return Ok(());
+    }
+    if check_only {
+        println!("llm-pricing v{latest} is available (installed: v{current})");
+        println!("Run 'llm-pricing self-update' to install it");
+        return Ok(());
+    }
+
+    let asset_name = platform_asset_name().ok_or_else(|| {
+        anyhow::anyhow!(
+            "No prebuilt binary for {}-{}; install with 'cargo install llm-pricing' instead",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })?;
+    let find_asset = |name: &str| {
+        release
+            .assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Release v{} has no asset named '{}'", latest, name))
+    };
+    let binary_asset = find_asset(asset_name)?;
+    let checksum_asset = find_asset(&format!("{asset_name}.sha256"))?;
+
+    println!("Downloading llm-pricing v{latest} ({asset_name})...");
+    let binary = client
+        .get(&binary_asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let checksum_file = client
+        .get(&checksum_asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    // The checksum file is in `sha256sum` format: "<hex digest>  <file name>". It comes from
+    // the same release as the binary, so it only guards against a corrupted download, not a
+    // tampered release.
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Empty checksum file for {}", asset_name))?
+        .to_lowercase();
+    let actual: String = Sha256::digest(&binary)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if actual != expected {
+        return Err(anyhow::anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}. The binary was not replaced",
+            asset_name,
+            expected,
+            actual
+        ));
+    }
+
+    let current_exe = std::env::current_exe().context("Could not locate the running binary")?;
+    replace_binary(&current_exe, &binary)?;
+    println!("Updated llm-pricing v{current} -> v{latest} ({})", current_exe.display());
+    Ok(())
+}
+
+fn replace_binary(current_exe: &Path, binary: &[u8]) -> anyhow::Result<()> {
+    let staged = current_exe.with_extension("update");
+    fs::write(&staged, binary)
+        .with_context(|| format!("Failed to write {}", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Windows can't overwrite a running executable, but it can rename it out of the way
+    #[cfg(windows)]
+    {
+        let previous = current_exe.with_extension("old.exe");
+        let _ = fs::remove_file(&previous);
+        fs::rename(current_exe, &previous)
+            .with_context(|| format!("Failed to move {} aside", current_exe.display()))?;
+    }
+
+    fs::rename(&staged, current_exe)
+        .with_context(|| format!("Failed to replace {}", current_exe.display()))?;
+    Ok(())
+}
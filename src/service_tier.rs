@@ -0,0 +1,75 @@
+use crate::Model;
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Price multipliers of non-standard processing tiers relative to the standard prices the API
+/// reports, keyed by model ID. Models missing from the map only offer the standard tier.
+const BUNDLED_SERVICE_TIERS: &str = include_str!("../data/service_tiers.json");
+
+/// Processing tier a request is billed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceTier {
+    /// Discounted, slower processing for latency-tolerant work
+    Flex,
+    /// Regular pricing, as reported by the API
+    #[default]
+    Standard,
+    /// Faster processing at a premium
+    Priority,
+}
+
+impl ServiceTier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ServiceTier::Flex => "flex",
+            ServiceTier::Standard => "standard",
+            ServiceTier::Priority => "priority",
+        }
+    }
+}
+
+pub struct ServiceTiers(HashMap<String, HashMap<ServiceTier, f64>>);
+
+impl ServiceTiers {
+    pub fn bundled() -> anyhow::Result<Self> {
+        serde_json::from_str(BUNDLED_SERVICE_TIERS)
+            .map(Self)
+            .context("Invalid bundled service tier data")
+    }
+
+    /// Multiplier on the standard prices of a model, if it offers the tier
+    pub fn multiplier(&self, model_id: &str, tier: ServiceTier) -> Option<f64> {
+        match tier {
+            ServiceTier::Standard => Some(1.0),
+            _ => self.0.get(model_id)?.get(&tier).copied(),
+        }
+    }
+
+    /// Reprice models at a tier, dropping models that don't offer it
+    pub fn apply(&self, models: Vec<Model>, tier: ServiceTier) -> Vec<Model> {
+        if tier == ServiceTier::Standard {
+            return models;
+        }
+
+        models
+            .into_iter()
+            .filter_map(|mut model| {
+                let multiplier = self.multiplier(&model.id, tier)?;
+                let pricing = &mut model.pricing;
+                for price in [&mut pricing.prompt, &mut pricing.completion]
+                    .into_iter()
+                    .chain(pricing.input_cache_read.as_mut())
+                    .chain(pricing.input_cache_write.as_mut())
+                {
+                    if let Ok(value) = price.parse::<f64>() {
+                        *price = (value * multiplier).to_string();
+                    }
+                }
+                Some(model)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{paths, Model};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The model list in the shape the API returns it, plus when it was fetched, so snapshots can
+/// be read back by anything that reads API responses
+#[derive(Serialize)]
+struct SnapshotFile<'a> {
+    fetched_at: DateTime<Utc>,
+    data: &'a [Model],
+}
+
+/// Where snapshots go when no path is given
+pub fn snapshots_dir() -> anyhow::Result<PathBuf> {
+    Ok(paths::data_dir()?.join("snapshots"))
+}
+
+/// Save the model list to `file`, or to a file named after the fetch time in the snapshots
+/// directory, and return where it went
+pub fn save(
+    models: &[Model],
+    fetched_at: DateTime<Utc>,
+    file: Option<&Path>,
+    pretty: bool,
+) -> anyhow::Result<PathBuf> {
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => snapshots_dir()?.join(format!("models-{}.json", fetched_at.format("%Y%m%dT%H%M%SZ"))),
+    };
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let snapshot = SnapshotFile {
+        fetched_at,
+        data: models,
+    };
+    let contents = if pretty {
+        serde_json::to_string_pretty(&snapshot)?
+    } else {
+        serde_json::to_string(&snapshot)?
+    };
+    fs::write(&path, contents + "\n").with_context(|| format!("Failed to write snapshot {}", path.display()))?;
+    Ok(path)
+}
@@ -0,0 +1,161 @@
+use crate::{
+    output, print_titled_table, results,
+    table::{self, Cell, Table},
+    Metadata, Model, OutputFormat, SourceInfo,
+};
+use anyhow::Context;
+use chrono::{DateTime, TimeDelta};
+use rusqlite::{params, types::Value, Connection};
+
+const SCHEMA: &str = "
+CREATE TABLE models (
+    id TEXT NOT NULL,
+    name TEXT,
+    provider TEXT NOT NULL,
+    input_price REAL,
+    output_price REAL,
+    cache_read_price REAL,
+    cache_write_price REAL,
+    request_price REAL,
+    image_price REAL,
+    context_length INTEGER,
+    max_completion_tokens INTEGER,
+    modality TEXT,
+    tokenizer TEXT,
+    license TEXT,
+    created TEXT
+);
+CREATE TABLE results (
+    name TEXT NOT NULL,
+    saved_at TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    model TEXT NOT NULL,
+    total_cost REAL NOT NULL
+);
+";
+
+/// SQLite's date functions read this format
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Create the `models` table from the catalog and the `results` table from calc results saved
+/// with --save-as. Token prices are multiplied by `tokens_per_price`, e.g. 1,000,000 to store
+/// prices per 1M tokens; request and image prices are stored as they are.
+pub fn load(
+    connection: &Connection,
+    models: &[&Model],
+    metadata: &Metadata,
+    tokens_per_price: f64,
+) -> anyhow::Result<()> {
+    connection.execute_batch(SCHEMA)?;
+    let transaction = connection.unchecked_transaction()?;
+    {
+        let parse = |price: Option<&String>| price.and_then(|price| price.parse::<f64>().ok());
+        let token_price = |price: Option<&String>| parse(price).map(|price| price * tokens_per_price);
+        let mut insert = transaction.prepare(
+            "INSERT INTO models VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        )?;
+        for model in models {
+            let pricing = &model.pricing;
+            let architecture = model.architecture.as_ref();
+            insert.execute(params![
+                model.id,
+                model.name,
+                model.id.split('/').next().unwrap_or("unknown"),
+                token_price(Some(&pricing.prompt)),
+                token_price(Some(&pricing.completion)),
+                token_price(pricing.input_cache_read.as_ref()),
+                token_price(pricing.input_cache_write.as_ref()),
+                parse(pricing.request.as_ref()),
+                parse(pricing.image.as_ref()),
+                model.context_length,
+                model
+                    .top_provider
+                    .as_ref()
+                    .and_then(|provider| provider.max_completion_tokens),
+                architecture.and_then(|arch| arch.modality.as_deref()),
+                architecture.and_then(|arch| arch.tokenizer.as_deref()),
+                metadata.license(model),
+                model
+                    .created
+                    .and_then(|created| DateTime::from_timestamp(created as i64, 0))
+                    .map(|created| created.format(TIMESTAMP_FORMAT).to_string()),
+            ])?;
+        }
+
+        let mut insert =
+            transaction.prepare("INSERT INTO results VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+        for cost in results::saved_costs()? {
+            insert.execute(params![
+                cost.name,
+                cost.saved_at.format(TIMESTAMP_FORMAT).to_string(),
+                cost.input,
+                cost.output,
+                cost.model,
+                cost.total_cost,
+            ])?;
+        }
+    }
+    transaction.commit()?;
+    Ok(())
+}
+
+fn cell(value: &Value) -> Cell {
+    match value {
+        Value::Null => Cell::Missing,
+        Value::Integer(value) => Cell::Number(*value as f64, 0),
+        Value::Real(value) => Cell::decimal(*value),
+        Value::Text(text) => Cell::text(text),
+        Value::Blob(bytes) => Cell::text(format!("<{} bytes>", bytes.len())),
+    }
+}
+
+/// Run a query against the current prices and saved results, and print its rows
+pub fn run(
+    query: &str,
+    models: &[&Model],
+    metadata: &Metadata,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let unit = table::price_unit();
+    let connection = Connection::open_in_memory()?;
+    load(&connection, models, metadata, unit.tokens())?;
+
+    let mut statement = connection.prepare(query).context("Invalid SQL query")?;
+    let columns: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if columns.is_empty() {
+        return Err(anyhow::anyhow!(
+            "The query doesn't return any columns. Use a SELECT query, e.g. \
+             \"SELECT provider, min(input_price) FROM models GROUP BY 1\""
+        ));
+    }
+    let mut query_rows = statement.query([])?;
+    let mut rows = Vec::new();
+    while let Some(row) = query_rows.next()? {
+        let values = (0..columns.len())
+            .map(|i| row.get::<_, Value>(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(values);
+    }
+
+    if matches!(format, OutputFormat::Json | OutputFormat::Ndjson) {
+        return output::print_sql_json(&columns, &rows, format, source, stale_after);
+    }
+
+    let mut table = Table::new(columns);
+    for row in &rows {
+        table.push(row.iter().map(cell).collect());
+    }
+    let heading = format!("SQL query: {} rows", rows.len());
+    let note = format!(
+        "Token prices in USD per {}; request and image prices in USD each.",
+        unit.describe()
+    );
+    print_titled_table(&heading, &table, Some(&note), format, source, stale_after)
+}
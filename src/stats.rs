@@ -0,0 +1,131 @@
+use crate::{
+    invoice::Usage,
+    model_link, price_header, print_titled_table,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+use std::collections::BTreeMap;
+
+/// Min, median, mean and max of prices, or four missing cells when there are none
+fn price_stats(mut prices: Vec<f64>) -> [Cell; 4] {
+    if prices.is_empty() {
+        return [Cell::Missing, Cell::Missing, Cell::Missing, Cell::Missing];
+    }
+    prices.sort_by(f64::total_cmp);
+    let middle = prices.len() / 2;
+    let median = if prices.len().is_multiple_of(2) {
+        (prices[middle - 1] + prices[middle]) / 2.0
+    } else {
+        prices[middle]
+    };
+    let mean = prices.iter().sum::<f64>() / prices.len() as f64;
+    [
+        Cell::Price(prices[0]),
+        Cell::Price(median),
+        Cell::Price(mean),
+        Cell::Price(prices[prices.len() - 1]),
+    ]
+}
+
+/// Input and output prices averaged over the tokens each model used, so they reflect what
+/// was paid rather than the spread of the catalog. Cached input counts at the cache read
+/// price, as it's billed.
+fn weighted_prices(models: &[Model], usage: &BTreeMap<String, Usage>) -> [Cell; 2] {
+    let price = |price: &str| price.parse::<f64>().ok().filter(|price| *price >= 0.0);
+    let (mut input_cost, mut input_tokens, mut output_cost, mut output_tokens) = (0.0, 0, 0.0, 0);
+    for model in models {
+        let Some(used) = usage.get(&model.id) else {
+            continue;
+        };
+        if let Some(input_price) = price(&model.pricing.prompt) {
+            let cache_read_price = model
+                .pricing
+                .input_cache_read
+                .as_deref()
+                .and_then(price)
+                .unwrap_or(input_price);
+            let cached = used.cached.min(used.input);
+            input_cost += (used.input - cached) as f64 * input_price + cached as f64 * cache_read_price;
+            input_tokens += used.input;
+        }
+        if let Some(output_price) = price(&model.pricing.completion) {
+            output_cost += used.output as f64 * output_price;
+            output_tokens += used.output;
+        }
+    }
+    let average = |cost: f64, tokens: u64| {
+        if tokens > 0 {
+            Cell::Price(cost / tokens as f64)
+        } else {
+            Cell::Missing
+        }
+    };
+    [average(input_cost, input_tokens), average(output_cost, output_tokens)]
+}
+
+/// Print one row per provider summarizing its models' prices and context windows, so
+/// providers can be compared as a whole. With `usage`, average prices weighted by the tokens
+/// actually used follow the catalog statistics.
+pub fn print(
+    grouped: &BTreeMap<String, Vec<Model>>,
+    usage: Option<&BTreeMap<String, Usage>>,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut headers = vec!["Provider".to_string(), "Models".to_string()];
+    for price in ["Input", "Output"] {
+        for stat in ["Min", "Median", "Mean", "Max"] {
+            headers.push(price_header(&format!("{stat} {price}")));
+        }
+    }
+    if usage.is_some() {
+        headers.extend(["Weighted Input", "Weighted Output"].map(price_header));
+    }
+    headers.extend(["Cheapest".to_string(), "Context".to_string()]);
+
+    let mut table = Table::new(headers);
+    for (provider, models) in grouped {
+        // Negative prices mark variable pricing, such as a router's
+        let price = |price: &str| price.parse::<f64>().ok().filter(|price| *price >= 0.0);
+        let mut cells = vec![Cell::text(provider), Cell::Number(models.len() as f64, 0)];
+        cells.extend(price_stats(models.iter().filter_map(|model| price(&model.pricing.prompt)).collect()));
+        cells.extend(price_stats(models.iter().filter_map(|model| price(&model.pricing.completion)).collect()));
+        if let Some(usage) = usage {
+            cells.extend(weighted_prices(models, usage));
+        }
+
+        let cheapest = models
+            .iter()
+            .filter_map(|model| Some((model, price(&model.pricing.prompt)? + price(&model.pricing.completion)?)))
+            .min_by(|(a, a_price), (b, b_price)| a_price.total_cmp(b_price).then_with(|| a.id.cmp(&b.id)));
+        cells.push(cheapest.map_or(Cell::Missing, |(model, _)| model_link(&model.id)));
+
+        let contexts: Vec<u64> = models.iter().filter_map(|model| model.context_length).collect();
+        cells.push(match (contexts.iter().min(), contexts.iter().max()) {
+            (Some(min), Some(max)) if min == max => Cell::text(min.to_string()),
+            (Some(min), Some(max)) => Cell::text(format!("{min}-{max}")),
+            _ => Cell::Missing,
+        });
+        table.push(cells);
+    }
+
+    let mut note = "Prices in USD. Cheapest: the lowest input plus output price. Context: the range of \
+                    context windows in tokens."
+        .to_string();
+    if usage.is_some() {
+        note.push_str(
+            " Weighted: prices averaged over the tokens each model used in the usage file, with cached \
+             input at the cache read price; N/A for providers with no usage.",
+        );
+    }
+    print_titled_table(
+        "Provider statistics",
+        &table,
+        Some(&note),
+        format,
+        source,
+        stale_after,
+    )
+}
@@ -0,0 +1,911 @@
+use crate::{html, terminal};
+use clap::ValueEnum;
+use rust_xlsxwriter::{Format, Worksheet, XlsxError};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+/// How many tokens a displayed price covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum PriceUnit {
+    Token,
+    #[value(name = "1k")]
+    Thousand,
+    #[default]
+    #[value(name = "1m")]
+    Million,
+}
+
+impl PriceUnit {
+    /// Tokens a price in this unit is for
+    pub fn tokens(self) -> f64 {
+        match self {
+            PriceUnit::Token => 1.0,
+            PriceUnit::Thousand => 1_000.0,
+            PriceUnit::Million => 1_000_000.0,
+        }
+    }
+
+    /// Short form for headers and rates, e.g. "1K" as in "$0.003/1K"
+    pub fn short(self) -> &'static str {
+        match self {
+            PriceUnit::Token => "token",
+            PriceUnit::Thousand => "1K",
+            PriceUnit::Million => "1M",
+        }
+    }
+
+    /// Long form for prose, e.g. "1K tokens" as in "per 1K tokens"
+    pub fn describe(self) -> &'static str {
+        match self {
+            PriceUnit::Token => "token",
+            PriceUnit::Thousand => "1K tokens",
+            PriceUnit::Million => "1M tokens",
+        }
+    }
+
+    /// Enough decimals to tell cheap models apart at this unit
+    fn default_precision(self) -> usize {
+        match self {
+            PriceUnit::Token => 8,
+            PriceUnit::Thousand => 5,
+            PriceUnit::Million => 2,
+        }
+    }
+}
+
+static PRICE_UNIT: AtomicU8 = AtomicU8::new(PriceUnit::Million as u8);
+
+/// Change the unit prices are displayed per, as requested with --per
+pub fn set_price_unit(unit: PriceUnit) {
+    PRICE_UNIT.store(unit as u8, Ordering::Relaxed);
+}
+
+pub fn price_unit() -> PriceUnit {
+    match PRICE_UNIT.load(Ordering::Relaxed) {
+        unit if unit == PriceUnit::Token as u8 => PriceUnit::Token,
+        unit if unit == PriceUnit::Thousand as u8 => PriceUnit::Thousand,
+        _ => PriceUnit::Million,
+    }
+}
+
+static NO_HEADER: AtomicBool = AtomicBool::new(false);
+
+/// Leave out header rows, as requested with --no-header
+pub fn set_no_header(no_header: bool) {
+    NO_HEADER.store(no_header, Ordering::Relaxed);
+}
+
+fn show_header() -> bool {
+    !NO_HEADER.load(Ordering::Relaxed) && !terminal::is_quiet()
+}
+
+/// How tables are drawn in the terminal, chosen with --style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum TableStyle {
+    /// Columns separated by '|', with dashed rules
+    #[default]
+    Ascii,
+    /// Box-drawing borders with rounded corners
+    Rounded,
+    /// Markdown table syntax, padded to line up
+    Markdown,
+    /// Columns separated by spaces, without rules
+    Borderless,
+    /// A `header: value` line per cell and a blank line between rows, without borders,
+    /// colors or padding, which screen readers read far better than wide tables
+    Blocks,
+}
+
+static STYLE: AtomicU8 = AtomicU8::new(TableStyle::Ascii as u8);
+
+pub fn set_style(style: TableStyle) {
+    STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+fn style() -> TableStyle {
+    match STYLE.load(Ordering::Relaxed) {
+        style if style == TableStyle::Rounded as u8 => TableStyle::Rounded,
+        style if style == TableStyle::Markdown as u8 => TableStyle::Markdown,
+        style if style == TableStyle::Borderless as u8 => TableStyle::Borderless,
+        style if style == TableStyle::Blocks as u8 => TableStyle::Blocks,
+        _ => TableStyle::Ascii,
+    }
+}
+
+/// A horizontal line across the table
+struct Rule {
+    left: &'static str,
+    fill: char,
+    junction: &'static str,
+    right: &'static str,
+    /// End numeric columns with ':', as Markdown does to right-align them
+    align_marks: bool,
+}
+
+impl Rule {
+    fn render(&self, widths: &[usize], numeric: &[bool]) -> String {
+        let segments: Vec<String> = widths
+            .iter()
+            .zip(numeric)
+            .map(|(width, numeric)| {
+                let mut segment: String = std::iter::repeat_n(self.fill, *width).collect();
+                if self.align_marks && *numeric && *width > 0 {
+                    segment.pop();
+                    segment.push(':');
+                }
+                segment
+            })
+            .collect();
+        format!("{}{}{}", self.left, segments.join(self.junction), self.right)
+    }
+}
+
+/// The strings a style draws rows with, and the rules it draws between sections
+struct Borders {
+    left: &'static str,
+    separator: &'static str,
+    right: &'static str,
+    top: Option<Rule>,
+    header: Option<Rule>,
+    footer: Option<Rule>,
+    bottom: Option<Rule>,
+}
+
+impl TableStyle {
+    fn borders(self) -> Borders {
+        let rule = |left, fill, junction, right| {
+            Some(Rule {
+                left,
+                fill,
+                junction,
+                right,
+                align_marks: false,
+            })
+        };
+        match self {
+            TableStyle::Ascii => Borders {
+                left: "",
+                separator: " | ",
+                right: "",
+                top: None,
+                header: rule("", '-', "-+-", ""),
+                footer: rule("", '-', "-+-", ""),
+                bottom: None,
+            },
+            TableStyle::Rounded => Borders {
+                left: "│ ",
+                separator: " │ ",
+                right: " │",
+                top: rule("╭─", '─', "─┬─", "─╮"),
+                header: rule("├─", '─', "─┼─", "─┤"),
+                footer: rule("├─", '─', "─┼─", "─┤"),
+                bottom: rule("╰─", '─', "─┴─", "─╯"),
+            },
+            TableStyle::Markdown => Borders {
+                left: "| ",
+                separator: " | ",
+                right: " |",
+                top: None,
+                header: Some(Rule {
+                    left: "| ",
+                    fill: '-',
+                    junction: " | ",
+                    right: " |",
+                    align_marks: true,
+                }),
+                footer: None,
+                bottom: None,
+            },
+            TableStyle::Borderless | TableStyle::Blocks => Borders {
+                left: "",
+                separator: "  ",
+                right: "",
+                top: None,
+                header: None,
+                footer: None,
+                bottom: None,
+            },
+        }
+    }
+}
+
+/// Narrowest a wrapped text column gets, so IDs stay readable in very narrow terminals
+const MIN_WRAP_WIDTH: usize = 12;
+
+fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Narrow the widest shrinkable columns one character at a time until the table fits in
+/// `available` columns, or nothing can shrink further
+fn shrink_to_fit(widths: &mut [usize], minimums: &[usize], available: usize) {
+    while widths.iter().sum::<usize>() > available {
+        let widest = (0..widths.len())
+            .filter(|&i| widths[i] > minimums[i])
+            .max_by_key(|&i| widths[i]);
+        match widest {
+            Some(i) => widths[i] -= 1,
+            None => break,
+        }
+    }
+}
+
+/// Split text into lines of at most `width` characters, preferring to break after '/', '-',
+/// ':' or a space so model IDs split at their natural boundaries
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest: Vec<char> = text.chars().collect();
+    while rest.len() > width && width > 0 {
+        let split = rest[..width]
+            .iter()
+            .rposition(|c| matches!(c, '/' | '-' | ':' | ' '))
+            .map_or(width, |i| i + 1);
+        let line: String = rest.drain(..split).collect();
+        lines.push(line.trim_end().to_string());
+        while rest.first() == Some(&' ') {
+            rest.remove(0);
+        }
+    }
+    if lines.is_empty() || !rest.is_empty() {
+        lines.push(rest.into_iter().collect());
+    }
+    lines
+}
+
+/// Format a per-token price in the display unit, e.g. 3.00 for $0.000003 per 1M tokens
+pub fn format_price(price_per_token: f64) -> String {
+    let unit = price_unit();
+    format_number(price_per_token * unit.tokens(), precision(unit.default_precision()))
+}
+
+/// Decimal places for prices and costs chosen with --precision, or `usize::MAX` for each
+/// kind's default
+static PRECISION: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Override the decimal places shown for prices and costs
+pub fn set_precision(precision: Option<usize>) {
+    PRECISION.store(precision.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+/// Decimal places for prices and costs, falling back to `default` without --precision
+pub fn precision(default: usize) -> usize {
+    match PRECISION.load(Ordering::Relaxed) {
+        usize::MAX => default,
+        precision => precision,
+    }
+}
+
+/// Format with a fixed number of decimals and commas between thousands, e.g. 1,048,576.00
+pub fn format_number(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+    // Rounding can leave "-0.00", which isn't worth a sign
+    if value < 0.0 && grouped.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+/// A single table cell. Numeric cells keep their value so each output format can render
+/// them appropriately: rounded for the terminal, full precision for CSV.
+#[derive(Debug, Clone)]
+pub enum Cell {
+    Text(String),
+    /// Text that links to a web page, clickable in terminals that support hyperlinks
+    Link { text: String, url: String },
+    /// Price per token in USD, displayed per 1M tokens or the unit chosen with --per
+    Price(f64),
+    /// Dollar amount
+    Cost(f64),
+    /// Plain number displayed with the given number of decimal places
+    Number(f64, usize),
+    /// Value the model doesn't have, such as cache pricing on a model without caching
+    Missing,
+}
+
+impl Cell {
+    pub fn text(value: impl Into<String>) -> Self {
+        Cell::Text(value.into())
+    }
+
+    /// Price from the API's string representation, missing if it can't be parsed
+    pub fn price_str(price_str: &str) -> Self {
+        price_str.parse::<f64>().map(Cell::Price).unwrap_or(Cell::Missing)
+    }
+
+    /// Number shown with as many decimal places as it needs, up to 10
+    pub fn decimal(value: f64) -> Self {
+        let formatted = format_decimal(value);
+        let places = formatted.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+        Cell::Number(value, places)
+    }
+
+    /// Text shown for the cell, with thousands separators
+    pub fn display(&self) -> String {
+        self.format(true)
+    }
+
+    fn format(&self, grouped: bool) -> String {
+        let number = |value: f64, decimals: usize| {
+            if grouped {
+                format_number(value, decimals)
+            } else {
+                format!("{:.*}", decimals, value)
+            }
+        };
+        match self {
+            Cell::Text(text) | Cell::Link { text, .. } => text.clone(),
+            Cell::Price(price) => {
+                let unit = price_unit();
+                number(price * unit.tokens(), precision(unit.default_precision()))
+            }
+            Cell::Cost(cost) => {
+                let amount = number(cost.abs(), precision(6));
+                // Scripts reading --quiet output want bare numbers
+                let currency = if terminal::is_quiet() { "" } else { "$" };
+                // Rounding can leave "-$0.000000", which isn't worth a sign
+                if *cost < 0.0 && amount.chars().any(|c| matches!(c, '1'..='9')) {
+                    format!("-{currency}{amount}")
+                } else {
+                    format!("{currency}{amount}")
+                }
+            }
+            Cell::Number(value, decimals) => number(*value, *decimals),
+            Cell::Missing => "N/A".to_string(),
+        }
+    }
+
+    /// Number format matching how the cell is displayed in the terminal, with prices and
+    /// costs in dollars
+    fn spreadsheet_format(&self) -> Format {
+        let decimals = |places: usize| {
+            if places == 0 {
+                String::new()
+            } else {
+                format!(".{}", "0".repeat(places))
+            }
+        };
+        match self {
+            Cell::Price(_) => {
+                let places = precision(price_unit().default_precision());
+                Format::new().set_num_format(format!("$#,##0{}", decimals(places)))
+            }
+            Cell::Cost(_) => Format::new().set_num_format(format!("$#,##0{}", decimals(precision(6)))),
+            Cell::Number(_, places) => {
+                Format::new().set_num_format(format!("#,##0{}", decimals(*places)))
+            }
+            _ => Format::new(),
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Cell::Price(_) | Cell::Cost(_) | Cell::Number(..))
+    }
+
+    fn value(&self) -> Option<f64> {
+        match self {
+            Cell::Price(value) | Cell::Cost(value) | Cell::Number(value, _) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Numeric cell of the same kind holding a different value
+    fn with_value(&self, value: f64) -> Cell {
+        match self {
+            Cell::Price(_) => Cell::Price(value),
+            Cell::Number(_, decimals) => Cell::Number(value, *decimals),
+            _ => Cell::Cost(value),
+        }
+    }
+
+    fn raw(&self) -> String {
+        match self {
+            Cell::Text(text) | Cell::Link { text, .. } => text.clone(),
+            Cell::Price(price) => format_decimal(price * price_unit().tokens()),
+            Cell::Cost(cost) | Cell::Number(cost, _) => format_decimal(*cost),
+            Cell::Missing => String::new(),
+        }
+    }
+}
+
+/// Format without float noise (e.g. 0.028000000000000004) or trailing zeros
+pub fn format_decimal(value: f64) -> String {
+    let formatted = format!("{:.10}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+    /// Summary rows shown below the data, such as totals. Left out of CSV, which is for data.
+    pub footer: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+            footer: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Cell>) {
+        self.rows.push(row);
+    }
+
+    /// Print as an aligned text table in the chosen --style, or tab-separated without padding
+    /// in plain mode. Numeric columns are right-aligned so magnitudes line up, and text columns
+    /// such as long model IDs wrap when the table is wider than the terminal. Plain mode leaves
+    /// out thousands separators so numbers stay easy to parse. The blocks style is kept even
+    /// when piped, since it has no padding or colors to strip, but not with --quiet.
+    pub fn print(&self) {
+        if style() == TableStyle::Blocks && !terminal::is_quiet() {
+            self.print_blocks();
+            return;
+        }
+        let plain = terminal::is_plain();
+        let display = |rows: &[Vec<Cell>]| -> Vec<Vec<String>> {
+            rows.iter()
+                .map(|row| row.iter().map(|cell| cell.format(!plain)).collect())
+                .collect()
+        };
+        let rows = display(&self.rows);
+        let footer = display(&self.footer);
+
+        if plain {
+            if show_header() {
+                println!("{}", self.headers.join("\t"));
+            }
+            for row in rows.iter().chain(&footer) {
+                println!("{}", row.join("\t"));
+            }
+            return;
+        }
+
+        let style = style();
+        let borders = style.borders();
+        let numeric: Vec<bool> = (0..self.headers.len())
+            .map(|i| self.rows.iter().any(|row| row[i].is_numeric()))
+            .collect();
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                rows.iter()
+                    .chain(&footer)
+                    .map(|row| text_width(&row[i]))
+                    .max()
+                    .unwrap_or(0)
+                    .max(text_width(header))
+            })
+            .collect();
+        // Markdown rows can't span lines, so only the other styles wrap
+        if style != TableStyle::Markdown {
+            if let Some(available) = terminal::width() {
+                let overhead = text_width(borders.left)
+                    + text_width(borders.right)
+                    + text_width(borders.separator) * widths.len().saturating_sub(1);
+                let minimums: Vec<usize> = self
+                    .headers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, header)| {
+                        if numeric[i] {
+                            widths[i]
+                        } else {
+                            text_width(header).max(MIN_WRAP_WIDTH).min(widths[i])
+                        }
+                    })
+                    .collect();
+                shrink_to_fit(&mut widths, &minimums, available.saturating_sub(overhead));
+            }
+        }
+
+        let heat = if terminal::use_color() {
+            self.heat_positions()
+        } else {
+            Vec::new()
+        };
+        let links = terminal::use_links();
+
+        // Pad before coloring or linking so escape codes don't count towards the width
+        let format_cell = |i: usize, text: &str, cell: Option<&Cell>, position: Option<f64>| {
+            let padding = " ".repeat(widths[i].saturating_sub(text_width(text)));
+            let mut styled = match cell {
+                Some(Cell::Link { url, .. }) if links && !text.is_empty() => {
+                    terminal::hyperlink(text, url)
+                }
+                _ => text.to_string(),
+            };
+            if let Some(position) = position {
+                styled = terminal::heat(&styled, position);
+            }
+            if numeric[i] {
+                format!("{padding}{styled}")
+            } else {
+                format!("{styled}{padding}")
+            }
+        };
+        let print_line = |cells: Vec<String>| {
+            let line = format!(
+                "{}{}{}",
+                borders.left,
+                cells.join(borders.separator),
+                borders.right
+            );
+            println!("{}", line.trim_end());
+        };
+        let print_rule = |rule: &Option<Rule>| {
+            if let Some(rule) = rule {
+                println!("{}", rule.render(&widths, &numeric));
+            }
+        };
+        // Each row takes as many lines as its most-wrapped cell
+        let print_row = |texts: &[String], cells: &[Cell], positions: Option<&Vec<Option<f64>>>| {
+            let wrapped: Vec<Vec<String>> = texts
+                .iter()
+                .enumerate()
+                .map(|(i, text)| wrap(text, widths[i]))
+                .collect();
+            let height = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+            for line in 0..height {
+                print_line(
+                    wrapped
+                        .iter()
+                        .enumerate()
+                        .map(|(i, parts)| {
+                            let text = parts.get(line).map(String::as_str).unwrap_or("");
+                            let position = positions.and_then(|positions| positions[i]);
+                            format_cell(i, text, Some(&cells[i]), position)
+                        })
+                        .collect(),
+                );
+            }
+        };
+
+        print_rule(&borders.top);
+        if show_header() {
+            print_line(
+                self.headers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, header)| format_cell(i, header, None, None))
+                    .collect(),
+            );
+            print_rule(&borders.header);
+        }
+        for (row, texts) in rows.iter().enumerate() {
+            print_row(texts, &self.rows[row], heat.get(row));
+        }
+        if !footer.is_empty() {
+            print_rule(&borders.footer);
+            for (row, texts) in footer.iter().enumerate() {
+                print_row(texts, &self.footer[row], None);
+            }
+        }
+        print_rule(&borders.bottom);
+    }
+
+    /// Print each row as `header: value` lines with a blank line between rows, leaving out
+    /// empty cells
+    fn print_blocks(&self) {
+        for (i, row) in self.rows.iter().chain(&self.footer).enumerate() {
+            if i > 0 {
+                println!();
+            }
+            for (header, cell) in self.headers.iter().zip(row) {
+                let value = cell.format(true);
+                if !value.is_empty() {
+                    println!("{header}: {value}");
+                }
+            }
+        }
+    }
+
+    /// Add min, median and max footer rows for each numeric column
+    pub fn add_summary(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let columns: Vec<Vec<f64>> = (0..self.headers.len())
+            .map(|i| {
+                // Routers list a price of -1 when it depends on the model they pick
+                let mut values: Vec<f64> = self
+                    .rows
+                    .iter()
+                    .filter(|row| !matches!(row[i], Cell::Price(price) if price < 0.0))
+                    .filter_map(|row| row[i].value())
+                    .collect();
+                values.sort_by(f64::total_cmp);
+                values
+            })
+            .collect();
+
+        // Min, median and max of each column with any numbers in it
+        let stats: Vec<Option<[f64; 3]>> = columns
+            .iter()
+            .map(|values| {
+                let (first, last) = (values.first()?, values.last()?);
+                let middle = values.len() / 2;
+                let median = if values.len().is_multiple_of(2) {
+                    (values[middle - 1] + values[middle]) / 2.0
+                } else {
+                    values[middle]
+                };
+                Some([*first, median, *last])
+            })
+            .collect();
+
+        for (stat, label) in ["Min", "Median", "Max"].into_iter().enumerate() {
+            let row = self.footer_row(label, |i| stats[i].map(|values| values[stat]));
+            self.footer.push(row);
+        }
+    }
+
+    /// Add a row below the others, shown like summary and total rows
+    pub fn push_footer(&mut self, row: Vec<Cell>) {
+        self.footer.push(row);
+    }
+
+    /// Add a footer row totalling each numeric column
+    pub fn add_totals(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let row = self.footer_row("Total", |i| {
+            let mut values = self.rows.iter().filter_map(|row| row[i].value()).peekable();
+            values.peek().is_some().then(|| values.sum())
+        });
+        self.footer.push(row);
+    }
+
+    /// Footer row with `label` in the first text column and the result of `value` in each
+    /// numeric column, formatted like the column's cells
+    fn footer_row(&self, label: &str, value: impl Fn(usize) -> Option<f64>) -> Vec<Cell> {
+        let label_column = (0..self.headers.len())
+            .find(|&i| !self.rows.iter().any(|row| row[i].is_numeric()));
+        (0..self.headers.len())
+            .map(|i| {
+                let template = self.rows.iter().map(|row| &row[i]).find(|cell| cell.is_numeric());
+                match (template, value(i)) {
+                    (Some(template), Some(value)) => template.with_value(value),
+                    _ if label_column == Some(i) => Cell::text(label),
+                    _ => Cell::text(""),
+                }
+            })
+            .collect()
+    }
+
+    /// Where each price or cost sits among the distinct values in its column, from 0.0
+    /// (cheapest) to 1.0 (most expensive). Ranking rather than scaling keeps a single
+    /// outlier from washing out the rest of the column.
+    fn heat_positions(&self) -> Vec<Vec<Option<f64>>> {
+        let amount = |cell: &Cell| match cell {
+            Cell::Price(value) | Cell::Cost(value) => Some(*value),
+            _ => None,
+        };
+
+        let columns: Vec<Vec<f64>> = (0..self.headers.len())
+            .map(|i| {
+                let mut values: Vec<f64> =
+                    self.rows.iter().filter_map(|row| amount(&row[i])).collect();
+                values.sort_by(f64::total_cmp);
+                values.dedup();
+                values
+            })
+            .collect();
+
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&columns)
+                    .map(|(cell, values)| {
+                        let value = amount(cell)?;
+                        if values.len() < 2 {
+                            return None;
+                        }
+                        let rank = values.partition_point(|other| *other < value);
+                        Some(rank as f64 / (values.len() - 1) as f64)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Print as CSV with a header row, keeping full numeric precision
+    pub fn print_csv(&self) {
+        if show_header() {
+            println!("{}", csv_line(self.headers.iter().map(String::as_str)));
+        }
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(Cell::raw).collect();
+            println!("{}", csv_line(cells.iter().map(String::as_str)));
+        }
+    }
+
+    /// Render as an HTML table. Numeric cells carry their raw value for sorting.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table>\n<thead><tr>");
+        for header in &self.headers {
+            html.push_str(&format!("<th>{}</th>", html::escape(header)));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for row in &self.rows {
+            html.push_str(&html_row(row));
+        }
+        html.push_str("</tbody>\n");
+        if !self.footer.is_empty() {
+            html.push_str("<tfoot>\n");
+            for row in &self.footer {
+                html.push_str(&html_row(row));
+            }
+            html.push_str("</tfoot>\n");
+        }
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Render as a GitHub-flavored Markdown table, with numeric columns right-aligned and
+    /// footer rows in bold
+    pub fn to_markdown(&self) -> String {
+        let numeric: Vec<bool> = (0..self.headers.len())
+            .map(|i| self.rows.iter().any(|row| row[i].is_numeric()))
+            .collect();
+        let mut markdown = markdown_line(self.headers.iter().map(|header| markdown_escape(header)));
+        markdown.push_str(&markdown_line(
+            numeric
+                .iter()
+                .map(|numeric| if *numeric { "---:" } else { "---" }.to_string()),
+        ));
+        for row in &self.rows {
+            markdown.push_str(&markdown_line(row.iter().map(markdown_cell)));
+        }
+        for row in &self.footer {
+            markdown.push_str(&markdown_line(row.iter().map(|cell| {
+                let text = markdown_cell(cell);
+                if text.is_empty() {
+                    text
+                } else {
+                    format!("**{text}**")
+                }
+            })));
+        }
+        markdown
+    }
+
+    /// Write into a spreadsheet with typed cells: prices, costs and numbers stay numbers,
+    /// formatted like the terminal shows them, and model IDs stay links
+    pub fn write_worksheet(&self, worksheet: &mut Worksheet) -> Result<(), XlsxError> {
+        let bold = Format::new().set_bold();
+        for (column, header) in self.headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, column as u16, header, &bold)?;
+        }
+        let rows = self.rows.iter().map(|row| (row, false));
+        let footer = self.footer.iter().map(|row| (row, true));
+        for (index, (row, is_footer)) in rows.chain(footer).enumerate() {
+            let row_number = index as u32 + 1;
+            for (column, cell) in row.iter().enumerate() {
+                let mut format = cell.spreadsheet_format();
+                if is_footer {
+                    format = format.set_bold();
+                }
+                let column = column as u16;
+                match cell {
+                    Cell::Text(text) => {
+                        worksheet.write_string_with_format(row_number, column, text, &format)?;
+                    }
+                    Cell::Link { text, url } => {
+                        worksheet.write_url_with_text(row_number, column, url.as_str(), text)?;
+                    }
+                    Cell::Price(price) => {
+                        let value = price * price_unit().tokens();
+                        let value = format_decimal(value).parse().unwrap_or(value);
+                        worksheet.write_number_with_format(row_number, column, value, &format)?;
+                    }
+                    Cell::Cost(value) | Cell::Number(value, _) => {
+                        worksheet.write_number_with_format(row_number, column, *value, &format)?;
+                    }
+                    Cell::Missing => {}
+                }
+            }
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+        worksheet.autofit();
+        Ok(())
+    }
+
+    /// Header, rows and footer as JSON values for a spreadsheet API, with numbers kept as
+    /// numbers and prices in the --per unit
+    pub fn sheet_values(&self) -> Vec<Vec<serde_json::Value>> {
+        let value = |cell: &Cell| match cell {
+            Cell::Text(text) | Cell::Link { text, .. } => serde_json::Value::from(text.as_str()),
+            Cell::Price(price) => {
+                let value = price * price_unit().tokens();
+                serde_json::Value::from(format_decimal(value).parse().unwrap_or(value))
+            }
+            Cell::Cost(value) | Cell::Number(value, _) => serde_json::Value::from(*value),
+            Cell::Missing => serde_json::Value::from(""),
+        };
+        let header = self.headers.iter().map(|header| header.as_str().into()).collect();
+        std::iter::once(header)
+            .chain(
+                self.rows
+                    .iter()
+                    .chain(&self.footer)
+                    .map(|row| row.iter().map(value).collect()),
+            )
+            .collect()
+    }
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn markdown_cell(cell: &Cell) -> String {
+    match cell {
+        Cell::Link { text, url } => format!("[{}]({})", markdown_escape(text), url),
+        _ => markdown_escape(&cell.display()),
+    }
+}
+
+fn markdown_line(cells: impl Iterator<Item = String>) -> String {
+    format!("| {} |\n", cells.collect::<Vec<_>>().join(" | "))
+}
+
+fn html_row(row: &[Cell]) -> String {
+    let mut html = String::from("<tr>");
+    for cell in row {
+        match cell {
+            Cell::Text(text) => html.push_str(&format!("<td>{}</td>", html::escape(text))),
+            Cell::Link { text, url } => html.push_str(&format!(
+                "<td><a href=\"{}\">{}</a></td>",
+                html::escape(url),
+                html::escape(text)
+            )),
+            _ => html.push_str(&format!(
+                "<td class=\"number\" data-value=\"{}\">{}</td>",
+                cell.raw(),
+                html::escape(&cell.display())
+            )),
+        }
+    }
+    html.push_str("</tr>\n");
+    html
+}
+
+fn csv_line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    cells
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
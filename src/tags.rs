@@ -0,0 +1,105 @@
+use crate::Model;
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Rules deriving functional tags such as `code` or `vision` from what the API says about a
+/// model, so models can be found by what they're for rather than by name
+const BUNDLED_TAGS: &str = include_str!("../data/tags.json");
+
+/// When a tag applies: a model gets it if any of the rule's conditions hold
+#[derive(Deserialize)]
+struct Rule {
+    /// Parts of the model ID, e.g. "coder"
+    #[serde(default)]
+    ids: Vec<String>,
+    /// Phrases in the model's description or display name
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    input_modalities: Vec<String>,
+    #[serde(default)]
+    supported_parameters: Vec<String>,
+    /// Context length in tokens at or above which the tag applies
+    #[serde(default)]
+    min_context: Option<u64>,
+}
+
+impl Rule {
+    fn matches(&self, model: &Model) -> bool {
+        let id = model.id.to_lowercase();
+        if self.ids.iter().any(|part| id.contains(part.as_str())) {
+            return true;
+        }
+        let text = format!(
+            "{} {}",
+            model.name.as_deref().unwrap_or_default(),
+            model.description.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        if self.keywords.iter().any(|keyword| text.contains(keyword.as_str())) {
+            return true;
+        }
+        let architecture = model.architecture.as_ref();
+        let input_modalities = architecture.and_then(|arch| arch.input_modalities.as_ref());
+        if input_modalities.is_some_and(|modalities| {
+            modalities.iter().any(|modality| self.input_modalities.contains(modality))
+        }) {
+            return true;
+        }
+        if model.supported_parameters.as_ref().is_some_and(|parameters| {
+            parameters.iter().any(|parameter| self.supported_parameters.contains(parameter))
+        }) {
+            return true;
+        }
+        self.min_context
+            .zip(model.context_length)
+            .is_some_and(|(min, context)| context >= min)
+    }
+}
+
+pub struct Tags {
+    rules: BTreeMap<String, Rule>,
+}
+
+impl Tags {
+    pub fn bundled() -> anyhow::Result<Self> {
+        let rules: BTreeMap<String, Rule> =
+            serde_json::from_str(BUNDLED_TAGS).context("Invalid bundled tag rules")?;
+        let rules = rules
+            .into_iter()
+            .map(|(tag, mut rule)| {
+                // Match case-insensitively against lowercased model text
+                for phrase in rule.ids.iter_mut().chain(&mut rule.keywords) {
+                    *phrase = phrase.to_lowercase();
+                }
+                (tag, rule)
+            })
+            .collect();
+        Ok(Self { rules })
+    }
+
+    /// Tags that apply to a model, in alphabetical order
+    pub fn of(&self, model: &Model) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter(|(_, rule)| rule.matches(model))
+            .map(|(tag, _)| tag.as_str())
+            .collect()
+    }
+
+    /// Fail on a --tag that no rule defines, listing the ones that exist
+    pub fn check_known(&self, tags: &[String]) -> anyhow::Result<()> {
+        for tag in tags {
+            if !self.rules.contains_key(&tag.to_lowercase()) {
+                let known: Vec<&str> = self.rules.keys().map(String::as_str).collect();
+                return Err(anyhow::anyhow!(
+                    "Unknown tag '{}'. Tags are: {}",
+                    tag,
+                    known.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static NO_LINKS: AtomicBool = AtomicBool::new(false);
+static PAGED: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COPYING: AtomicBool = AtomicBool::new(false);
+static INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Force plain output, as requested with --plain
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Print only table rows for scripts, as requested with --quiet
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether to leave out headings, notes and currency symbols. Quiet output is always plain.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Record that stdout is held back for a pager, which shows it in the terminal even though
+/// stdout is no longer one
+pub fn set_paged() {
+    PAGED.store(true, Ordering::Relaxed);
+}
+
+/// Record that stdout is being copied to the clipboard on its way to the terminal. Output
+/// stays aligned, but without escape codes, which would paste as garbage.
+pub fn set_copying() {
+    COPYING.store(true, Ordering::Relaxed);
+}
+
+fn is_copying() -> bool {
+    COPYING.load(Ordering::Relaxed)
+}
+
+/// Whether to drop colors and alignment padding: with --plain, or when stdout is piped or
+/// redirected rather than shown in a terminal and CLICOLOR_FORCE isn't set
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+        || is_quiet()
+        || !(PAGED.load(Ordering::Relaxed)
+            || is_copying()
+            || io::stdout().is_terminal()
+            || color_forced())
+}
+
+/// Columns available for tables: $COLUMNS if set, otherwise the terminal's width. While
+/// paging, stdout is captured, so the width comes from stderr, which is still the terminal.
+pub fn width() -> Option<usize> {
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .filter(|columns| *columns > 0)
+    {
+        return Some(columns);
+    }
+    terminal_size::terminal_size()
+        .or_else(|| terminal_size::terminal_size_of(io::stderr()))
+        .map(|(terminal_size::Width(width), _)| width as usize)
+}
+
+/// Rows available for output: $LINES if set, otherwise the terminal's height, read from
+/// stderr like `width` as stdout may be held back for the pager
+pub fn height() -> Option<usize> {
+    if let Some(lines) = std::env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.parse::<usize>().ok())
+        .filter(|lines| *lines > 0)
+    {
+        return Some(lines);
+    }
+    terminal_size::terminal_size()
+        .or_else(|| terminal_size::terminal_size_of(io::stderr()))
+        .map(|(_, terminal_size::Height(height))| height as usize)
+}
+
+/// Allow asking questions on the terminal unless --no-interactive was given. Set before any
+/// pager or clipboard capture replaces stdout, so it reflects where output really goes.
+pub fn set_interactive(allowed: bool) {
+    let interactive = allowed && io::stdin().is_terminal() && io::stdout().is_terminal();
+    INTERACTIVE.store(interactive, Ordering::Relaxed);
+}
+
+/// Whether someone is at the terminal to answer a question, such as which model was meant
+pub fn is_interactive() -> bool {
+    INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Never emit hyperlinks, as requested with --no-links
+pub fn set_no_links(no_links: bool) {
+    NO_LINKS.store(no_links, Ordering::Relaxed);
+}
+
+/// Whether to render OSC 8 hyperlinks: only in a terminal known to support them, since
+/// others print the escape sequences as garbage. Not every pager passes them through, so
+/// never when paging, nor when copying to the clipboard.
+pub fn use_links() -> bool {
+    !NO_LINKS.load(Ordering::Relaxed)
+        && !PAGED.load(Ordering::Relaxed)
+        && !is_copying()
+        && !is_plain()
+        && supports_hyperlinks()
+}
+
+/// Best-effort detection from the environment, as there's no way to query the terminal.
+/// FORCE_HYPERLINK overrides it either way.
+fn supports_hyperlinks() -> bool {
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if ["WT_SESSION", "KITTY_WINDOW_ID", "KONSOLE_VERSION", "DOMTERM"]
+        .iter()
+        .any(|name| var(name).is_some())
+    {
+        return true;
+    }
+    if var("TERM_PROGRAM").is_some_and(|program| {
+        ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"].contains(&program.as_str())
+    }) {
+        return true;
+    }
+    // GNOME Terminal, Tilix and other VTE-based terminals since 0.50
+    if var("VTE_VERSION").and_then(|version| version.parse::<u32>().ok()) >= Some(5000) {
+        return true;
+    }
+    var("TERM").is_some_and(|term| {
+        ["xterm-kitty", "alacritty", "foot", "xterm-ghostty", "wezterm"].contains(&term.as_str())
+    })
+}
+
+/// Wrap text in an OSC 8 hyperlink to `url`
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+fn env_set(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Whether CLICOLOR_FORCE asks for styled output even when stdout is piped, unless NO_COLOR
+/// says otherwise (https://bixense.com/clicolors)
+fn color_forced() -> bool {
+    env_set("NO_COLOR").is_none() && env_set("CLICOLOR_FORCE").is_some_and(|force| force != "0")
+}
+
+/// Whether to colorize output: never in plain mode, when copying to the clipboard, or when
+/// NO_COLOR (see https://no-color.org) or CLICOLOR=0 is set
+pub fn use_color() -> bool {
+    let no_color = env_set("NO_COLOR").is_some() || env_set("CLICOLOR").as_deref() == Some("0");
+    !no_color && !is_copying() && !is_plain()
+}
+
+/// Color text on a green-to-red gradient, where 0.0 is green (cheapest) and 1.0 is red
+pub fn heat(text: &str, position: f64) -> String {
+    let position = position.clamp(0.0, 1.0);
+    // Walk the 6x6x6 xterm-256 color cube from green through yellow to red
+    let (red, green) = if position < 0.5 {
+        ((position * 2.0 * 5.0).round() as u8, 5)
+    } else {
+        (5, ((1.0 - position) * 2.0 * 5.0).round() as u8)
+    };
+    let index = 16 + 36 * red + 6 * green;
+    format!("\x1b[38;5;{index}m{text}\x1b[0m")
+}
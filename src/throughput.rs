@@ -0,0 +1,112 @@
+use crate::{
+    calculate_cost, print_titled_table,
+    rate_limits::RateLimits,
+    table::{Cell, Table},
+    Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+use std::str::FromStr;
+
+/// Input and output tokens of a typical request, written as INPUT/OUTPUT
+#[derive(Debug, Clone, Copy)]
+pub struct TokensPerRequest {
+    pub input: u64,
+    pub output: u64,
+}
+
+impl FromStr for TokensPerRequest {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid tokens per request: '{}'. Expected INPUT/OUTPUT (e.g., '1500/300')",
+                s
+            )
+        };
+        let (input, output) = s.split_once('/').ok_or_else(invalid)?;
+        Ok(Self {
+            input: input.trim().parse().map_err(|_| invalid())?,
+            output: output.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+struct ThroughputRow<'a> {
+    model: &'a Model,
+    keys: Option<u64>,
+    cost_per_request: f64,
+}
+
+/// Print which models can sustain a request rate under their provider's rate limits, the
+/// number of keys needed to do so, and what the load costs
+pub fn print(
+    models: &[&Model],
+    rate_limits: &RateLimits,
+    requests_per_second: f64,
+    tokens: TokensPerRequest,
+    format: OutputFormat,
+    source: &SourceInfo,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let mut rows = Vec::new();
+    for model in models {
+        let cost = calculate_cost(model, tokens.input, tokens.output, None, 5)?;
+        rows.push(ThroughputRow {
+            model,
+            keys: rate_limits
+                .lookup(&model.id)
+                .map(|limit| limit.keys_needed(requests_per_second, tokens.input, tokens.output)),
+            cost_per_request: cost.total_cost,
+        });
+    }
+    // Models with known limits first, cheapest first
+    rows.sort_by(|a, b| {
+        b.keys
+            .is_some()
+            .cmp(&a.keys.is_some())
+            .then(a.cost_per_request.total_cmp(&b.cost_per_request))
+    });
+
+    let requests_per_hour = requests_per_second * 3600.0;
+    let mut table = Table::new([
+        "Model",
+        "Tier",
+        "RPM/key",
+        "TPM/key",
+        "Keys",
+        "Per Request",
+        "Per Hour",
+        "Per 30 Days",
+    ]);
+    for row in &rows {
+        let limit = rate_limits.lookup(&row.model.id);
+        table.push(vec![
+            Cell::text(&row.model.id),
+            limit
+                .map(|limit| Cell::text(limit.tier.as_deref().unwrap_or("")))
+                .unwrap_or(Cell::Missing),
+            limit
+                .map(|limit| Cell::Number(limit.rpm as f64, 0))
+                .unwrap_or(Cell::Missing),
+            limit
+                .map(|limit| Cell::text(limit.describe_tokens()))
+                .unwrap_or(Cell::Missing),
+            row.keys
+                .map(|keys| Cell::Number(keys as f64, 0))
+                .unwrap_or(Cell::Missing),
+            Cell::Cost(row.cost_per_request),
+            Cell::Cost(row.cost_per_request * requests_per_hour),
+            Cell::Cost(row.cost_per_request * requests_per_hour * 24.0 * 30.0),
+        ]);
+    }
+
+    let heading = format!(
+        "Throughput: {} requests/s at {} input + {} output tokens per request",
+        requests_per_second, tokens.input, tokens.output
+    );
+    let note = "Keys: API keys or accounts at the listed tier needed to stay within its rate \
+                limits; higher tiers allow more. N/A: no rate limit data for the model.";
+
+    print_titled_table(&heading, &table, Some(note), format, source, stale_after)
+}
@@ -0,0 +1,62 @@
+use crate::{
+    diff::{self, Snapshot},
+    filter_by_name, load_catalog, parse_duration, why, Args, Metadata, Model, OutputFormat, SourceInfo,
+};
+use chrono::TimeDelta;
+use std::io::Write;
+
+/// What a poll found, labelled with its fetch time for the heading
+fn snapshot(models: &[Model], source: &SourceInfo) -> Snapshot {
+    Snapshot::from_models(models, source.fetched_at.format("%Y-%m-%d %H:%M UTC").to_string())
+}
+
+/// Re-fetch prices every `interval` until interrupted, printing the models added, removed and
+/// re-priced since the previous poll. Polls that find no changes print nothing, and failed
+/// polls are skipped with a warning so a network blip doesn't end the watch.
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    models: Vec<Model>,
+    source: SourceInfo,
+    interval: &str,
+    filters: &[String],
+    args: &Args,
+    metadata: &Metadata,
+    format: OutputFormat,
+    stale_after: TimeDelta,
+) -> anyhow::Result<()> {
+    let period = parse_duration(interval)?
+        .to_std()
+        .expect("parse_duration only returns positive durations");
+    let watched = |models: Vec<Model>| {
+        let included = models
+            .into_iter()
+            .filter(|model| why::is_included(model, args, metadata))
+            .collect();
+        filter_by_name(included, filters)
+    };
+
+    let models = watched(models);
+    eprintln!(
+        "Watching {} models for price changes every {interval}, starting from prices fetched {}. Press Ctrl-C to stop.",
+        models.len(),
+        source.fetched_at.format("%Y-%m-%d %H:%M UTC"),
+    );
+    let mut previous = snapshot(&models, &source);
+    loop {
+        tokio::time::sleep(period).await;
+        let catalog = match load_catalog(false, None).await {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                eprintln!("Warning: couldn't fetch prices: {e:#}");
+                continue;
+            }
+        };
+        let current = snapshot(&watched(catalog.models), &catalog.source);
+        if !current.same_as(&previous) {
+            diff::print(&previous, &current, format, &catalog.source, stale_after)?;
+            // Show each change as it happens when stdout is a pipe
+            std::io::stdout().flush()?;
+        }
+        previous = current;
+    }
+}
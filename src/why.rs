@@ -0,0 +1,153 @@
+use crate::{find_model, huggingface, license, matches_name_filters, Args, Metadata, Model};
+
+/// The outcome of one filtering stage for one model
+pub struct Check {
+    /// The flag or setting behind the stage, e.g. "--license apache-2.0"
+    pub stage: String,
+    /// Why the stage excluded the model, if it did
+    pub excluded: Option<String>,
+}
+
+/// Run the filters every command applies to the catalog, skipping ones that aren't in use
+pub fn catalog_checks(model: &Model, args: &Args, metadata: &Metadata) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if args.exclude_deprecated {
+        checks.push(Check {
+            stage: "--exclude-deprecated".to_string(),
+            excluded: metadata
+                .lifecycle
+                .get(&model.id)
+                .map(|entry| format!("model is {}", entry.status(metadata.today))),
+        });
+    }
+
+    if !args.license.is_empty() {
+        let model_license = metadata.license(model);
+        let matched = args
+            .license
+            .iter()
+            .any(|filter| license::matches(model_license, filter));
+        checks.push(Check {
+            stage: format!("--license {}", args.license.join(", ")),
+            excluded: (!matched).then(|| format!("license is {model_license}")),
+        });
+    }
+
+    if !args.approval.is_empty() {
+        let status = metadata.approval(model);
+        let matched = status.is_some_and(|status| args.approval.contains(&status));
+        let wanted: Vec<&str> = args.approval.iter().map(|status| status.as_str()).collect();
+        checks.push(Check {
+            stage: format!("--approval {}", wanted.join(", ")),
+            excluded: (!matched).then(|| match status {
+                Some(status) => format!("approval status is {}", status.as_str()),
+                None => "model has no approval status".to_string(),
+            }),
+        });
+    }
+
+    if !args.tag.is_empty() {
+        let tags = metadata.tags.of(model);
+        let matched = args
+            .tag
+            .iter()
+            .any(|wanted| tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted)));
+        checks.push(Check {
+            stage: format!("--tag {}", args.tag.join(", ")),
+            excluded: (!matched).then(|| match tags.as_slice() {
+                [] => "model has no tags".to_string(),
+                tags => format!("tags are {}", tags.join(", ")),
+            }),
+        });
+    }
+
+    if let Some(min_params) = args.min_params {
+        let params = metadata.hugging_face(model).and_then(|info| info.params);
+        checks.push(Check {
+            stage: format!("--min-params {}", huggingface::format_params(min_params)),
+            excluded: match params {
+                Some(params) if params >= min_params => None,
+                Some(params) => Some(format!(
+                    "model has {} parameters",
+                    huggingface::format_params(params)
+                )),
+                None => Some("parameter count isn't known".to_string()),
+            },
+        });
+    }
+
+    if !args.region.is_empty() {
+        checks.push(Check {
+            stage: format!("--region {}", args.region.join(", ")),
+            // Models whose endpoints couldn't be fetched, or that have providers missing from
+            // the region map, may still be served from the region
+            excluded: metadata
+                .residency
+                .get(&model.id)
+                .filter(|residency| {
+                    residency.unknown.is_empty() && !residency.served_from(&args.region)
+                })
+                .map(|residency| {
+                    let regions: Vec<&str> = residency.regions.iter().map(String::as_str).collect();
+                    if regions.is_empty() {
+                        "model has no endpoints".to_string()
+                    } else {
+                        format!("providers serve from {}", regions.join(", "))
+                    }
+                }),
+        });
+    }
+
+    checks
+}
+
+/// Whether a model passes every catalog filter
+pub fn is_included(model: &Model, args: &Args, metadata: &Metadata) -> bool {
+    catalog_checks(model, args, metadata)
+        .iter()
+        .all(|check| check.excluded.is_none())
+}
+
+/// Explain, stage by stage, whether `query` makes it into `list` output and what excludes it
+pub fn explain(
+    catalog: &[Model],
+    query: &str,
+    filters: &[String],
+    args: &Args,
+    metadata: &Metadata,
+) -> anyhow::Result<()> {
+    let model = find_model(catalog, query)?;
+
+    let mut checks = catalog_checks(model, args, metadata);
+    if !filters.is_empty() {
+        let filter_lowers: Vec<String> = filters.iter().map(|f| f.to_lowercase()).collect();
+        checks.push(Check {
+            stage: format!("name filter {}", filters.join(", ")),
+            excluded: (!matches_name_filters(model, &filter_lowers))
+                .then(|| "neither the ID nor the name contains a filter".to_string()),
+        });
+    }
+
+    println!("{}", model.id);
+    println!("  included  in the catalog");
+    for check in &checks {
+        match &check.excluded {
+            Some(reason) => println!("  EXCLUDED  {}: {reason}", check.stage),
+            None => println!("  included  {}", check.stage),
+        }
+    }
+
+    let excluded_by: Vec<&str> = checks
+        .iter()
+        .filter(|check| check.excluded.is_some())
+        .map(|check| check.stage.as_str())
+        .collect();
+    println!();
+    if excluded_by.is_empty() {
+        println!("{} would be listed", model.id);
+    } else {
+        println!("{} is excluded by {}", model.id, excluded_by.join("; "));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::table::Table;
+use anyhow::Context;
+use rust_xlsxwriter::Workbook;
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Excel's limit on sheet name length
+const MAX_SHEET_NAME: usize = 31;
+
+/// Where --output xlsx writes the workbook, from --out-file
+static OUT_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn set_out_file(path: PathBuf) {
+    let _ = OUT_FILE.set(path);
+}
+
+/// A valid, unique sheet name: at most 31 characters, without the characters Excel reserves.
+/// Excel compares names case-insensitively, so duplicates get a number.
+fn sheet_name(name: &str, used: &mut Vec<String>) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\') { '-' } else { c })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c: char| c == '\'' || c.is_whitespace());
+    let base = if cleaned.is_empty() { "Sheet" } else { cleaned };
+
+    let mut candidate: String = base.chars().take(MAX_SHEET_NAME).collect();
+    let mut number = 2;
+    while used.iter().any(|name| name.eq_ignore_ascii_case(&candidate)) {
+        let suffix = format!(" ({number})");
+        let kept = MAX_SHEET_NAME - suffix.len();
+        candidate = format!("{}{suffix}", base.chars().take(kept).collect::<String>());
+        number += 1;
+    }
+    used.push(candidate.clone());
+    candidate
+}
+
+/// Write each table to its own sheet of a workbook at `path`
+pub fn save(sheets: &[(&str, &Table)], path: &Path) -> anyhow::Result<()> {
+    let mut workbook = Workbook::new();
+    let mut used = Vec::new();
+    for (name, table) in sheets {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet_name(name, &mut used))?;
+        table.write_worksheet(worksheet)?;
+    }
+    workbook
+        .save(path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Write each table to its own sheet of a workbook at the --out-file path
+pub fn write(sheets: &[(&str, &Table)]) -> anyhow::Result<()> {
+    let path = OUT_FILE
+        .get()
+        .context("xlsx output needs a file to write; pass --out-file <PATH>")?;
+    save(sheets, path)?;
+
+    let plural = if sheets.len() == 1 { "" } else { "s" };
+    println!("Wrote {} sheet{plural} to {}", sheets.len(), path.display());
+    Ok(())
+}